@@ -0,0 +1,182 @@
+use crate::buffer::buffer;
+use crate::de::unexpected;
+use crate::policy::DispatchPolicy;
+use crate::replay::Replay;
+use serde::de::{Deserialize, Deserializer, Error, Visitor};
+use serde::ser::{Serialize, Serializer};
+use serde_value::Value;
+use std::fmt;
+
+/// A byte string or a struct, for binary formats such as CBOR or MessagePack
+/// that have a type of their own for blobs.
+///
+/// Byte strings are kept as they are, rather than going through `String`, and
+/// are serialized back as byte strings. Maps and sequences are handed to
+/// `S`, other values are rejected.
+///
+/// ```rust
+/// use serde::{Deserialize, Serialize};
+/// use serde_either::BytesOrStruct;
+///
+/// #[derive(Serialize, Deserialize, Debug, PartialEq)]
+/// struct Link {
+///     url: String,
+/// }
+///
+/// let res: BytesOrStruct<Link> = serde_json::from_str(r#"{"url": "a"}"#).unwrap();
+///
+/// assert_eq!(res.as_struct(), Some(&Link { url: String::from("a") }));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum BytesOrStruct<S> {
+    Bytes(Vec<u8>),
+    Struct(S),
+}
+
+impl<S> BytesOrStruct<S> {
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Self::Bytes(b) => Some(b),
+            Self::Struct(_) => None,
+        }
+    }
+
+    pub fn as_struct(&self) -> Option<&S> {
+        match self {
+            Self::Bytes(_) => None,
+            Self::Struct(s) => Some(s),
+        }
+    }
+}
+
+impl<'de, S> Deserialize<'de> for BytesOrStruct<S>
+where
+    S: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let human_readable = deserializer.is_human_readable();
+        match buffer(deserializer, &DispatchPolicy::default())? {
+            Value::Bytes(b) => Ok(Self::Bytes(b)),
+            value @ Value::Map(_) | value @ Value::Seq(_) => {
+                S::deserialize(Replay::new(value, human_readable)).map(Self::Struct)
+            }
+            value => Err(D::Error::invalid_type(
+                unexpected(&value),
+                &"bytes, a map or a sequence",
+            )),
+        }
+    }
+}
+
+impl<S> Serialize for BytesOrStruct<S>
+where
+    S: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            BytesOrStruct::Bytes(b) => serializer.serialize_bytes(b),
+            BytesOrStruct::Struct(s) => s.serialize(serializer),
+        }
+    }
+}
+
+/// A text string or a byte string, for binary formats such as CBOR that tell
+/// them apart.
+///
+/// Each arm is serialized back as the same kind of string, so values round
+/// trip without going through a lossy conversion.
+///
+/// ```rust
+/// use serde_either::StringOrBytes;
+///
+/// let text = StringOrBytes::String(String::from("a"));
+///
+/// assert_eq!(text.as_bytes(), b"a");
+/// assert_eq!(StringOrBytes::Bytes(vec![0xff]).as_str(), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum StringOrBytes {
+    String(String),
+    Bytes(Vec<u8>),
+}
+
+impl StringOrBytes {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s),
+            Self::Bytes(_) => None,
+        }
+    }
+
+    /// Returns the bytes of either arm, text being encoded as UTF-8.
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            Self::String(s) => s.as_bytes(),
+            Self::Bytes(b) => b,
+        }
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        match self {
+            Self::String(s) => s.into_bytes(),
+            Self::Bytes(b) => b,
+        }
+    }
+}
+
+struct StringOrBytesVisitor;
+
+impl<'de> Visitor<'de> for StringOrBytesVisitor {
+    type Value = StringOrBytes;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string or bytes")
+    }
+
+    fn visit_char<E: Error>(self, v: char) -> Result<Self::Value, E> {
+        Ok(StringOrBytes::String(v.to_string()))
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(StringOrBytes::String(v.to_owned()))
+    }
+
+    fn visit_string<E: Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(StringOrBytes::String(v))
+    }
+
+    fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(StringOrBytes::Bytes(v.to_owned()))
+    }
+
+    fn visit_byte_buf<E: Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(StringOrBytes::Bytes(v))
+    }
+}
+
+impl<'de> Deserialize<'de> for StringOrBytes {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(StringOrBytesVisitor)
+    }
+}
+
+impl Serialize for StringOrBytes {
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            StringOrBytes::String(s) => serializer.serialize_str(s),
+            StringOrBytes::Bytes(b) => serializer.serialize_bytes(b),
+        }
+    }
+}
@@ -0,0 +1,531 @@
+use crate::enums::{StringOrStruct, StringOrStructOrVec};
+use serde::de::value::{
+    BorrowedBytesDeserializer, BorrowedStrDeserializer, BytesDeserializer, EnumAccessDeserializer,
+    MapAccessDeserializer, SeqAccessDeserializer,
+};
+use serde::de::{
+    Deserialize, Deserializer, EnumAccess, Error, IntoDeserializer, MapAccess, SeqAccess, Visitor,
+};
+use serde::ser::{Serialize, Serializer};
+use std::borrow::Cow;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// A [`StringOrStruct`] whose string borrows from the input when the format
+/// allows it, saving an allocation per string shorthand in large documents.
+///
+/// The input is not buffered: strings are borrowed, or copied when they
+/// contain escapes or the format cannot lend them, and maps and sequences
+/// are handed straight to `S`. Fields of this type need `#[serde(borrow)]`,
+/// and the type cannot be deserialized from readers: use [`StringOrStruct`]
+/// there.
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use serde_either::CowStringOrStruct;
+/// use std::borrow::Cow;
+///
+/// #[derive(Deserialize)]
+/// struct Author {
+///     name: String,
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct Book<'a> {
+///     #[serde(borrow)]
+///     author: CowStringOrStruct<'a, Author>,
+/// }
+///
+/// let book: Book = serde_json::from_str(r#"{"author": "John Smith"}"#).unwrap();
+///
+/// assert!(matches!(book.author, CowStringOrStruct::String(Cow::Borrowed("John Smith"))));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum CowStringOrStruct<'a, S> {
+    String(Cow<'a, str>),
+    Struct(S),
+}
+
+impl<'a, S> CowStringOrStruct<'a, S> {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s),
+            Self::Struct(_) => None,
+        }
+    }
+
+    pub fn as_struct(&self) -> Option<&S> {
+        match self {
+            Self::String(_) => None,
+            Self::Struct(s) => Some(s),
+        }
+    }
+
+    /// Copies a borrowed string, so the value no longer borrows the input.
+    pub fn into_owned(self) -> StringOrStruct<S> {
+        match self {
+            Self::String(s) => StringOrStruct::String(s.into_owned()),
+            Self::Struct(s) => StringOrStruct::Struct(s),
+        }
+    }
+}
+
+impl<S> From<StringOrStruct<S>> for CowStringOrStruct<'_, S> {
+    fn from(value: StringOrStruct<S>) -> Self {
+        match value {
+            StringOrStruct::String(s) => Self::String(Cow::Owned(s)),
+            StringOrStruct::Struct(s) => Self::Struct(s),
+        }
+    }
+}
+
+struct CowStringOrStructVisitor<'a, S>(PhantomData<fn() -> CowStringOrStruct<'a, S>>);
+
+impl<'de: 'a, 'a, S> Visitor<'de> for CowStringOrStructVisitor<'a, S>
+where
+    S: Deserialize<'de>,
+{
+    type Value = CowStringOrStruct<'a, S>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("String or Struct")
+    }
+
+    fn visit_borrowed_str<E: Error>(self, v: &'de str) -> Result<Self::Value, E> {
+        Ok(CowStringOrStruct::String(Cow::Borrowed(v)))
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(CowStringOrStruct::String(Cow::Owned(v.to_owned())))
+    }
+
+    fn visit_string<E: Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(CowStringOrStruct::String(Cow::Owned(v)))
+    }
+
+    // sequences are handed to the Struct arm, as for StringOrStruct
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        S::deserialize(SeqAccessDeserializer::new(seq)).map(CowStringOrStruct::Struct)
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        S::deserialize(MapAccessDeserializer::new(map)).map(CowStringOrStruct::Struct)
+    }
+}
+
+impl<'de: 'a, 'a, S> Deserialize<'de> for CowStringOrStruct<'a, S>
+where
+    S: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(CowStringOrStructVisitor(PhantomData))
+    }
+}
+
+impl<S> Serialize for CowStringOrStruct<'_, S>
+where
+    S: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            CowStringOrStruct::String(s) => serializer.serialize_str(s),
+            CowStringOrStruct::Struct(s) => s.serialize(serializer),
+        }
+    }
+}
+
+/// A [`StringOrStruct`] that borrows from the input: its string is a `&str`
+/// slice of the input, and `S` may borrow too, for instance with `&str` fields.
+///
+/// The input is not buffered, so `#[serde(borrow)]` works down to the
+/// struct. Strings that cannot be borrowed, because they contain escapes or
+/// come from a reader, are rejected like they are for `&str`.
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use serde_either::StringOrStructRef;
+///
+/// #[derive(Deserialize, Debug, PartialEq)]
+/// struct Author<'a> {
+///     name: &'a str,
+/// }
+///
+/// let input = String::from(r#"[{"name": "John"}, "Michael"]"#);
+/// let res: Vec<StringOrStructRef<Author>> = serde_json::from_str(&input).unwrap();
+///
+/// assert_eq!(res[0].as_struct(), Some(&Author { name: "John" }));
+/// assert_eq!(res[1].as_str(), Some("Michael"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum StringOrStructRef<'a, S> {
+    String(&'a str),
+    Struct(S),
+}
+
+impl<'a, S> StringOrStructRef<'a, S> {
+    pub fn as_str(&self) -> Option<&'a str> {
+        match *self {
+            Self::String(s) => Some(s),
+            Self::Struct(_) => None,
+        }
+    }
+
+    pub fn as_struct(&self) -> Option<&S> {
+        match self {
+            Self::String(_) => None,
+            Self::Struct(s) => Some(s),
+        }
+    }
+
+    /// Copies the string, so the value no longer borrows the input when `S`
+    /// does not.
+    pub fn into_owned(self) -> StringOrStruct<S> {
+        match self {
+            Self::String(s) => StringOrStruct::String(s.to_owned()),
+            Self::Struct(s) => StringOrStruct::Struct(s),
+        }
+    }
+}
+
+struct StringOrStructRefVisitor<'a, S>(PhantomData<fn() -> StringOrStructRef<'a, S>>);
+
+impl<'de: 'a, 'a, S> Visitor<'de> for StringOrStructRefVisitor<'a, S>
+where
+    S: Deserialize<'de>,
+{
+    type Value = StringOrStructRef<'a, S>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("borrowed String or Struct")
+    }
+
+    fn visit_borrowed_str<E: Error>(self, v: &'de str) -> Result<Self::Value, E> {
+        Ok(StringOrStructRef::String(v))
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        S::deserialize(SeqAccessDeserializer::new(seq)).map(StringOrStructRef::Struct)
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        S::deserialize(MapAccessDeserializer::new(map)).map(StringOrStructRef::Struct)
+    }
+}
+
+impl<'de: 'a, 'a, S> Deserialize<'de> for StringOrStructRef<'a, S>
+where
+    S: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(StringOrStructRefVisitor(PhantomData))
+    }
+}
+
+impl<S> Serialize for StringOrStructRef<'_, S>
+where
+    S: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            StringOrStructRef::String(s) => serializer.serialize_str(s),
+            StringOrStructRef::Struct(s) => s.serialize(serializer),
+        }
+    }
+}
+
+/// A [`StringOrStructOrVec`] that borrows from the input, like
+/// [`StringOrStructRef`]: `S` and `V` may borrow too.
+///
+/// ```rust
+/// use serde_either::StringOrStructOrVecRef;
+/// use std::collections::HashMap;
+///
+/// type Field<'a> = StringOrStructOrVecRef<'a, HashMap<&'a str, &'a str>, Vec<&'a str>>;
+///
+/// let input = String::from(r#"["a", {"b": "c"}, ["d", "e"]]"#);
+/// let res: Vec<Field> = serde_json::from_str(&input).unwrap();
+///
+/// assert_eq!(res[0].as_str(), Some("a"));
+/// assert_eq!(res[1].as_struct().map(|map| map["b"]), Some("c"));
+/// assert_eq!(res[2].as_vec(), Some(&vec!["d", "e"]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum StringOrStructOrVecRef<'a, S, V> {
+    String(&'a str),
+    Struct(S),
+    Vec(V),
+}
+
+impl<'a, S, V> StringOrStructOrVecRef<'a, S, V> {
+    pub fn as_str(&self) -> Option<&'a str> {
+        match *self {
+            Self::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_struct(&self) -> Option<&S> {
+        match self {
+            Self::Struct(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_vec(&self) -> Option<&V> {
+        match self {
+            Self::Vec(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Copies the string, so the value no longer borrows the input when `S`
+    /// and `V` do not.
+    pub fn into_owned(self) -> StringOrStructOrVec<S, V> {
+        match self {
+            Self::String(s) => StringOrStructOrVec::String(s.to_owned()),
+            Self::Struct(s) => StringOrStructOrVec::Struct(s),
+            Self::Vec(v) => StringOrStructOrVec::Vec(v),
+        }
+    }
+}
+
+struct StringOrStructOrVecRefVisitor<'a, S, V>(
+    PhantomData<fn() -> StringOrStructOrVecRef<'a, S, V>>,
+);
+
+impl<'de: 'a, 'a, S, V> Visitor<'de> for StringOrStructOrVecRefVisitor<'a, S, V>
+where
+    S: Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    type Value = StringOrStructOrVecRef<'a, S, V>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("borrowed String, Struct or Vec")
+    }
+
+    fn visit_borrowed_str<E: Error>(self, v: &'de str) -> Result<Self::Value, E> {
+        Ok(StringOrStructOrVecRef::String(v))
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        V::deserialize(SeqAccessDeserializer::new(seq)).map(StringOrStructOrVecRef::Vec)
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        S::deserialize(MapAccessDeserializer::new(map)).map(StringOrStructOrVecRef::Struct)
+    }
+}
+
+impl<'de: 'a, 'a, S, V> Deserialize<'de> for StringOrStructOrVecRef<'a, S, V>
+where
+    S: Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(StringOrStructOrVecRefVisitor(PhantomData))
+    }
+}
+
+impl<S, V> Serialize for StringOrStructOrVecRef<'_, S, V>
+where
+    S: Serialize,
+    V: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            StringOrStructOrVecRef::String(s) => serializer.serialize_str(s),
+            StringOrStructOrVecRef::Struct(s) => s.serialize(serializer),
+            StringOrStructOrVecRef::Vec(v) => v.serialize(serializer),
+        }
+    }
+}
+
+/// A [`SingleOrVec`](crate::SingleOrVec) whose elements may borrow from the
+/// input.
+///
+/// The input is not buffered: sequences are deserialized as a `Vec<S>`, and
+/// anything else is handed straight to `S`.
+///
+/// ```rust
+/// use serde_either::SingleOrVecRef;
+///
+/// let input = String::from(r#"["a", "b"]"#);
+/// let res: SingleOrVecRef<&str> = serde_json::from_str(&input).unwrap();
+///
+/// assert_eq!(res, SingleOrVecRef::Vec(vec!["a", "b"]));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SingleOrVecRef<S> {
+    Single(S),
+    Vec(Vec<S>),
+}
+
+impl<S> SingleOrVecRef<S> {
+    pub fn into_vec(self) -> Vec<S> {
+        match self {
+            Self::Single(s) => vec![s],
+            Self::Vec(v) => v,
+        }
+    }
+}
+
+struct SingleOrVecRefVisitor<S>(PhantomData<fn() -> S>);
+
+macro_rules! single {
+    ($($method:ident($ty:ty)),* $(,)?) => {
+        $(
+            fn $method<E: Error>(self, v: $ty) -> Result<Self::Value, E> {
+                S::deserialize(v.into_deserializer()).map(SingleOrVecRef::Single)
+            }
+        )*
+    };
+}
+
+impl<'de, S> Visitor<'de> for SingleOrVecRefVisitor<S>
+where
+    S: Deserialize<'de>,
+{
+    type Value = SingleOrVecRef<S>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("Single or Vec")
+    }
+
+    single! {
+        visit_bool(bool),
+        visit_i8(i8),
+        visit_i16(i16),
+        visit_i32(i32),
+        visit_i64(i64),
+        visit_i128(i128),
+        visit_u8(u8),
+        visit_u16(u16),
+        visit_u32(u32),
+        visit_u64(u64),
+        visit_u128(u128),
+        visit_f32(f32),
+        visit_f64(f64),
+        visit_char(char),
+        visit_str(&str),
+        visit_string(String),
+    }
+
+    fn visit_borrowed_str<E: Error>(self, v: &'de str) -> Result<Self::Value, E> {
+        S::deserialize(BorrowedStrDeserializer::new(v)).map(SingleOrVecRef::Single)
+    }
+
+    fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        S::deserialize(BytesDeserializer::new(v)).map(SingleOrVecRef::Single)
+    }
+
+    fn visit_borrowed_bytes<E: Error>(self, v: &'de [u8]) -> Result<Self::Value, E> {
+        S::deserialize(BorrowedBytesDeserializer::new(v)).map(SingleOrVecRef::Single)
+    }
+
+    fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+        S::deserialize(().into_deserializer()).map(SingleOrVecRef::Single)
+    }
+
+    fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+        self.visit_unit()
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        S::deserialize(deserializer).map(SingleOrVecRef::Single)
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        S::deserialize(deserializer).map(SingleOrVecRef::Single)
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        Vec::<S>::deserialize(SeqAccessDeserializer::new(seq)).map(SingleOrVecRef::Vec)
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        S::deserialize(MapAccessDeserializer::new(map)).map(SingleOrVecRef::Single)
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        S::deserialize(EnumAccessDeserializer::new(data)).map(SingleOrVecRef::Single)
+    }
+}
+
+impl<'de, S> Deserialize<'de> for SingleOrVecRef<S>
+where
+    S: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(SingleOrVecRefVisitor(PhantomData))
+    }
+}
+
+impl<S> Serialize for SingleOrVecRef<S>
+where
+    S: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            SingleOrVecRef::Single(s) => s.serialize(serializer),
+            SingleOrVecRef::Vec(v) => v.serialize(serializer),
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use crate::policy::{DeserializeWithPolicy, DispatchPolicy};
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+use std::ops::{Deref, DerefMut};
+
+/// Wraps one of the enums of this crate, limiting how much input is
+/// buffered while it is deserialized.
+///
+/// Strings (and byte strings) longer than `MAX_STRING_LEN` bytes, and
+/// sequences or maps with more than `MAX_SEQ_LEN` entries, are rejected with
+/// an error as soon as they are encountered. Use
+/// [`DispatchPolicy::max_string_len`] and [`DispatchPolicy::max_seq_len`] to
+/// configure the same limits at runtime.
+///
+/// ```rust
+/// use serde_either::{Bounded, StringOrStruct};
+/// use std::collections::HashMap;
+///
+/// type Field = Bounded<StringOrStruct<HashMap<String, String>>, 8, 16>;
+///
+/// assert!(serde_json::from_str::<Field>(r#""short""#).is_ok());
+/// assert!(serde_json::from_str::<Field>(r#""much too long""#).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bounded<T, const MAX_STRING_LEN: usize, const MAX_SEQ_LEN: usize>(pub T);
+
+impl<T, const MAX_STRING_LEN: usize, const MAX_SEQ_LEN: usize>
+    Bounded<T, MAX_STRING_LEN, MAX_SEQ_LEN>
+{
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T, const MAX_STRING_LEN: usize, const MAX_SEQ_LEN: usize> Deref
+    for Bounded<T, MAX_STRING_LEN, MAX_SEQ_LEN>
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T, const MAX_STRING_LEN: usize, const MAX_SEQ_LEN: usize> DerefMut
+    for Bounded<T, MAX_STRING_LEN, MAX_SEQ_LEN>
+{
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<'de, T, const MAX_STRING_LEN: usize, const MAX_SEQ_LEN: usize> Deserialize<'de>
+    for Bounded<T, MAX_STRING_LEN, MAX_SEQ_LEN>
+where
+    T: DeserializeWithPolicy<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let policy = DispatchPolicy::new()
+            .max_string_len(MAX_STRING_LEN)
+            .max_seq_len(MAX_SEQ_LEN);
+        T::deserialize_with_policy(deserializer, &policy).map(Bounded)
+    }
+}
+
+impl<T, const MAX_STRING_LEN: usize, const MAX_SEQ_LEN: usize> Serialize
+    for Bounded<T, MAX_STRING_LEN, MAX_SEQ_LEN>
+where
+    T: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
@@ -0,0 +1,251 @@
+use crate::policy::DispatchPolicy;
+use serde::de::{
+    DeserializeSeed, Deserializer, EnumAccess, Error, MapAccess, SeqAccess, VariantAccess, Visitor,
+};
+use serde_value::Value;
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Buffers any value into a `serde_value::Value`, enforcing the limits of
+/// the policy while doing so.
+pub(crate) fn buffer<'de, D>(deserializer: D, policy: &DispatchPolicy) -> Result<Value, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    BufferSeed::new(policy).deserialize(deserializer)
+}
+
+// mirrors serde-value's ValueVisitor, see https://github.com/arcnmx/serde-value/blob/master/src/de.rs
+#[derive(Clone, Copy)]
+pub(crate) struct BufferSeed<'a> {
+    policy: &'a DispatchPolicy,
+    depth: usize,
+}
+
+impl<'a> BufferSeed<'a> {
+    pub(crate) fn new(policy: &'a DispatchPolicy) -> Self {
+        BufferSeed { policy, depth: 0 }
+    }
+
+    // returns the seed for the values nested in the current one
+    pub(crate) fn nested<E: Error>(self) -> Result<Self, E> {
+        let depth = self.depth + 1;
+        if depth > self.policy.depth_limit() {
+            return Err(E::custom(format_args!(
+                "value exceeds the maximum nesting depth of {}",
+                self.policy.depth_limit()
+            )));
+        }
+        Ok(BufferSeed { depth, ..self })
+    }
+
+    fn check_string_len<E: Error>(&self, len: usize) -> Result<(), E> {
+        match self.policy.string_len_limit() {
+            Some(max) if len > max => Err(E::custom(format_args!(
+                "string of length {} exceeds the maximum length of {}",
+                len, max
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_seq_len<E: Error>(&self, len: usize) -> Result<(), E> {
+        match self.policy.seq_len_limit() {
+            Some(max) if len > max => Err(E::custom(format_args!(
+                "sequence exceeds the maximum length of {}",
+                max
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<'de, 'a> DeserializeSeed<'de> for BufferSeed<'a> {
+    type Value = Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de, 'a> Visitor<'de> for BufferSeed<'a> {
+    type Value = Value;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str("any value")
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<Value, E> {
+        Ok(Value::Bool(value))
+    }
+
+    fn visit_i8<E>(self, value: i8) -> Result<Value, E> {
+        Ok(Value::I8(value))
+    }
+
+    fn visit_i16<E>(self, value: i16) -> Result<Value, E> {
+        Ok(Value::I16(value))
+    }
+
+    fn visit_i32<E>(self, value: i32) -> Result<Value, E> {
+        Ok(Value::I32(value))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Value, E> {
+        Ok(Value::I64(value))
+    }
+
+    fn visit_u8<E>(self, value: u8) -> Result<Value, E> {
+        Ok(Value::U8(value))
+    }
+
+    fn visit_u16<E>(self, value: u16) -> Result<Value, E> {
+        Ok(Value::U16(value))
+    }
+
+    fn visit_u32<E>(self, value: u32) -> Result<Value, E> {
+        Ok(Value::U32(value))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Value, E> {
+        Ok(Value::U64(value))
+    }
+
+    fn visit_f32<E>(self, value: f32) -> Result<Value, E> {
+        Ok(Value::F32(value))
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<Value, E> {
+        Ok(Value::F64(value))
+    }
+
+    fn visit_char<E>(self, value: char) -> Result<Value, E> {
+        Ok(Value::Char(value))
+    }
+
+    fn visit_str<E: Error>(self, value: &str) -> Result<Value, E> {
+        self.check_string_len(value.len())?;
+        Ok(Value::String(value.into()))
+    }
+
+    fn visit_string<E: Error>(self, value: String) -> Result<Value, E> {
+        self.check_string_len(value.len())?;
+        Ok(Value::String(value))
+    }
+
+    fn visit_unit<E>(self) -> Result<Value, E> {
+        Ok(Value::Unit)
+    }
+
+    fn visit_none<E>(self) -> Result<Value, E> {
+        Ok(Value::Option(None))
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let nested = self.nested()?;
+        deserializer
+            .deserialize_any(nested)
+            .map(|v| Value::Option(Some(Box::new(v))))
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let nested = self.nested()?;
+        deserializer
+            .deserialize_any(nested)
+            .map(|v| Value::Newtype(Box::new(v)))
+    }
+
+    fn visit_seq<A>(self, mut access: A) -> Result<Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let nested = self.nested()?;
+        let mut values = Vec::new();
+        while let Some(elem) = access.next_element_seed(nested)? {
+            values.push(elem);
+            self.check_seq_len(values.len())?;
+        }
+        Ok(Value::Seq(values))
+    }
+
+    fn visit_map<A>(self, mut access: A) -> Result<Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let nested = self.nested()?;
+        let mut values = BTreeMap::new();
+        while let Some((key, value)) = access.next_entry_seed(nested, nested)? {
+            values.insert(key, value);
+            self.check_seq_len(values.len())?;
+        }
+        Ok(Value::Map(values))
+    }
+
+    // enums, such as yaml's tagged values (`!Tag value`), are buffered in their
+    // externally tagged form: a map holding the tag and its content
+    //
+    // the content is read as a newtype variant whatever its shape, as self
+    // describing formats hand it to `nested` alike. Input that only supports
+    // unit variants rejects that without reading anything, and is buffered
+    // with a unit content
+    fn visit_enum<A>(self, access: A) -> Result<Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        let nested = self.nested()?;
+        let (tag, variant) = access.variant_seed(nested)?;
+        let read = Cell::new(false);
+        let content = match variant.newtype_variant_seed(Tracked {
+            seed: nested,
+            read: &read,
+        }) {
+            Ok(content) => content,
+            Err(_) if !read.get() => Value::Unit,
+            Err(e) => return Err(e),
+        };
+        let mut values = BTreeMap::new();
+        values.insert(tag, content);
+        Ok(Value::Map(values))
+    }
+
+    fn visit_bytes<E: Error>(self, value: &[u8]) -> Result<Value, E> {
+        self.check_string_len(value.len())?;
+        Ok(Value::Bytes(value.into()))
+    }
+
+    fn visit_byte_buf<E: Error>(self, value: Vec<u8>) -> Result<Value, E> {
+        self.check_string_len(value.len())?;
+        Ok(Value::Bytes(value))
+    }
+}
+
+// a seed recording whether it was handed a deserializer
+struct Tracked<'a, S> {
+    seed: S,
+    read: &'a Cell<bool>,
+}
+
+impl<'de, 'a, S> DeserializeSeed<'de> for Tracked<'a, S>
+where
+    S: DeserializeSeed<'de>,
+{
+    type Value = S::Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<S::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.read.set(true);
+        self.seed.deserialize(deserializer)
+    }
+}
@@ -1,11 +1,17 @@
-use crate::enums::{SingleOrVec, StringOrStruct, StringOrStructOrVec};
+use crate::enums::{OneOrMany, SingleOrVec, StringOrStruct, StringOrStructOrVec};
+use crate::kind::Kind;
+use crate::policy::{DeserializeWithPolicy, DispatchPolicy};
+use crate::replay::Replay;
+use crate::stream::{dispatch, Dispatch};
+use crate::warnings::{warn, Warning};
 use serde::de::{Deserialize, Deserializer, Error, Expected, Unexpected};
-use serde_value::{Value, ValueDeserializer};
+use serde_value::Value;
+use std::fmt;
 
 // the unexpected function was copied from https://github.com/arcnmx/serde-value/blob/master/src/lib.rs
 // note that serde-value is licensed under MIT https://github.com/arcnmx/serde-value/blob/master/COPYING
 // credit goes to arcnmx
-fn unexpected(value: &Value) -> Unexpected {
+pub(crate) fn unexpected(value: &Value) -> Unexpected<'_> {
     match *value {
         Value::Bool(b) => serde::de::Unexpected::Bool(b),
         Value::U8(n) => serde::de::Unexpected::Unsigned(n as u64),
@@ -29,27 +35,230 @@ fn unexpected(value: &Value) -> Unexpected {
     }
 }
 
-impl<'de, S, V> StringOrStructOrVec<S, V>
+fn number_to_string(value: &Value) -> Option<String> {
+    match *value {
+        Value::U8(n) => Some(n.to_string()),
+        Value::U16(n) => Some(n.to_string()),
+        Value::U32(n) => Some(n.to_string()),
+        Value::U64(n) => Some(n.to_string()),
+        Value::I8(n) => Some(n.to_string()),
+        Value::I16(n) => Some(n.to_string()),
+        Value::I32(n) => Some(n.to_string()),
+        Value::I64(n) => Some(n.to_string()),
+        Value::F32(n) => Some(n.to_string()),
+        Value::F64(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+// lists the kinds a type accepts under a policy, e.g. "String, Struct or Vec"
+pub(crate) struct Expecting<'a> {
+    pub(crate) kinds: &'a [Kind],
+    pub(crate) policy: &'a DispatchPolicy,
+}
+
+impl<'a> Expected for Expecting<'a> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let allowed: Vec<&str> = self
+            .kinds
+            .iter()
+            .filter(|kind| self.policy.is_allowed(**kind))
+            .map(Kind::as_str)
+            .collect();
+
+        match allowed.split_last() {
+            None => formatter.write_str("nothing"),
+            Some((last, [])) => formatter.write_str(last),
+            Some((last, rest)) => write!(formatter, "{} or {}", rest.join(", "), last),
+        }
+    }
+}
+
+fn ensure_allowed<E: Error>(
+    kind: Kind,
+    value: Value,
+    kinds: &[Kind],
+    policy: &DispatchPolicy,
+) -> Result<(Kind, Value), E> {
+    if policy.is_allowed(kind) {
+        match (kind, &value) {
+            (Kind::String, _) => warn(Warning::StringForm),
+            (Kind::Vec, Value::Seq(items)) if items.is_empty() => warn(Warning::EmptyVec),
+            _ => {}
+        }
+        Ok((kind, value))
+    } else {
+        Err(Error::invalid_type(
+            unexpected(&value),
+            &Expecting { kinds, policy },
+        ))
+    }
+}
+
+fn unwrap_seq_of_one(value: Value, policy: &DispatchPolicy) -> Result<Value, Value> {
+    match value {
+        Value::Seq(mut items) if policy.is_seq_of_one_as_struct() && items.len() == 1 => {
+            warn(Warning::SeqOfOneAsStruct);
+            Ok(items.remove(0))
+        }
+        _ => Err(value),
+    }
+}
+
+// toml encodes datetimes as a map with a single private key holding their text
+const TOML_DATETIME_KEY: &str = "$__toml_private_datetime";
+
+// serde_json's arbitrary_precision feature encodes numbers as a map with a
+// single private key holding their text
+pub(crate) const NUMBER_TOKEN: &str = "$serde_json::private::Number";
+
+pub(crate) fn number_token(value: &Value) -> Option<&str> {
+    match value {
+        Value::Map(map) if map.len() == 1 => match map.iter().next() {
+            Some((Value::String(key), Value::String(text))) if key == NUMBER_TOKEN => Some(text),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// tells whether a map key marks a format-specific encoding of a scalar
+pub(crate) fn is_private_key(key: &Value) -> bool {
+    match key {
+        Value::String(key) => key == TOML_DATETIME_KEY || key == NUMBER_TOKEN,
+        _ => false,
+    }
+}
+
+// returns the text of format-specific encodings of strings, such as toml datetimes
+fn passthrough_string(value: &Value) -> Option<&str> {
+    match value {
+        Value::Map(map) if map.len() == 1 => match map.iter().next() {
+            Some((Value::String(key), Value::String(text))) if key == TOML_DATETIME_KEY => {
+                Some(text)
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Deserializes the Struct arm. Format-specific encodings are passed to `S`
+/// untouched, and fall back to their text with a warning when `S` rejects
+/// them.
+pub(crate) fn struct_or_passthrough<'de, S, E>(
+    value: Value,
+    human_readable: bool,
+) -> Result<Result<S, String>, E>
+where
+    S: Deserialize<'de>,
+    E: Error,
+{
+    match passthrough_string(&value).map(str::to_owned) {
+        Some(text) => Ok(
+            S::deserialize(Replay::<E>::new(value, human_readable)).map_err(|_| {
+                warn(Warning::DatetimeAsString);
+                warn(Warning::StringForm);
+                text
+            }),
+        ),
+        None => S::deserialize(Replay::new(value, human_readable)).map(Ok),
+    }
+}
+
+// classifies a buffered value for the types that carry a String arm
+pub(crate) fn classify<E: Error>(
+    value: Value,
+    kinds: &[Kind],
+    policy: &DispatchPolicy,
+) -> Result<(Kind, Value), E> {
+    let value = match unwrap_seq_of_one(value, policy) {
+        Ok(item) => return ensure_allowed(Kind::Struct, item, kinds, policy),
+        Err(value) => value,
+    };
+    match value {
+        Value::String(_) | Value::Bytes(_) => ensure_allowed(Kind::String, value, kinds, policy),
+        Value::Char(c) => ensure_allowed(Kind::String, Value::String(c.to_string()), kinds, policy),
+        Value::Seq(_) if kinds.contains(&Kind::Vec) => {
+            ensure_allowed(Kind::Vec, value, kinds, policy)
+        }
+        // without a Vec arm, sequences belong to the Struct arm
+        Value::Seq(_) => ensure_allowed(Kind::Struct, value, kinds, policy),
+        Value::Map(_) => match number_token(&value) {
+            Some(text) if policy.is_numbers_as_string() => {
+                warn(Warning::NumberAsString);
+                let text = Value::String(text.to_owned());
+                ensure_allowed(Kind::String, text, kinds, policy)
+            }
+            // kept as is, so that types supporting arbitrary precision get every digit
+            Some(_) => ensure_allowed(Kind::Struct, value, kinds, policy),
+            None => match passthrough_string(&value) {
+                Some(text) if !policy.is_allowed(Kind::Struct) => {
+                    let text = Value::String(text.to_owned());
+                    ensure_allowed(Kind::String, text, kinds, policy)
+                }
+                _ => ensure_allowed(Kind::Struct, value, kinds, policy),
+            },
+        },
+        _ => match number_to_string(&value) {
+            Some(s) if policy.is_numbers_as_string() => {
+                warn(Warning::NumberAsString);
+                ensure_allowed(Kind::String, Value::String(s), kinds, policy)
+            }
+            _ => Err(Error::invalid_type(
+                unexpected(&value),
+                &Expecting { kinds, policy },
+            )),
+        },
+    }
+}
+
+impl<'de, S, V> Dispatch<'de> for StringOrStructOrVec<S, V>
 where
     S: Deserialize<'de>,
     V: Deserialize<'de>,
 {
-    fn deserialize_with_expected<D>(
+    const KINDS: &'static [Kind] = &[Kind::String, Kind::Struct, Kind::Vec];
+
+    fn from_buffered<E: Error>(
+        value: Value,
+        policy: &DispatchPolicy,
+        human_readable: bool,
+    ) -> Result<Self, E> {
+        match classify(value, Self::KINDS, policy)? {
+            (Kind::String, value) => Ok(Self::String(String::deserialize(Replay::new(
+                value,
+                human_readable,
+            ))?)),
+            (Kind::Struct, value) => match struct_or_passthrough(value, human_readable)? {
+                Ok(s) => Ok(Self::Struct(s)),
+                Err(text) => Ok(Self::String(text)),
+            },
+            (Kind::Vec, value) => Ok(Self::Vec(V::deserialize(Replay::new(
+                value,
+                human_readable,
+            ))?)),
+        }
+    }
+
+    fn from_struct<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        S::deserialize(deserializer).map(Self::Struct)
+    }
+}
+
+impl<'de, S, V> DeserializeWithPolicy<'de> for StringOrStructOrVec<S, V>
+where
+    S: Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    fn deserialize_with_policy<D>(
         deserializer: D,
-        expected: &dyn Expected,
+        policy: &DispatchPolicy,
     ) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        let value = Value::deserialize(deserializer)?;
-        return match value {
-            Value::String(_) | Value::Bytes(_) => Ok(Self::String(String::deserialize(
-                ValueDeserializer::new(value),
-            )?)),
-            Value::Seq(_) => Ok(Self::Vec(V::deserialize(ValueDeserializer::new(value))?)),
-            Value::Map(_) => Ok(Self::Struct(S::deserialize(ValueDeserializer::new(value))?)),
-            _ => Err(Error::invalid_type(unexpected(&value), expected)),
-        };
+        dispatch(deserializer, policy)
     }
 }
 
@@ -62,10 +271,52 @@ where
     where
         D: Deserializer<'de>,
     {
-        StringOrStructOrVec::<S, V>::deserialize_with_expected(
-            deserializer,
-            &"String, Struct or Vec",
-        )
+        Self::deserialize_with_policy(deserializer, &DispatchPolicy::default())
+    }
+}
+
+impl<'de, S> Dispatch<'de> for StringOrStruct<S>
+where
+    S: Deserialize<'de>,
+{
+    const KINDS: &'static [Kind] = &[Kind::String, Kind::Struct];
+
+    // sequences are handed to the Struct arm, so `S` can itself be a Vec
+    fn from_buffered<E: Error>(
+        value: Value,
+        policy: &DispatchPolicy,
+        human_readable: bool,
+    ) -> Result<Self, E> {
+        match classify(value, Self::KINDS, policy)? {
+            (Kind::String, value) => Ok(Self::String(String::deserialize(Replay::new(
+                value,
+                human_readable,
+            ))?)),
+            // classify only returns the kinds listed in KINDS
+            (_, value) => match struct_or_passthrough(value, human_readable)? {
+                Ok(s) => Ok(Self::Struct(s)),
+                Err(text) => Ok(Self::String(text)),
+            },
+        }
+    }
+
+    fn from_struct<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        S::deserialize(deserializer).map(Self::Struct)
+    }
+}
+
+impl<'de, S> DeserializeWithPolicy<'de> for StringOrStruct<S>
+where
+    S: Deserialize<'de>,
+{
+    fn deserialize_with_policy<D>(
+        deserializer: D,
+        policy: &DispatchPolicy,
+    ) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        dispatch(deserializer, policy)
     }
 }
 
@@ -77,16 +328,57 @@ where
     where
         D: Deserializer<'de>,
     {
-        let value = StringOrStructOrVec::<S, S>::deserialize_with_expected(
-            deserializer,
-            &"String or Struct",
-        )?;
-        return match value {
-            StringOrStructOrVec::String(s) => Ok(StringOrStruct::String(s)),
-            StringOrStructOrVec::Struct(v) | StringOrStructOrVec::Vec(v) => {
-                Ok(StringOrStruct::Struct(v))
-            }
+        Self::deserialize_with_policy(deserializer, &DispatchPolicy::default())
+    }
+}
+
+impl<'de, S> Dispatch<'de> for SingleOrVec<S>
+where
+    S: Deserialize<'de>,
+{
+    const KINDS: &'static [Kind] = &[Kind::Struct, Kind::Vec];
+
+    fn from_buffered<E: Error>(
+        value: Value,
+        policy: &DispatchPolicy,
+        human_readable: bool,
+    ) -> Result<Self, E> {
+        let kinds = Self::KINDS;
+        let (kind, value) = match unwrap_seq_of_one(value, policy) {
+            Ok(item) => ensure_allowed(Kind::Struct, item, kinds, policy)?,
+            Err(value @ Value::Seq(_)) => ensure_allowed(Kind::Vec, value, kinds, policy)?,
+            Err(value) => ensure_allowed(Kind::Struct, value, kinds, policy)?,
         };
+
+        match kind {
+            Kind::Vec => Ok(Self::Vec(Vec::<S>::deserialize(Replay::new(
+                value,
+                human_readable,
+            ))?)),
+            _ => Ok(Self::Single(S::deserialize(Replay::new(
+                value,
+                human_readable,
+            ))?)),
+        }
+    }
+
+    fn from_struct<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        S::deserialize(deserializer).map(Self::Single)
+    }
+}
+
+impl<'de, S> DeserializeWithPolicy<'de> for SingleOrVec<S>
+where
+    S: Deserialize<'de>,
+{
+    fn deserialize_with_policy<D>(
+        deserializer: D,
+        policy: &DispatchPolicy,
+    ) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        dispatch(deserializer, policy)
     }
 }
 
@@ -98,13 +390,27 @@ where
     where
         D: Deserializer<'de>,
     {
-        let value = Value::deserialize(deserializer)?;
+        Self::deserialize_with_policy(deserializer, &DispatchPolicy::default())
+    }
+}
 
-        return match value {
-            Value::Seq(_) => Ok(Self::Vec(Vec::<S>::deserialize(ValueDeserializer::new(
-                value,
-            ))?)),
-            _ => Ok(Self::Single(S::deserialize(ValueDeserializer::new(value))?)),
-        };
+impl<'de, S> Deserialize<'de> for OneOrMany<S>
+where
+    S: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match SingleOrVec::<S>::deserialize(deserializer)? {
+            SingleOrVec::Single(one) => Ok(Self::One(one)),
+            SingleOrVec::Vec(mut many) => {
+                if many.is_empty() {
+                    return Err(Error::invalid_length(0, &"at least one element"));
+                }
+                let first = many.remove(0);
+                Ok(Self::Many(first, many))
+            }
+        }
     }
 }
@@ -0,0 +1,153 @@
+use crate::de::NUMBER_TOKEN;
+use rust_decimal::Decimal;
+use serde::de::{Deserialize, Deserializer, Error, IgnoredAny, MapAccess, Unexpected, Visitor};
+use serde::ser::{Serialize, Serializer};
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::str::FromStr;
+
+/// A [`Decimal`] written either as a number or as a quoted numeric string
+/// (`12.5`, `"12.50"`, `"1e-3"`).
+///
+/// Quoted strings are parsed exactly, keeping their scale, which is why
+/// financial APIs usually quote their amounts. Bare numbers are only read
+/// exactly when serde_json's `arbitrary_precision` feature is enabled.
+/// Otherwise they reach the decimal as an `f64`, and digits beyond its
+/// precision are lost. Floats are converted through their shortest
+/// representation, so `0.1` becomes `0.1` rather than the nearest binary
+/// fraction. The value is always serialized as a string.
+///
+/// ```rust
+/// use rust_decimal::Decimal;
+/// use serde_either::StringOrDecimal;
+///
+/// let quoted: StringOrDecimal = serde_json::from_str(r#""0.10""#).unwrap();
+/// let number: StringOrDecimal = serde_json::from_str("0.1").unwrap();
+///
+/// assert_eq!(*quoted, *number);
+/// assert_eq!(quoted.to_string(), "0.10");
+/// assert_eq!(serde_json::to_string(&quoted).unwrap(), r#""0.10""#);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct StringOrDecimal(pub Decimal);
+
+impl StringOrDecimal {
+    pub fn into_inner(self) -> Decimal {
+        self.0
+    }
+}
+
+impl Deref for StringOrDecimal {
+    type Target = Decimal;
+
+    fn deref(&self) -> &Decimal {
+        &self.0
+    }
+}
+
+impl DerefMut for StringOrDecimal {
+    fn deref_mut(&mut self) -> &mut Decimal {
+        &mut self.0
+    }
+}
+
+impl From<Decimal> for StringOrDecimal {
+    fn from(decimal: Decimal) -> Self {
+        StringOrDecimal(decimal)
+    }
+}
+
+impl fmt::Display for StringOrDecimal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+fn parse_decimal(s: &str) -> Option<Decimal> {
+    let s = s.trim();
+    Decimal::from_str(s)
+        .or_else(|_| Decimal::from_scientific(s))
+        .ok()
+}
+
+struct DecimalVisitor;
+
+impl<'de> Visitor<'de> for DecimalVisitor {
+    type Value = StringOrDecimal;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a number or a numeric string")
+    }
+
+    fn visit_i64<E: Error>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(StringOrDecimal(Decimal::from(v)))
+    }
+
+    fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(StringOrDecimal(Decimal::from(v)))
+    }
+
+    fn visit_i128<E: Error>(self, v: i128) -> Result<Self::Value, E> {
+        Decimal::try_from_i128_with_scale(v, 0)
+            .map(StringOrDecimal)
+            .map_err(|_| E::custom(format!("{} does not fit in a decimal", v)))
+    }
+
+    fn visit_u128<E: Error>(self, v: u128) -> Result<Self::Value, E> {
+        match i128::try_from(v) {
+            Ok(v) => self.visit_i128(v),
+            Err(_) => Err(E::custom(format!("{} does not fit in a decimal", v))),
+        }
+    }
+
+    fn visit_f64<E: Error>(self, v: f64) -> Result<Self::Value, E> {
+        // the Display of a float is its shortest round-tripping representation
+        parse_decimal(&v.to_string())
+            .map(StringOrDecimal)
+            .ok_or_else(|| E::invalid_value(Unexpected::Float(v), &self))
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        parse_decimal(v)
+            .map(StringOrDecimal)
+            .ok_or_else(|| E::invalid_value(Unexpected::Str(v), &self))
+    }
+
+    // serde_json's arbitrary_precision feature hands numbers over as a map
+    // with a single private key holding their text
+    fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        match access.next_key::<String>()? {
+            Some(key) if key == NUMBER_TOKEN => {
+                let text: String = access.next_value()?;
+                let decimal = DecimalVisitor.visit_str(&text)?;
+                match access.next_key::<IgnoredAny>()? {
+                    None => Ok(decimal),
+                    Some(_) => Err(A::Error::invalid_type(Unexpected::Map, &self)),
+                }
+            }
+            _ => Err(A::Error::invalid_type(Unexpected::Map, &self)),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for StringOrDecimal {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(DecimalVisitor)
+    }
+}
+
+impl Serialize for StringOrDecimal {
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        serializer.collect_str(&self.0)
+    }
+}
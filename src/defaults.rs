@@ -0,0 +1,84 @@
+//! Functions for `#[serde(default = "...")]` attributes on fields holding
+//! the enums of this crate.
+
+use crate::enums::{SingleOrVec, StringOrStruct};
+
+/// Returns `StringOrStruct::String("")`, for use as
+/// `#[serde(default = "serde_either::defaults::empty_string_or_struct")]`.
+///
+/// It is also re-exported at the crate root as `default_string`:
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use serde_either::StringOrStruct;
+/// use std::collections::HashMap;
+///
+/// #[derive(Deserialize)]
+/// struct Config {
+///     #[serde(default = "serde_either::default_string")]
+///     mode: StringOrStruct<HashMap<String, String>>,
+/// }
+///
+/// let config: Config = serde_json::from_str("{}").unwrap();
+/// assert_eq!(config.mode, StringOrStruct::String(String::new()));
+/// ```
+pub fn empty_string_or_struct<S>() -> StringOrStruct<S> {
+    StringOrStruct::String(String::new())
+}
+
+/// Returns an empty `SingleOrVec::Vec`, for use as
+/// `#[serde(default = "serde_either::defaults::empty_single_or_vec")]`.
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use serde_either::SingleOrVec;
+///
+/// #[derive(Deserialize)]
+/// struct Config {
+///     #[serde(default = "serde_either::defaults::empty_single_or_vec")]
+///     hosts: SingleOrVec<String>,
+/// }
+///
+/// let config: Config = serde_json::from_str("{}").unwrap();
+/// assert!(config.hosts.is_empty());
+/// ```
+pub fn empty_single_or_vec<S>() -> SingleOrVec<S> {
+    SingleOrVec::Vec(Vec::new())
+}
+
+/// Returns `StringOrStruct::String` with the given literal.
+///
+/// Attributes can only name functions without arguments, so this is meant to
+/// be called from one, as [`default_string_fn!`](crate::default_string_fn)
+/// does.
+pub fn string_default<S>(literal: impl Into<String>) -> StringOrStruct<S> {
+    StringOrStruct::String(literal.into())
+}
+
+/// Defines a function returning `StringOrStruct::String` with the given
+/// literal, for use with `#[serde(default = "...")]`.
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use serde_either::StringOrStruct;
+/// use std::collections::HashMap;
+///
+/// serde_either::default_string_fn!(default_mode, "auto");
+///
+/// #[derive(Deserialize)]
+/// struct Config {
+///     #[serde(default = "default_mode")]
+///     mode: StringOrStruct<HashMap<String, String>>,
+/// }
+///
+/// let config: Config = serde_json::from_str("{}").unwrap();
+/// assert_eq!(config.mode, StringOrStruct::String(String::from("auto")));
+/// ```
+#[macro_export]
+macro_rules! default_string_fn {
+    ($vis:vis $name:ident, $literal:expr) => {
+        $vis fn $name<S>() -> $crate::StringOrStruct<S> {
+            $crate::defaults::string_default($literal)
+        }
+    };
+}
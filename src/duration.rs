@@ -0,0 +1,120 @@
+use serde::de::{Deserialize, Deserializer, Error, Unexpected, Visitor};
+use serde::ser::{Serialize, Serializer};
+use std::convert::TryFrom;
+use std::fmt;
+use std::time::Duration;
+
+/// A duration written either as a number, of seconds or of milliseconds when
+/// `MILLIS` is `true`, or as a human-readable string such as `"5m"` or
+/// `"1h 30m"`, parsed with humantime.
+///
+/// Both arms hold the parsed [`Duration`], and each is serialized back in its
+/// own shape: a number in the same unit, or a string formatted by humantime.
+/// Negative and non-finite numbers are rejected.
+///
+/// ```rust
+/// use serde_either::DurationOrNumber;
+/// use std::time::Duration;
+///
+/// let seconds: DurationOrNumber = serde_json::from_str("90").unwrap();
+/// let text: DurationOrNumber = serde_json::from_str(r#""1m 30s""#).unwrap();
+/// let millis: DurationOrNumber<true> = serde_json::from_str("1500").unwrap();
+///
+/// assert_eq!(seconds.duration(), text.duration());
+/// assert_eq!(millis.duration(), Duration::from_millis(1500));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum DurationOrNumber<const MILLIS: bool = false> {
+    Number(Duration),
+    String(Duration),
+}
+
+impl<const MILLIS: bool> DurationOrNumber<MILLIS> {
+    /// Returns the duration, whichever form it was written in.
+    pub fn duration(&self) -> Duration {
+        match *self {
+            Self::Number(d) | Self::String(d) => d,
+        }
+    }
+}
+
+impl<const MILLIS: bool> From<DurationOrNumber<MILLIS>> for Duration {
+    fn from(value: DurationOrNumber<MILLIS>) -> Self {
+        value.duration()
+    }
+}
+
+struct DurationOrNumberVisitor<const MILLIS: bool>;
+
+impl<'de, const MILLIS: bool> Visitor<'de> for DurationOrNumberVisitor<MILLIS> {
+    type Value = DurationOrNumber<MILLIS>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if MILLIS {
+            formatter.write_str("a number of milliseconds or a duration string")
+        } else {
+            formatter.write_str("a number of seconds or a duration string")
+        }
+    }
+
+    fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+        let duration = if MILLIS {
+            Duration::from_millis(v)
+        } else {
+            Duration::from_secs(v)
+        };
+        Ok(DurationOrNumber::Number(duration))
+    }
+
+    fn visit_i64<E: Error>(self, v: i64) -> Result<Self::Value, E> {
+        match u64::try_from(v) {
+            Ok(v) => self.visit_u64(v),
+            Err(_) => Err(E::invalid_value(Unexpected::Signed(v), &self)),
+        }
+    }
+
+    fn visit_f64<E: Error>(self, v: f64) -> Result<Self::Value, E> {
+        let secs = if MILLIS { v / 1000.0 } else { v };
+        Duration::try_from_secs_f64(secs)
+            .map(DurationOrNumber::Number)
+            .map_err(|_| E::invalid_value(Unexpected::Float(v), &self))
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        humantime::parse_duration(v)
+            .map(DurationOrNumber::String)
+            .map_err(|e| E::custom(format!("invalid duration {:?}: {}", v, e)))
+    }
+}
+
+impl<'de, const MILLIS: bool> Deserialize<'de> for DurationOrNumber<MILLIS> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(DurationOrNumberVisitor)
+    }
+}
+
+impl<const MILLIS: bool> Serialize for DurationOrNumber<MILLIS> {
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match *self {
+            DurationOrNumber::Number(d) if MILLIS => {
+                match u64::try_from(d.as_nanos() / 1_000_000) {
+                    Ok(millis) if d.subsec_nanos() % 1_000_000 == 0 => {
+                        serializer.serialize_u64(millis)
+                    }
+                    _ => serializer.serialize_f64(d.as_secs_f64() * 1000.0),
+                }
+            }
+            DurationOrNumber::Number(d) if d.subsec_nanos() == 0 => {
+                serializer.serialize_u64(d.as_secs())
+            }
+            DurationOrNumber::Number(d) => serializer.serialize_f64(d.as_secs_f64()),
+            DurationOrNumber::String(d) => serializer.collect_str(&humantime::format_duration(d)),
+        }
+    }
+}
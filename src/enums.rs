@@ -1,4 +1,10 @@
-#[derive(Debug, PartialEq)]
+use std::borrow::Cow;
+use std::fmt::Display;
+use std::mem;
+use std::ops::RangeBounds;
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum StringOrStruct<S> {
     String(String),
     Struct(S),
@@ -13,7 +19,80 @@ impl<S: Clone> Clone for StringOrStruct<S> {
     }
 }
 
-#[derive(Debug, PartialEq)]
+/// Defaults to the `Struct` arm holding `S::default()`, so that a missing
+/// field reads as the struct with its own defaults rather than an empty
+/// string.
+impl<S: Default> Default for StringOrStruct<S> {
+    fn default() -> Self {
+        Self::Struct(S::default())
+    }
+}
+
+impl<S> From<String> for StringOrStruct<S> {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl<S> From<&str> for StringOrStruct<S> {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_owned())
+    }
+}
+
+impl<S> From<AsStruct<S>> for StringOrStruct<S> {
+    fn from(value: AsStruct<S>) -> Self {
+        Self::Struct(value.0)
+    }
+}
+
+impl<S: Display> StringOrStruct<S> {
+    /// Renders the value in its compact string form, regardless of which arm
+    /// it currently holds.
+    ///
+    /// The `String` arm is returned as is, while the `Struct` arm is rendered
+    /// through its `Display` implementation, which is expected to be the
+    /// inverse of its `FromStr` implementation.
+    pub fn to_canonical_string(&self) -> String {
+        match self {
+            Self::String(as_string) => as_string.clone(),
+            Self::Struct(as_struct) => as_struct.to_string(),
+        }
+    }
+}
+
+impl<S: FromStr> StringOrStruct<S> {
+    /// Parses the `String` arm into the `Struct` arm through `FromStr`,
+    /// leaving the `Struct` arm untouched.
+    ///
+    /// This is mostly useful for map keys, which most formats only allow to
+    /// be strings and therefore always land in the `String` arm.
+    pub fn into_parsed(self) -> Result<Self, S::Err> {
+        match self {
+            Self::String(as_string) => as_string.parse().map(Self::Struct),
+            Self::Struct(as_struct) => Ok(Self::Struct(as_struct)),
+        }
+    }
+}
+
+impl<S: FromStr + PartialEq> StringOrStruct<S> {
+    /// Compares two values semantically: a `String` arm equals a `Struct`
+    /// arm when it parses into an equal struct.
+    ///
+    /// Two `String` arms are compared as strings, without parsing them.
+    pub fn eq_normalized(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::String(a), Self::String(b)) => a == b,
+            (Self::Struct(a), Self::Struct(b)) => a == b,
+            (Self::String(text), Self::Struct(parsed))
+            | (Self::Struct(parsed), Self::String(text)) => {
+                text.parse::<S>().is_ok_and(|text| text == *parsed)
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum StringOrStructOrVec<S, V> {
     String(String),
     Struct(S),
@@ -30,7 +109,48 @@ impl<S: Clone, V: Clone> Clone for StringOrStructOrVec<S, V> {
     }
 }
 
-#[derive(Debug, PartialEq)]
+/// Defaults to the `Struct` arm holding `S::default()`, as
+/// [`StringOrStruct`] does.
+impl<S: Default, V> Default for StringOrStructOrVec<S, V> {
+    fn default() -> Self {
+        Self::Struct(S::default())
+    }
+}
+
+impl<S, V> From<String> for StringOrStructOrVec<S, V> {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl<S, V> From<&str> for StringOrStructOrVec<S, V> {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_owned())
+    }
+}
+
+impl<S, V> From<AsStruct<S>> for StringOrStructOrVec<S, V> {
+    fn from(value: AsStruct<S>) -> Self {
+        Self::Struct(value.0)
+    }
+}
+
+impl<S, V> From<AsVec<V>> for StringOrStructOrVec<S, V> {
+    fn from(value: AsVec<V>) -> Self {
+        Self::Vec(value.0)
+    }
+}
+
+impl<S, V> From<StringOrStruct<S>> for StringOrStructOrVec<S, V> {
+    fn from(value: StringOrStruct<S>) -> Self {
+        match value {
+            StringOrStruct::String(s) => Self::String(s),
+            StringOrStruct::Struct(s) => Self::Struct(s),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum SingleOrVec<S> {
     Single(S),
     Vec(Vec<S>),
@@ -44,3 +164,225 @@ impl<S: Clone> Clone for SingleOrVec<S> {
         }
     }
 }
+
+/// Defaults to an empty `Vec`.
+impl<S> Default for SingleOrVec<S> {
+    fn default() -> Self {
+        Self::Vec(Vec::new())
+    }
+}
+
+impl<S> From<Vec<S>> for SingleOrVec<S> {
+    fn from(value: Vec<S>) -> Self {
+        Self::Vec(value)
+    }
+}
+
+impl<S> From<OneOrMany<S>> for SingleOrVec<S> {
+    fn from(value: OneOrMany<S>) -> Self {
+        match value {
+            OneOrMany::One(first) => Self::Single(first),
+            many => Self::Vec(many.into_vec()),
+        }
+    }
+}
+
+impl<S> SingleOrVec<S> {
+    /// Returns the number of elements, `Single` counting as one.
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Single(_) => 1,
+            Self::Vec(as_vec) => as_vec.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Inserts an element at `index`, shifting the following elements.
+    ///
+    /// Like all the positional mutators, the value is left as `Single` when
+    /// it ends up with exactly one element, and as `Vec` otherwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`, leaving the value unchanged.
+    pub fn insert(&mut self, index: usize, element: S) {
+        let len = self.len();
+        assert!(
+            index <= len,
+            "insertion index (is {}) should be <= len (is {})",
+            index,
+            len
+        );
+        let mut elements = self.take_vec();
+        elements.insert(index, element);
+        *self = Self::from_elements(elements);
+    }
+
+    /// Removes and returns the element at `index`, shifting the following
+    /// elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds, leaving the value unchanged.
+    pub fn remove(&mut self, index: usize) -> S {
+        let len = self.len();
+        assert!(
+            index < len,
+            "removal index (is {}) should be < len (is {})",
+            index,
+            len
+        );
+        let mut elements = self.take_vec();
+        let element = elements.remove(index);
+        *self = Self::from_elements(elements);
+        element
+    }
+
+    /// Removes the elements in `range` and returns them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds, leaving the value unchanged.
+    pub fn drain<R>(&mut self, range: R) -> Vec<S>
+    where
+        R: RangeBounds<usize>,
+    {
+        // checked up front, so that a panic leaves the value untouched
+        let bounds = (range.start_bound().cloned(), range.end_bound().cloned());
+        let _ = &self.as_slice()[bounds];
+        let mut elements = self.take_vec();
+        let drained = elements.drain(range).collect();
+        *self = Self::from_elements(elements);
+        drained
+    }
+
+    fn as_slice(&self) -> &[S] {
+        match self {
+            Self::Single(as_single) => std::slice::from_ref(as_single),
+            Self::Vec(as_vec) => as_vec,
+        }
+    }
+
+    fn take_vec(&mut self) -> Vec<S> {
+        match mem::replace(self, Self::Vec(Vec::new())) {
+            Self::Single(as_single) => vec![as_single],
+            Self::Vec(as_vec) => as_vec,
+        }
+    }
+
+    fn from_elements(mut elements: Vec<S>) -> Self {
+        match elements.len() {
+            1 => Self::Single(elements.remove(0)),
+            _ => Self::Vec(elements),
+        }
+    }
+}
+
+impl<S: Clone> SingleOrVec<S> {
+    /// Returns the elements as a `Cow`, for APIs that take slices but
+    /// sometimes need to own them.
+    ///
+    /// Both arms are borrowed, `Single` as a one-element slice, so nothing is
+    /// cloned until the `Cow` is turned into an owned `Vec`.
+    pub fn as_cow(&self) -> Cow<'_, [S]> {
+        Cow::Borrowed(self.as_slice())
+    }
+}
+
+impl<S: PartialEq> SingleOrVec<S> {
+    /// Compares the elements of two values, regardless of their arm:
+    /// `Single(x)` equals `Vec(vec![x])`.
+    pub fn eq_normalized(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+/// Like [`SingleOrVec`], but guaranteed to hold at least one element: empty
+/// sequences are rejected during deserialization.
+///
+/// `Many` stores the first element of the sequence apart from the others, so
+/// that [`OneOrMany::first`] never fails.
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum OneOrMany<S> {
+    One(S),
+    Many(S, Vec<S>),
+}
+
+impl<S: Clone> Clone for OneOrMany<S> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::One(as_one) => Self::One(as_one.clone()),
+            Self::Many(first, rest) => Self::Many(first.clone(), rest.clone()),
+        }
+    }
+}
+
+impl<S> OneOrMany<S> {
+    pub fn first(&self) -> &S {
+        match self {
+            Self::One(first) | Self::Many(first, _) => first,
+        }
+    }
+
+    /// Returns the number of elements, which is never zero.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        match self {
+            Self::One(_) => 1,
+            Self::Many(_, rest) => 1 + rest.len(),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &S> {
+        let rest: &[S] = match self {
+            Self::One(_) => &[],
+            Self::Many(_, rest) => rest,
+        };
+        std::iter::once(self.first()).chain(rest)
+    }
+
+    pub fn into_vec(self) -> Vec<S> {
+        match self {
+            Self::One(first) => vec![first],
+            Self::Many(first, mut rest) => {
+                rest.insert(0, first);
+                rest
+            }
+        }
+    }
+}
+
+impl<S: PartialEq> OneOrMany<S> {
+    /// Compares the elements of two values, regardless of their arm:
+    /// `One(x)` equals `Many(x, vec![])`.
+    pub fn eq_normalized(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+/// Marks a value as the `Struct` arm of [`StringOrStruct`] and the other
+/// enums with a `Struct` arm for `From`, which could otherwise not tell it
+/// apart from the `String` arm when `S` is itself a `String`.
+///
+/// ```rust
+/// use serde_either::{AsStruct, AsVec, StringOrStruct, StringOrStructOrVec};
+///
+/// let text: StringOrStruct<Vec<u8>> = "a".into();
+/// let parsed: StringOrStruct<Vec<u8>> = AsStruct(vec![1]).into();
+/// let listed: StringOrStructOrVec<u8, Vec<u8>> = AsVec(vec![1]).into();
+///
+/// assert_eq!(text, StringOrStruct::String(String::from("a")));
+/// assert_eq!(parsed, StringOrStruct::Struct(vec![1]));
+/// assert_eq!(listed, StringOrStructOrVec::Vec(vec![1]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AsStruct<S>(pub S);
+
+/// Marks a value as the `Vec` arm of [`StringOrStructOrVec`] and the other
+/// enums with a generic `Vec` arm for `From`, as [`AsStruct`] does for the
+/// `Struct` arm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AsVec<V>(pub V);
@@ -0,0 +1,179 @@
+use serde::de::{Deserialize, Deserializer, Error, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Environment variables, listed as `KEY=VALUE` strings or given as a map,
+/// as docker-compose allows for `environment`.
+///
+/// Each form is kept, and serialized back, as it was written. Map values may
+/// be strings, numbers or booleans, which are kept as their text, or null for
+/// variables without a value. Likewise, list entries without a `=` have no
+/// value.
+///
+/// ```rust
+/// use serde_either::EnvListOrMap;
+///
+/// let list: EnvListOrMap = serde_yaml::from_str("[RACK_ENV=development, SHOW]").unwrap();
+/// let map: EnvListOrMap = serde_yaml::from_str("{ RACK_ENV: development, SHOW: }").unwrap();
+///
+/// assert_eq!(list.clone().into_map(), map.clone().into_map());
+/// assert_eq!(
+///     map.into_pairs(),
+///     vec![
+///         (String::from("RACK_ENV"), Some(String::from("development"))),
+///         (String::from("SHOW"), None),
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum EnvListOrMap {
+    List(Vec<String>),
+    Map(Vec<(String, Option<String>)>),
+}
+
+impl EnvListOrMap {
+    /// Returns the variables in the order they were written, splitting the
+    /// entries of lists on their first `=`.
+    pub fn into_pairs(self) -> Vec<(String, Option<String>)> {
+        match self {
+            Self::List(entries) => entries
+                .into_iter()
+                .map(|entry| match entry.find('=') {
+                    Some(index) => (
+                        entry[..index].to_owned(),
+                        Some(entry[index + 1..].to_owned()),
+                    ),
+                    None => (entry, None),
+                })
+                .collect(),
+            Self::Map(entries) => entries,
+        }
+    }
+
+    /// Returns the variables by name, the last one winning when a name is
+    /// repeated.
+    pub fn into_map(self) -> BTreeMap<String, Option<String>> {
+        self.into_pairs().into_iter().collect()
+    }
+}
+
+// the value of a variable given in a map
+struct EnvValue(Option<String>);
+
+struct EnvValueVisitor;
+
+impl<'de> Visitor<'de> for EnvValueVisitor {
+    type Value = EnvValue;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string, a number, a boolean or null")
+    }
+
+    fn visit_bool<E: Error>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(EnvValue(Some(v.to_string())))
+    }
+
+    fn visit_i64<E: Error>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(EnvValue(Some(v.to_string())))
+    }
+
+    fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(EnvValue(Some(v.to_string())))
+    }
+
+    fn visit_f64<E: Error>(self, v: f64) -> Result<Self::Value, E> {
+        Ok(EnvValue(Some(v.to_string())))
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(EnvValue(Some(v.to_owned())))
+    }
+
+    fn visit_string<E: Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(EnvValue(Some(v)))
+    }
+
+    fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+        Ok(EnvValue(None))
+    }
+
+    fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+        Ok(EnvValue(None))
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for EnvValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(EnvValueVisitor)
+    }
+}
+
+struct EnvListOrMapVisitor;
+
+impl<'de> Visitor<'de> for EnvListOrMapVisitor {
+    type Value = EnvListOrMap;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a list of KEY=VALUE strings or a map")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut entries = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(entry) = seq.next_element()? {
+            entries.push(entry);
+        }
+        Ok(EnvListOrMap::List(entries))
+    }
+
+    fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entries = Vec::with_capacity(access.size_hint().unwrap_or(0).min(4096));
+        while let Some((key, EnvValue(value))) = access.next_entry()? {
+            entries.push((key, value));
+        }
+        Ok(EnvListOrMap::Map(entries))
+    }
+}
+
+impl<'de> Deserialize<'de> for EnvListOrMap {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(EnvListOrMapVisitor)
+    }
+}
+
+impl Serialize for EnvListOrMap {
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            EnvListOrMap::List(entries) => entries.serialize(serializer),
+            EnvListOrMap::Map(entries) => {
+                let mut map = serializer.serialize_map(Some(entries.len()))?;
+                for (key, value) in entries {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use crate::enums::StringOrStructOrVec;
+
+/// Extension trait flattening a list of three-way values.
+///
+/// # Example
+/// ```rust
+/// use serde_either::{FlattenExt, StringOrStructOrVec};
+///
+/// let values: Vec<StringOrStructOrVec<u32, Vec<u32>>> = vec![
+///     StringOrStructOrVec::Struct(1),
+///     StringOrStructOrVec::String("two".to_owned()),
+///     StringOrStructOrVec::Vec(vec![3, 4]),
+/// ];
+///
+/// let (structs, strings) = values.flatten_either();
+/// assert_eq!(structs, vec![1, 3, 4]);
+/// assert_eq!(strings, vec!["two".to_owned()]);
+/// ```
+pub trait FlattenExt<S> {
+    /// Collects every `Struct` and every element of every `Vec` into a single
+    /// list, in order, returning the raw `String` values alongside.
+    fn flatten_either(self) -> (Vec<S>, Vec<String>);
+}
+
+impl<S> FlattenExt<S> for Vec<StringOrStructOrVec<S, Vec<S>>> {
+    fn flatten_either(self) -> (Vec<S>, Vec<String>) {
+        let mut structs = Vec::with_capacity(self.len());
+        let mut strings = Vec::new();
+        for value in self {
+            match value {
+                StringOrStructOrVec::String(s) => strings.push(s),
+                StringOrStructOrVec::Struct(s) => structs.push(s),
+                StringOrStructOrVec::Vec(v) => structs.extend(v),
+            }
+        }
+        (structs, strings)
+    }
+}
@@ -0,0 +1,129 @@
+use crate::buffer::buffer;
+use crate::de::number_token;
+use crate::policy::DispatchPolicy;
+use crate::replay::Replay;
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+use serde_value::Value;
+
+/// The objects that carry their own id, so that [`IdOrObject::id`] can read
+/// it from either arm.
+pub trait HasId {
+    type Id;
+
+    fn id(&self) -> &Self::Id;
+}
+
+/// An id, or the object it refers to, as REST APIs with expandable fields
+/// return them: `"customer": "cus_123"` vs `"customer": { "id": "cus_123", ... }`.
+///
+/// Maps are deserialized into `T`, and anything else, usually a string or a
+/// number, into `Id`.
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use serde_either::{HasId, IdOrObject};
+///
+/// #[derive(Deserialize)]
+/// struct Customer {
+///     id: String,
+///     email: String,
+/// }
+///
+/// impl HasId for Customer {
+///     type Id = String;
+///
+///     fn id(&self) -> &String {
+///         &self.id
+///     }
+/// }
+///
+/// let id: IdOrObject<String, Customer> = serde_json::from_str(r#""cus_123""#).unwrap();
+/// let expanded: IdOrObject<String, Customer> =
+///     serde_json::from_str(r#"{"id": "cus_123", "email": "a@b.c"}"#).unwrap();
+///
+/// assert!(id.id_only());
+/// assert!(expanded.expanded());
+/// assert_eq!(id.id(), expanded.id());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum IdOrObject<Id, T> {
+    Id(Id),
+    Object(T),
+}
+
+impl<Id, T> IdOrObject<Id, T> {
+    /// Returns whether the object has been expanded.
+    pub fn expanded(&self) -> bool {
+        matches!(self, Self::Object(_))
+    }
+
+    /// Returns whether only the id has been sent.
+    pub fn id_only(&self) -> bool {
+        matches!(self, Self::Id(_))
+    }
+
+    pub fn as_object(&self) -> Option<&T> {
+        match self {
+            Self::Id(_) => None,
+            Self::Object(t) => Some(t),
+        }
+    }
+
+    /// Returns the id, reading it from the object with `id_of` when it has
+    /// been expanded.
+    pub fn id_with<'a, F>(&'a self, id_of: F) -> &'a Id
+    where
+        F: FnOnce(&'a T) -> &'a Id,
+    {
+        match self {
+            Self::Id(id) => id,
+            Self::Object(t) => id_of(t),
+        }
+    }
+}
+
+impl<Id, T> IdOrObject<Id, T>
+where
+    T: HasId<Id = Id>,
+{
+    /// Returns the id, whichever form was sent.
+    pub fn id(&self) -> &Id {
+        self.id_with(T::id)
+    }
+}
+
+impl<'de, Id, T> Deserialize<'de> for IdOrObject<Id, T>
+where
+    Id: Deserialize<'de>,
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let human_readable = deserializer.is_human_readable();
+        match buffer(deserializer, &DispatchPolicy::default())? {
+            value @ Value::Map(_) if number_token(&value).is_none() => {
+                T::deserialize(Replay::new(value, human_readable)).map(Self::Object)
+            }
+            value => Id::deserialize(Replay::new(value, human_readable)).map(Self::Id),
+        }
+    }
+}
+
+impl<Id, T> Serialize for IdOrObject<Id, T>
+where
+    Id: Serialize,
+    T: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            IdOrObject::Id(id) => id.serialize(serializer),
+            IdOrObject::Object(t) => t.serialize(serializer),
+        }
+    }
+}
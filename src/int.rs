@@ -0,0 +1,108 @@
+use serde::de::{Deserialize, Deserializer, Error, Unexpected, Visitor};
+use serde::ser::{Serialize, Serializer};
+use std::convert::TryFrom;
+use std::fmt;
+
+/// An integer or a string, like Kubernetes' `IntOrString` used for ports
+/// (`8080` or `"http"`) and rollout sizes (`1` or `"25%"`).
+///
+/// Integers are 32 bits, as in Kubernetes, and strings are kept as they are,
+/// even when they hold a number.
+///
+/// ```rust
+/// use serde_either::IntOrString;
+///
+/// let port: IntOrString = serde_json::from_str("8080").unwrap();
+/// let surge: IntOrString = serde_json::from_str(r#""25%""#).unwrap();
+///
+/// assert_eq!(port.as_int(), Some(8080));
+/// assert_eq!(surge.as_percentage(), Some(25));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum IntOrString {
+    Int(i32),
+    String(String),
+}
+
+impl IntOrString {
+    pub fn as_int(&self) -> Option<i32> {
+        match *self {
+            Self::Int(n) => Some(n),
+            Self::String(_) => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::Int(_) => None,
+            Self::String(s) => Some(s),
+        }
+    }
+
+    /// Returns the percentage held by a string such as `"25%"`.
+    ///
+    /// Integers and strings without a trailing `%` are not percentages.
+    pub fn as_percentage(&self) -> Option<i32> {
+        self.as_str()?.strip_suffix('%')?.parse().ok()
+    }
+}
+
+impl fmt::Display for IntOrString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Int(n) => fmt::Display::fmt(n, f),
+            Self::String(s) => f.write_str(s),
+        }
+    }
+}
+
+struct IntOrStringVisitor;
+
+impl<'de> Visitor<'de> for IntOrStringVisitor {
+    type Value = IntOrString;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a 32-bit integer or a string")
+    }
+
+    fn visit_i64<E: Error>(self, v: i64) -> Result<Self::Value, E> {
+        i32::try_from(v)
+            .map(IntOrString::Int)
+            .map_err(|_| E::invalid_value(Unexpected::Signed(v), &self))
+    }
+
+    fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+        i32::try_from(v)
+            .map(IntOrString::Int)
+            .map_err(|_| E::invalid_value(Unexpected::Unsigned(v), &self))
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(IntOrString::String(v.to_owned()))
+    }
+
+    fn visit_string<E: Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(IntOrString::String(v))
+    }
+}
+
+impl<'de> Deserialize<'de> for IntOrString {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(IntOrStringVisitor)
+    }
+}
+
+impl Serialize for IntOrString {
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            IntOrString::Int(n) => serializer.serialize_i32(*n),
+            IntOrString::String(s) => serializer.serialize_str(s),
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use crate::enums::StringOrStruct;
+use serde::de::{Deserialize, DeserializeSeed, Deserializer};
+use serde::ser::{Serialize, Serializer};
+use std::collections::HashSet;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// Like [`StringOrStruct`], but the String arm shares its allocation with
+/// every other equal string produced by the same [`Interner`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum InternedStringOrStruct<S> {
+    String(Arc<str>),
+    Struct(S),
+}
+
+/// A cache of `Arc<str>`, used to deduplicate the String arms of values
+/// deserialized through [`Interner::seed`].
+///
+/// The cache is guarded by a `Mutex`, so a single interner can be shared by
+/// the threads of a parallel ingest, and their equal strings share one
+/// allocation.
+///
+/// ```rust
+/// use serde::de::DeserializeSeed;
+/// use serde_either::{InternedStringOrStruct, Interner};
+/// use std::collections::HashMap;
+/// use std::sync::Arc;
+///
+/// let interner = Interner::new();
+/// let mut values = Vec::new();
+/// for json in &[r#""default""#, r#""default""#] {
+///     let mut deserializer = serde_json::Deserializer::from_str(json);
+///     let value = interner
+///         .seed::<HashMap<String, String>>()
+///         .deserialize(&mut deserializer)
+///         .unwrap();
+///     values.push(value);
+/// }
+///
+/// match (&values[0], &values[1]) {
+///     (InternedStringOrStruct::String(a), InternedStringOrStruct::String(b)) => {
+///         assert!(Arc::ptr_eq(a, b))
+///     }
+///     _ => unreachable!(),
+/// }
+/// assert_eq!(interner.len(), 1);
+/// ```
+#[derive(Debug, Default)]
+pub struct Interner {
+    strings: Mutex<HashSet<Arc<str>>>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached `Arc<str>` equal to `s`, adding it if needed.
+    pub fn intern(&self, s: &str) -> Arc<str> {
+        let mut strings = self.strings();
+        match strings.get(s) {
+            Some(interned) => interned.clone(),
+            None => {
+                let interned: Arc<str> = Arc::from(s);
+                strings.insert(interned.clone());
+                interned
+            }
+        }
+    }
+
+    /// Returns the number of distinct strings in the cache.
+    pub fn len(&self) -> usize {
+        self.strings().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strings().is_empty()
+    }
+
+    // the cache stays consistent when a thread panics while holding it, as
+    // strings are only ever inserted whole
+    fn strings(&self) -> MutexGuard<'_, HashSet<Arc<str>>> {
+        self.strings.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns a `DeserializeSeed` producing an [`InternedStringOrStruct`]
+    /// whose String arm is interned in this cache.
+    pub fn seed<S>(&self) -> InternSeed<'_, S> {
+        InternSeed {
+            interner: self,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// A `DeserializeSeed` created by [`Interner::seed`].
+pub struct InternSeed<'a, S> {
+    interner: &'a Interner,
+    marker: PhantomData<S>,
+}
+
+impl<'de, 'a, S> DeserializeSeed<'de> for InternSeed<'a, S>
+where
+    S: Deserialize<'de>,
+{
+    type Value = InternedStringOrStruct<S>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match StringOrStruct::<S>::deserialize(deserializer)? {
+            StringOrStruct::String(s) => {
+                Ok(InternedStringOrStruct::String(self.interner.intern(&s)))
+            }
+            StringOrStruct::Struct(s) => Ok(InternedStringOrStruct::Struct(s)),
+        }
+    }
+}
+
+impl<S> Serialize for InternedStringOrStruct<S>
+where
+    S: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            InternedStringOrStruct::String(s) => serializer.serialize_str(s),
+            InternedStringOrStruct::Struct(s) => s.serialize(serializer),
+        }
+    }
+}
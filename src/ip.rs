@@ -0,0 +1,91 @@
+use crate::enums::StringOrStruct;
+use ipnet::{IpNet, PrefixLenError};
+use serde::de::{Deserializer, Error};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+
+/// Either an IP address or network written as a string (`"10.0.0.1"`,
+/// `"10.0.0.0/8"`), or a structured form.
+///
+/// Strings are parsed into an [`IpNet`] during deserialization; a plain
+/// address gets the full prefix length of its family. The structured form
+/// defaults to [`IpNetParts`]. The `Ip` arm is serialized in CIDR notation.
+///
+/// ```rust
+/// use serde_either::IpOrStruct;
+///
+/// let cidr: IpOrStruct = serde_json::from_str(r#""10.0.0.0/8""#).unwrap();
+/// let parts: IpOrStruct = serde_json::from_str(r#"{"addr": "10.0.0.0", "prefix": 8}"#).unwrap();
+///
+/// assert_eq!(cidr.to_ipnet().unwrap(), parts.to_ipnet().unwrap());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum IpOrStruct<S = IpNetParts> {
+    Ip(IpNet),
+    Struct(S),
+}
+
+/// The structured form of an IP network: `{"addr": "10.0.0.0", "prefix": 8}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct IpNetParts {
+    pub addr: IpAddr,
+    pub prefix: u8,
+}
+
+impl IpNetParts {
+    pub fn to_ipnet(&self) -> Result<IpNet, PrefixLenError> {
+        IpNet::new(self.addr, self.prefix)
+    }
+}
+
+impl IpOrStruct<IpNetParts> {
+    /// Returns the network, whichever form it was written in.
+    pub fn to_ipnet(&self) -> Result<IpNet, PrefixLenError> {
+        match self {
+            Self::Ip(net) => Ok(*net),
+            Self::Struct(parts) => parts.to_ipnet(),
+        }
+    }
+}
+
+fn parse_ip(s: &str) -> Result<IpNet, String> {
+    if s.contains('/') {
+        s.parse::<IpNet>()
+            .map_err(|e| format!("invalid IP network {:?}: {}", s, e))
+    } else {
+        s.parse::<IpAddr>()
+            .map(IpNet::from)
+            .map_err(|e| format!("invalid IP address {:?}: {}", s, e))
+    }
+}
+
+impl<'de, S> Deserialize<'de> for IpOrStruct<S>
+where
+    S: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match StringOrStruct::<S>::deserialize(deserializer)? {
+            StringOrStruct::String(s) => parse_ip(&s).map(Self::Ip).map_err(D::Error::custom),
+            StringOrStruct::Struct(s) => Ok(Self::Struct(s)),
+        }
+    }
+}
+
+impl<S> Serialize for IpOrStruct<S>
+where
+    S: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            IpOrStruct::Ip(net) => serializer.collect_str(net),
+            IpOrStruct::Struct(s) => s.serialize(serializer),
+        }
+    }
+}
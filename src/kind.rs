@@ -0,0 +1,128 @@
+use crate::enums::{SingleOrVec, StringOrStruct, StringOrStructOrVec};
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// The shape of a value, as used to pick which arm of an enum it lands in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Kind {
+    String,
+    Struct,
+    Vec,
+}
+
+impl Kind {
+    /// Returns the name of the kind, as used in error messages.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Kind::String => "String",
+            Kind::Struct => "Struct",
+            Kind::Vec => "Vec",
+        }
+    }
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The error returned when parsing an unknown [`Kind`] name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseKindError {
+    pub name: String,
+}
+
+impl fmt::Display for ParseKindError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown kind {:?}, expected String, Struct or Vec",
+            self.name
+        )
+    }
+}
+
+impl std::error::Error for ParseKindError {}
+
+impl FromStr for Kind {
+    type Err = ParseKindError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "String" => Ok(Kind::String),
+            "Struct" => Ok(Kind::Struct),
+            "Vec" => Ok(Kind::Vec),
+            _ => Err(ParseKindError { name: s.into() }),
+        }
+    }
+}
+
+// kinds are serialized as their name, e.g. "Struct"
+impl Serialize for Kind {
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+struct KindVisitor;
+
+impl<'de> Visitor<'de> for KindVisitor {
+    type Value = Kind;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("String, Struct or Vec")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Kind, E> {
+        value
+            .parse()
+            .map_err(|_| E::unknown_variant(value, &["String", "Struct", "Vec"]))
+    }
+}
+
+impl<'de> Deserialize<'de> for Kind {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(KindVisitor)
+    }
+}
+
+impl<S> StringOrStruct<S> {
+    /// Returns which arm the value holds.
+    pub fn kind(&self) -> Kind {
+        match self {
+            Self::String(_) => Kind::String,
+            Self::Struct(_) => Kind::Struct,
+        }
+    }
+}
+
+impl<S, V> StringOrStructOrVec<S, V> {
+    /// Returns which arm the value holds.
+    pub fn kind(&self) -> Kind {
+        match self {
+            Self::String(_) => Kind::String,
+            Self::Struct(_) => Kind::Struct,
+            Self::Vec(_) => Kind::Vec,
+        }
+    }
+}
+
+impl<S> SingleOrVec<S> {
+    /// Returns which arm the value holds. `Single` is reported as
+    /// [`Kind::Struct`].
+    pub fn kind(&self) -> Kind {
+        match self {
+            Self::Single(_) => Kind::Struct,
+            Self::Vec(_) => Kind::Vec,
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use crate::buffer::BufferSeed;
+use crate::error::Error;
+use crate::policy::DispatchPolicy;
+use crate::replay::Replay;
+use serde::de::{Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+use std::fmt;
+use std::marker::PhantomData;
+
+/// A sequence whose elements are deserialized independently: elements that
+/// fail to deserialize are skipped and their errors recorded, instead of
+/// failing the whole sequence.
+///
+/// The input itself still has to be a sequence, and malformed input (such as
+/// invalid JSON) still fails the whole deserialization.
+///
+/// ```rust
+/// use serde_either::LenientVec;
+///
+/// let res: LenientVec<u8> = serde_json::from_str(r#"[1, "two", 3, 300]"#).unwrap();
+///
+/// assert_eq!(res.items(), &[1, 3]);
+/// assert_eq!(res.errors().iter().map(|e| e.index).collect::<Vec<_>>(), vec![1, 3]);
+/// ```
+#[derive(Debug)]
+pub struct LenientVec<T> {
+    items: Vec<T>,
+    errors: Vec<ElementError>,
+}
+
+/// The error of an element skipped by [`LenientVec`].
+#[derive(Debug)]
+pub struct ElementError {
+    /// Position of the element in the input sequence.
+    pub index: usize,
+    pub error: Error,
+}
+
+impl fmt::Display for ElementError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "element {}: {}", self.index, self.error)
+    }
+}
+
+impl std::error::Error for ElementError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl<T> LenientVec<T> {
+    /// Returns the elements that deserialized successfully, in input order.
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Returns the errors of the skipped elements, in input order.
+    pub fn errors(&self) -> &[ElementError] {
+        &self.errors
+    }
+
+    /// Returns true when no element was skipped.
+    pub fn is_clean(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.items
+    }
+
+    pub fn into_parts(self) -> (Vec<T>, Vec<ElementError>) {
+        (self.items, self.errors)
+    }
+}
+
+impl<T> Default for LenientVec<T> {
+    fn default() -> Self {
+        LenientVec {
+            items: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+}
+
+struct LenientVecVisitor<T> {
+    human_readable: bool,
+    marker: PhantomData<T>,
+}
+
+impl<'de, T> Visitor<'de> for LenientVecVisitor<T>
+where
+    T: Deserialize<'de>,
+{
+    type Value = LenientVec<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence")
+    }
+
+    fn visit_seq<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let policy = DispatchPolicy::default();
+        let mut res = LenientVec::default();
+        let mut index = 0;
+        while let Some(value) = access.next_element_seed(BufferSeed::new(&policy))? {
+            match T::deserialize(Replay::<Error>::new(value, self.human_readable)) {
+                Ok(item) => res.items.push(item),
+                Err(error) => res.errors.push(ElementError { index, error }),
+            }
+            index += 1;
+        }
+        Ok(res)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for LenientVec<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let human_readable = deserializer.is_human_readable();
+        deserializer.deserialize_seq(LenientVecVisitor {
+            human_readable,
+            marker: PhantomData,
+        })
+    }
+}
+
+impl<T> Serialize for LenientVec<T>
+where
+    T: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        self.items.serialize(serializer)
+    }
+}
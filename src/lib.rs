@@ -49,9 +49,162 @@
 //!
 //! ```
 //!
+//! # Comparing and sorting
+//!
+//! The enums implement `Eq`, `Hash`, `PartialOrd` and `Ord` whenever their
+//! arms do. Values are ordered by arm first, in the order the arms are
+//! declared, and then by content: every `StringOrStruct::String` sorts
+//! before every `StringOrStruct::Struct`.
+//!
+//! ```rust
+//! use serde_either::SingleOrVec;
+//!
+//! let mut values = vec![
+//!     SingleOrVec::Vec(vec![1]),
+//!     SingleOrVec::Single(2),
+//!     SingleOrVec::Single(1),
+//! ];
+//! values.sort();
+//!
+//! assert_eq!(values[0], SingleOrVec::Single(1));
+//! assert_eq!(values[2], SingleOrVec::Vec(vec![1]));
+//! ```
+//!
 
+mod blob;
+mod borrowed;
+mod bounded;
+mod buffer;
 mod de;
+#[cfg(feature = "rust_decimal")]
+mod decimal;
+pub mod defaults;
+#[cfg(feature = "humantime")]
+mod duration;
 mod enums;
+mod env;
+mod error;
+mod flatten;
+mod id;
+mod int;
+#[cfg(feature = "intern")]
+mod intern;
+#[cfg(feature = "ipnet")]
+mod ip;
+mod kind;
+mod lenient;
+mod localized;
+#[cfg(feature = "mlua")]
+mod lua;
+mod match_either;
+#[cfg(feature = "napi")]
+mod napi;
+#[cfg(feature = "rustler")]
+mod nif;
+mod normalized;
+mod null;
+mod number;
+mod one_of;
+#[cfg(feature = "sea-orm")]
+mod orm;
+mod path;
+mod peek;
+mod policy;
+#[cfg(feature = "postgres")]
+mod postgres;
+#[cfg(feature = "prost-types")]
+mod prost;
+mod raw;
+mod replay;
+#[cfg(feature = "async")]
+mod resolve;
+mod scalar;
+#[cfg(feature = "jsonschema")]
+mod schema;
 mod se;
+mod separated;
+mod sequence;
+#[cfg(feature = "smallvec")]
+mod small;
+mod socket;
+mod stream;
+mod tagged;
+#[cfg(any(feature = "chrono", feature = "time"))]
+mod timestamp;
+mod toggle;
+mod tree;
+#[cfg(feature = "url")]
+mod url;
+#[cfg(feature = "uuid")]
+mod uuid;
+mod variant;
+#[cfg(feature = "semver")]
+mod version;
+mod warnings;
 
+pub use blob::{BytesOrStruct, StringOrBytes};
+pub use borrowed::{CowStringOrStruct, SingleOrVecRef, StringOrStructOrVecRef, StringOrStructRef};
+pub use bounded::Bounded;
+#[cfg(feature = "rust_decimal")]
+pub use decimal::StringOrDecimal;
+pub use defaults::empty_string_or_struct as default_string;
+#[cfg(feature = "humantime")]
+pub use duration::DurationOrNumber;
 pub use enums::*;
+pub use env::EnvListOrMap;
+pub use error::Error;
+pub use flatten::FlattenExt;
+pub use id::{HasId, IdOrObject};
+pub use int::IntOrString;
+#[cfg(feature = "intern")]
+pub use intern::{InternSeed, InternedStringOrStruct, Interner};
+#[cfg(feature = "ipnet")]
+pub use ip::{IpNetParts, IpOrStruct};
+pub use kind::{Kind, ParseKindError};
+pub use lenient::{ElementError, LenientVec};
+pub use localized::LocalizedString;
+pub use normalized::NormalizedEq;
+pub use null::{SingleOrVecOrNull, StringOrStructOrNull, StringOrStructOrVecOrNull, UnitOrStruct};
+pub use number::{Number, NumberOrStruct, NumericStringOrNumber, StringOrNumber};
+pub use one_of::{Alternatives, AnyOf, OneOf};
+pub use path::PathOrStruct;
+#[cfg(feature = "camino")]
+pub use path::Utf8PathOrStruct;
+pub use peek::peek_kind;
+pub use policy::{DeserializeWithPolicy, DispatchPolicy, DispatchSeed, DEFAULT_MAX_DEPTH};
+#[cfg(feature = "serde_json")]
+pub use raw::JsonValueOr;
+pub use raw::{StringOrStructOrOther, StructOrRaw, UnknownOr, ValueOr};
+#[cfg(feature = "async")]
+pub use resolve::Resolve;
+pub use scalar::{Scalar, ScalarOrStruct};
+#[cfg(feature = "jsonschema")]
+pub use schema::SchemaSeed;
+pub use separated::CommaSeparatedOrVec;
+#[cfg(feature = "indexmap")]
+pub use sequence::IndexMapOrString;
+pub use sequence::{
+    KeyedMapOrVec, MapOrString, MapOrVec, SingleOrMap, SingleOrSet, StringOrStrings,
+    StringOrStructOrVecOrMap, StringOrVec, StructOrVec, TupleOrStruct,
+};
+#[cfg(feature = "smallvec")]
+pub use small::SingleOrSmallVec;
+pub use socket::{HostPort, StringOrSocketAddr};
+pub use tagged::TaggedOrUntagged;
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub use timestamp::{Timestamp, TimestampOrString};
+pub use toggle::{BoolOrStruct, CountOrBool, StringOrBool};
+pub use tree::{Leaves, Tree};
+#[cfg(feature = "url")]
+pub use url::UrlOrStruct;
+#[cfg(feature = "uuid")]
+pub use uuid::UuidStringOrBytes;
+pub use variant::VariantNameOrStruct;
+#[cfg(feature = "semver")]
+pub use version::{DependencyDetail, VersionOrStruct};
+pub use warnings::{Warned, Warning, WarningSeed};
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::match_either::{Arms2, Arms3, IntoArms};
+}
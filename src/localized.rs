@@ -0,0 +1,90 @@
+use crate::sequence::MapOrString;
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+use std::collections::BTreeMap;
+
+/// A text given either once for every locale, or per locale
+/// (`{"en": "Open", "de": "Öffnen"}`), as extension and web app manifests
+/// allow.
+///
+/// ```rust
+/// use serde_either::LocalizedString;
+///
+/// let plain: LocalizedString = serde_json::from_str(r#""Open""#).unwrap();
+/// let localized: LocalizedString =
+///     serde_json::from_str(r#"{"en": "Open", "de": "Öffnen"}"#).unwrap();
+///
+/// assert_eq!(plain.get("de-AT"), Some("Open"));
+/// assert_eq!(localized.get("de-AT"), Some("Öffnen"));
+/// assert_eq!(localized.get("fr"), None);
+/// assert_eq!(localized.fallback(), Some("Open"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum LocalizedString {
+    String(String),
+    Map(BTreeMap<String, String>),
+}
+
+/// The locale used by [`LocalizedString::fallback`] when it is present.
+const FALLBACK_LOCALE: &str = "en";
+
+impl LocalizedString {
+    /// Returns the text for `locale`, or for its language when there is no
+    /// text for the locale itself (`"de"` for `"de-AT"`).
+    ///
+    /// Plain strings are returned for every locale.
+    pub fn get(&self, locale: &str) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s),
+            Self::Map(map) => map
+                .get(locale)
+                .or_else(|| {
+                    let language = locale.split(['-', '_']).next()?;
+                    map.get(language)
+                })
+                .map(String::as_str),
+        }
+    }
+
+    /// Returns the text to use when no locale matches: the plain string, the
+    /// English text, or else the text of the first locale in alphabetical
+    /// order. Only empty maps have none.
+    pub fn fallback(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s),
+            Self::Map(map) => map
+                .get(FALLBACK_LOCALE)
+                .or_else(|| map.values().next())
+                .map(String::as_str),
+        }
+    }
+
+    /// Returns the text for `locale`, or the [`fallback`](LocalizedString::fallback).
+    pub fn get_or_fallback(&self, locale: &str) -> Option<&str> {
+        self.get(locale).or_else(|| self.fallback())
+    }
+}
+
+impl<'de> Deserialize<'de> for LocalizedString {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match MapOrString::deserialize(deserializer)? {
+            MapOrString::String(s) => Ok(Self::String(s)),
+            MapOrString::Map(map) => Ok(Self::Map(map)),
+        }
+    }
+}
+
+impl Serialize for LocalizedString {
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            LocalizedString::String(s) => serializer.serialize_str(s),
+            LocalizedString::Map(map) => map.serialize(serializer),
+        }
+    }
+}
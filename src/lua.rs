@@ -0,0 +1,116 @@
+use crate::enums::{OneOrMany, SingleOrVec, StringOrStruct, StringOrStructOrVec};
+use mlua::{FromLua, IntoLua, Lua, Result, Table, Value};
+
+// Lua has a single table type for both arrays and maps: only tables made of a
+// non-empty sequence and nothing else take the Vec arm, everything else
+// (including empty tables) is left to the struct
+fn is_array(table: &Table) -> bool {
+    let len = table.raw_len();
+    len > 0 && table.pairs::<Value, Value>().count() == len
+}
+
+impl<S, V> FromLua for StringOrStructOrVec<S, V>
+where
+    S: FromLua,
+    V: FromLua,
+{
+    fn from_lua(value: Value, lua: &Lua) -> Result<Self> {
+        match value {
+            Value::String(_) => String::from_lua(value, lua).map(Self::String),
+            Value::Table(ref table) if is_array(table) => V::from_lua(value, lua).map(Self::Vec),
+            _ => S::from_lua(value, lua).map(Self::Struct),
+        }
+    }
+}
+
+impl<S, V> IntoLua for StringOrStructOrVec<S, V>
+where
+    S: IntoLua,
+    V: IntoLua,
+{
+    fn into_lua(self, lua: &Lua) -> Result<Value> {
+        match self {
+            Self::String(s) => s.into_lua(lua),
+            Self::Struct(s) => s.into_lua(lua),
+            Self::Vec(v) => v.into_lua(lua),
+        }
+    }
+}
+
+impl<S> FromLua for StringOrStruct<S>
+where
+    S: FromLua,
+{
+    fn from_lua(value: Value, lua: &Lua) -> Result<Self> {
+        match value {
+            Value::String(_) => String::from_lua(value, lua).map(Self::String),
+            _ => S::from_lua(value, lua).map(Self::Struct),
+        }
+    }
+}
+
+impl<S> IntoLua for StringOrStruct<S>
+where
+    S: IntoLua,
+{
+    fn into_lua(self, lua: &Lua) -> Result<Value> {
+        match self {
+            Self::String(s) => s.into_lua(lua),
+            Self::Struct(s) => s.into_lua(lua),
+        }
+    }
+}
+
+impl<S> FromLua for SingleOrVec<S>
+where
+    S: FromLua,
+{
+    fn from_lua(value: Value, lua: &Lua) -> Result<Self> {
+        match value {
+            Value::Table(ref table) if is_array(table) => {
+                Vec::<S>::from_lua(value, lua).map(Self::Vec)
+            }
+            _ => S::from_lua(value, lua).map(Self::Single),
+        }
+    }
+}
+
+impl<S> IntoLua for SingleOrVec<S>
+where
+    S: IntoLua,
+{
+    fn into_lua(self, lua: &Lua) -> Result<Value> {
+        match self {
+            Self::Single(s) => s.into_lua(lua),
+            Self::Vec(v) => v.into_lua(lua),
+        }
+    }
+}
+
+impl<S> FromLua for OneOrMany<S>
+where
+    S: FromLua,
+{
+    // arrays are never empty, so there is no empty Vec to reject here
+    fn from_lua(value: Value, lua: &Lua) -> Result<Self> {
+        match SingleOrVec::<S>::from_lua(value, lua)? {
+            SingleOrVec::Single(one) => Ok(Self::One(one)),
+            SingleOrVec::Vec(mut many) => {
+                let first = many.remove(0);
+                Ok(Self::Many(first, many))
+            }
+        }
+    }
+}
+
+impl<S> IntoLua for OneOrMany<S>
+where
+    S: IntoLua,
+{
+    fn into_lua(self, lua: &Lua) -> Result<Value> {
+        match self {
+            Self::One(s) => s.into_lua(lua),
+            many => many.into_vec().into_lua(lua),
+        }
+    }
+}
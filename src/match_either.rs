@@ -0,0 +1,151 @@
+use crate::enums::{OneOrMany, SingleOrVec, StringOrStruct, StringOrStructOrVec};
+#[cfg(feature = "intern")]
+use crate::intern::InternedStringOrStruct;
+#[cfg(feature = "ipnet")]
+use crate::ip::IpOrStruct;
+#[cfg(feature = "ipnet")]
+use ipnet::IpNet;
+#[cfg(feature = "intern")]
+use std::sync::Arc;
+
+/// Matches on any enum of this crate by position, binding each arm's content
+/// to a pattern.
+///
+/// Arms are given in declaration order: `String`, `Struct` (and `Vec`) for
+/// the string enums, `Single` and `Vec` for [`SingleOrVec`], and `One` and
+/// `Many` for [`OneOrMany`], whose `Many` arm binds a `(first, rest)` tuple.
+/// Pass a reference to bind references instead of moving the content out.
+///
+/// ```rust
+/// use serde_either::{match_either, SingleOrVec, StringOrStructOrVec};
+///
+/// let value: StringOrStructOrVec<u32, Vec<u32>> = StringOrStructOrVec::Vec(vec![1, 2]);
+/// let total = match_either!(&value, s => s.len() as u32, n => *n, v => v.iter().sum());
+/// assert_eq!(total, 3);
+///
+/// let names: Vec<SingleOrVec<&str>> = vec![SingleOrVec::Single("a"), SingleOrVec::Vec(vec!["b", "c"])];
+/// let counts: Vec<usize> = names
+///     .into_iter()
+///     .map(|names| match_either!(names, _ => 1, v => v.len()))
+///     .collect();
+/// assert_eq!(counts, vec![1, 2]);
+/// ```
+#[macro_export]
+macro_rules! match_either {
+    ($value:expr, $first:pat => $on_first:expr, $second:pat => $on_second:expr $(,)?) => {
+        match $crate::__private::IntoArms::into_arms($value) {
+            $crate::__private::Arms2::First($first) => $on_first,
+            $crate::__private::Arms2::Second($second) => $on_second,
+        }
+    };
+    (
+        $value:expr,
+        $first:pat => $on_first:expr,
+        $second:pat => $on_second:expr,
+        $third:pat => $on_third:expr $(,)?
+    ) => {
+        match $crate::__private::IntoArms::into_arms($value) {
+            $crate::__private::Arms3::First($first) => $on_first,
+            $crate::__private::Arms3::Second($second) => $on_second,
+            $crate::__private::Arms3::Third($third) => $on_third,
+        }
+    };
+}
+
+pub enum Arms2<A, B> {
+    First(A),
+    Second(B),
+}
+
+pub enum Arms3<A, B, C> {
+    First(A),
+    Second(B),
+    Third(C),
+}
+
+// lets match_either! pick the number of arms from the type being matched
+pub trait IntoArms {
+    type Arms;
+
+    fn into_arms(self) -> Self::Arms;
+}
+
+// implements IntoArms for an enum with two single-field arms, and a reference to it
+macro_rules! two_arms {
+    ($name:ident<$($param:ident),+>, $first:ident($a:ty), $second:ident($b:ty)) => {
+        impl<$($param),+> IntoArms for $name<$($param),+> {
+            type Arms = Arms2<$a, $b>;
+
+            fn into_arms(self) -> Self::Arms {
+                match self {
+                    $name::$first(a) => Arms2::First(a),
+                    $name::$second(b) => Arms2::Second(b),
+                }
+            }
+        }
+
+        impl<'a, $($param),+> IntoArms for &'a $name<$($param),+> {
+            type Arms = Arms2<&'a $a, &'a $b>;
+
+            fn into_arms(self) -> Self::Arms {
+                match self {
+                    $name::$first(a) => Arms2::First(a),
+                    $name::$second(b) => Arms2::Second(b),
+                }
+            }
+        }
+    };
+}
+
+two_arms!(StringOrStruct<S>, String(String), Struct(S));
+two_arms!(SingleOrVec<S>, Single(S), Vec(Vec<S>));
+#[cfg(feature = "intern")]
+two_arms!(InternedStringOrStruct<S>, String(Arc<str>), Struct(S));
+#[cfg(feature = "ipnet")]
+two_arms!(IpOrStruct<S>, Ip(IpNet), Struct(S));
+
+impl<S, V> IntoArms for StringOrStructOrVec<S, V> {
+    type Arms = Arms3<String, S, V>;
+
+    fn into_arms(self) -> Self::Arms {
+        match self {
+            StringOrStructOrVec::String(s) => Arms3::First(s),
+            StringOrStructOrVec::Struct(s) => Arms3::Second(s),
+            StringOrStructOrVec::Vec(v) => Arms3::Third(v),
+        }
+    }
+}
+
+impl<'a, S, V> IntoArms for &'a StringOrStructOrVec<S, V> {
+    type Arms = Arms3<&'a String, &'a S, &'a V>;
+
+    fn into_arms(self) -> Self::Arms {
+        match self {
+            StringOrStructOrVec::String(s) => Arms3::First(s),
+            StringOrStructOrVec::Struct(s) => Arms3::Second(s),
+            StringOrStructOrVec::Vec(v) => Arms3::Third(v),
+        }
+    }
+}
+
+impl<S> IntoArms for OneOrMany<S> {
+    type Arms = Arms2<S, (S, Vec<S>)>;
+
+    fn into_arms(self) -> Self::Arms {
+        match self {
+            OneOrMany::One(s) => Arms2::First(s),
+            OneOrMany::Many(first, rest) => Arms2::Second((first, rest)),
+        }
+    }
+}
+
+impl<'a, S> IntoArms for &'a OneOrMany<S> {
+    type Arms = Arms2<&'a S, (&'a S, &'a Vec<S>)>;
+
+    fn into_arms(self) -> Self::Arms {
+        match self {
+            OneOrMany::One(s) => Arms2::First(s),
+            OneOrMany::Many(first, rest) => Arms2::Second((first, rest)),
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use crate::enums::{OneOrMany, SingleOrVec, StringOrStruct, StringOrStructOrVec};
+use napi::bindgen_prelude::{FromNapiValue, ToNapiValue};
+use napi::{sys, Error, Result, Status, ValueType};
+
+// the JS counterpart of each arm, as told apart when converting from napi values
+enum Shape {
+    String,
+    Array,
+    Other,
+}
+
+fn check(status: sys::napi_status) -> Result<()> {
+    match Status::from(status) {
+        Status::Ok => Ok(()),
+        status => Err(Error::from_status(status)),
+    }
+}
+
+unsafe fn shape(env: sys::napi_env, value: sys::napi_value) -> Result<Shape> {
+    let mut value_type = 0;
+    check(sys::napi_typeof(env, value, &mut value_type))?;
+    match ValueType::from(value_type) {
+        ValueType::String => Ok(Shape::String),
+        ValueType::Object => {
+            let mut is_array = false;
+            check(sys::napi_is_array(env, value, &mut is_array))?;
+            Ok(if is_array { Shape::Array } else { Shape::Other })
+        }
+        _ => Ok(Shape::Other),
+    }
+}
+
+impl<S, V> FromNapiValue for StringOrStructOrVec<S, V>
+where
+    S: FromNapiValue,
+    V: FromNapiValue,
+{
+    unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+        match shape(env, napi_val)? {
+            Shape::String => String::from_napi_value(env, napi_val).map(Self::String),
+            Shape::Array => V::from_napi_value(env, napi_val).map(Self::Vec),
+            Shape::Other => S::from_napi_value(env, napi_val).map(Self::Struct),
+        }
+    }
+}
+
+impl<S, V> ToNapiValue for StringOrStructOrVec<S, V>
+where
+    S: ToNapiValue,
+    V: ToNapiValue,
+{
+    unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+        match val {
+            Self::String(s) => String::to_napi_value(env, s),
+            Self::Struct(s) => S::to_napi_value(env, s),
+            Self::Vec(v) => V::to_napi_value(env, v),
+        }
+    }
+}
+
+impl<S> FromNapiValue for StringOrStruct<S>
+where
+    S: FromNapiValue,
+{
+    unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+        match shape(env, napi_val)? {
+            Shape::String => String::from_napi_value(env, napi_val).map(Self::String),
+            _ => S::from_napi_value(env, napi_val).map(Self::Struct),
+        }
+    }
+}
+
+impl<S> ToNapiValue for StringOrStruct<S>
+where
+    S: ToNapiValue,
+{
+    unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+        match val {
+            Self::String(s) => String::to_napi_value(env, s),
+            Self::Struct(s) => S::to_napi_value(env, s),
+        }
+    }
+}
+
+impl<S> FromNapiValue for SingleOrVec<S>
+where
+    S: FromNapiValue,
+{
+    unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+        match shape(env, napi_val)? {
+            Shape::Array => Vec::<S>::from_napi_value(env, napi_val).map(Self::Vec),
+            _ => S::from_napi_value(env, napi_val).map(Self::Single),
+        }
+    }
+}
+
+impl<S> ToNapiValue for SingleOrVec<S>
+where
+    S: ToNapiValue,
+{
+    unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+        match val {
+            Self::Single(s) => S::to_napi_value(env, s),
+            Self::Vec(v) => Vec::<S>::to_napi_value(env, v),
+        }
+    }
+}
+
+impl<S> FromNapiValue for OneOrMany<S>
+where
+    S: FromNapiValue,
+{
+    unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+        match SingleOrVec::<S>::from_napi_value(env, napi_val)? {
+            SingleOrVec::Single(one) => Ok(Self::One(one)),
+            SingleOrVec::Vec(mut many) => {
+                if many.is_empty() {
+                    return Err(Error::new(
+                        Status::InvalidArg,
+                        "expected an array with at least one element",
+                    ));
+                }
+                let first = many.remove(0);
+                Ok(Self::Many(first, many))
+            }
+        }
+    }
+}
+
+impl<S> ToNapiValue for OneOrMany<S>
+where
+    S: ToNapiValue,
+{
+    unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+        match val {
+            Self::One(s) => S::to_napi_value(env, s),
+            many => Vec::<S>::to_napi_value(env, many.into_vec()),
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use crate::enums::{OneOrMany, SingleOrVec, StringOrStruct, StringOrStructOrVec};
+use rustler::{Decoder, Encoder, Env, Error, NifResult, Term};
+
+// Elixir strings are binaries, and charlists are left to the Vec arm
+fn is_list(term: Term) -> bool {
+    term.is_list() || term.is_empty_list()
+}
+
+impl<'a, S, V> Decoder<'a> for StringOrStructOrVec<S, V>
+where
+    S: Decoder<'a>,
+    V: Decoder<'a>,
+{
+    fn decode(term: Term<'a>) -> NifResult<Self> {
+        if term.is_binary() {
+            term.decode().map(Self::String)
+        } else if is_list(term) {
+            term.decode().map(Self::Vec)
+        } else {
+            term.decode().map(Self::Struct)
+        }
+    }
+}
+
+impl<S, V> Encoder for StringOrStructOrVec<S, V>
+where
+    S: Encoder,
+    V: Encoder,
+{
+    fn encode<'a>(&self, env: Env<'a>) -> Term<'a> {
+        match self {
+            Self::String(s) => s.encode(env),
+            Self::Struct(s) => s.encode(env),
+            Self::Vec(v) => v.encode(env),
+        }
+    }
+}
+
+impl<'a, S> Decoder<'a> for StringOrStruct<S>
+where
+    S: Decoder<'a>,
+{
+    fn decode(term: Term<'a>) -> NifResult<Self> {
+        if term.is_binary() {
+            term.decode().map(Self::String)
+        } else {
+            term.decode().map(Self::Struct)
+        }
+    }
+}
+
+impl<S> Encoder for StringOrStruct<S>
+where
+    S: Encoder,
+{
+    fn encode<'a>(&self, env: Env<'a>) -> Term<'a> {
+        match self {
+            Self::String(s) => s.encode(env),
+            Self::Struct(s) => s.encode(env),
+        }
+    }
+}
+
+impl<'a, S> Decoder<'a> for SingleOrVec<S>
+where
+    S: Decoder<'a>,
+{
+    fn decode(term: Term<'a>) -> NifResult<Self> {
+        if is_list(term) {
+            term.decode().map(Self::Vec)
+        } else {
+            term.decode().map(Self::Single)
+        }
+    }
+}
+
+impl<S> Encoder for SingleOrVec<S>
+where
+    S: Encoder,
+{
+    fn encode<'a>(&self, env: Env<'a>) -> Term<'a> {
+        match self {
+            Self::Single(s) => s.encode(env),
+            Self::Vec(v) => v.encode(env),
+        }
+    }
+}
+
+impl<'a, S> Decoder<'a> for OneOrMany<S>
+where
+    S: Decoder<'a>,
+{
+    fn decode(term: Term<'a>) -> NifResult<Self> {
+        match SingleOrVec::<S>::decode(term)? {
+            SingleOrVec::Single(one) => Ok(Self::One(one)),
+            SingleOrVec::Vec(mut many) => {
+                if many.is_empty() {
+                    return Err(Error::BadArg);
+                }
+                let first = many.remove(0);
+                Ok(Self::Many(first, many))
+            }
+        }
+    }
+}
+
+impl<S> Encoder for OneOrMany<S>
+where
+    S: Encoder,
+{
+    fn encode<'a>(&self, env: Env<'a>) -> Term<'a> {
+        match self {
+            Self::One(s) => s.encode(env),
+            Self::Many(first, rest) => {
+                let terms: Vec<Term<'a>> = std::iter::once(first)
+                    .chain(rest)
+                    .map(|s| s.encode(env))
+                    .collect();
+                terms.encode(env)
+            }
+        }
+    }
+}
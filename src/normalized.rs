@@ -0,0 +1,35 @@
+use crate::enums::{OneOrMany, SingleOrVec, StringOrStruct};
+use std::str::FromStr;
+
+/// Wraps a value so that `==` compares it with `eq_normalized`, which is
+/// convenient with `assert_eq!`.
+///
+/// ```rust
+/// use serde_either::{NormalizedEq, SingleOrVec};
+///
+/// let single: SingleOrVec<u8> = serde_json::from_str("1").unwrap();
+/// let vec: SingleOrVec<u8> = serde_json::from_str("[1]").unwrap();
+///
+/// assert_ne!(single, vec);
+/// assert_eq!(NormalizedEq(single), NormalizedEq(vec));
+/// ```
+#[derive(Debug, Clone)]
+pub struct NormalizedEq<T>(pub T);
+
+impl<S: FromStr + PartialEq> PartialEq for NormalizedEq<StringOrStruct<S>> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_normalized(&other.0)
+    }
+}
+
+impl<S: PartialEq> PartialEq for NormalizedEq<SingleOrVec<S>> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_normalized(&other.0)
+    }
+}
+
+impl<S: PartialEq> PartialEq for NormalizedEq<OneOrMany<S>> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_normalized(&other.0)
+    }
+}
@@ -0,0 +1,527 @@
+use crate::buffer::buffer;
+use crate::de::unexpected;
+use crate::enums::{AsStruct, AsVec, SingleOrVec, StringOrStruct, StringOrStructOrVec};
+use crate::policy::DispatchPolicy;
+use crate::replay::Replay;
+use crate::stream::Dispatch;
+use serde::de::{Deserialize, Deserializer, Error};
+use serde::ser::{Serialize, SerializeSeq, Serializer};
+use serde_value::Value;
+
+/// Like [`StringOrStruct`], with a `Null` arm for explicit nulls.
+///
+/// `Option<StringOrStruct<S>>` turns both a missing field and `null` into
+/// `None`. Wrapping this type in an `Option` instead, with
+/// [`deserialize_some`](StringOrStructOrNull::deserialize_some), keeps them
+/// apart: a missing field is `None` and `null` is `Some(Null)`, which is
+/// serialized back as `null`.
+///
+/// ```rust
+/// use serde::{Deserialize, Serialize};
+/// use serde_either::StringOrStructOrNull;
+/// use std::collections::HashMap;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Patch {
+///     #[serde(
+///         default,
+///         deserialize_with = "StringOrStructOrNull::deserialize_some",
+///         skip_serializing_if = "Option::is_none"
+///     )]
+///     owner: Option<StringOrStructOrNull<HashMap<String, String>>>,
+/// }
+///
+/// let absent: Patch = serde_json::from_str("{}").unwrap();
+/// let cleared: Patch = serde_json::from_str(r#"{"owner": null}"#).unwrap();
+///
+/// assert_eq!(absent.owner, None);
+/// assert_eq!(cleared.owner, Some(StringOrStructOrNull::Null));
+/// assert_eq!(serde_json::to_string(&cleared).unwrap(), r#"{"owner":null}"#);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum StringOrStructOrNull<S> {
+    String(String),
+    Struct(S),
+    Null,
+}
+
+impl<S> StringOrStructOrNull<S> {
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    /// Turns the `Null` arm into `None`, and the others into the matching
+    /// arm of [`StringOrStruct`].
+    pub fn into_option(self) -> Option<StringOrStruct<S>> {
+        match self {
+            Self::String(s) => Some(StringOrStruct::String(s)),
+            Self::Struct(s) => Some(StringOrStruct::Struct(s)),
+            Self::Null => None,
+        }
+    }
+
+    /// Deserializes the value into `Some`, for use as
+    /// `#[serde(default, deserialize_with = "...")]` on an `Option` field,
+    /// so that `null` becomes `Some(Null)` rather than `None`.
+    pub fn deserialize_some<'de, D>(deserializer: D) -> Result<Option<Self>, D::Error>
+    where
+        D: Deserializer<'de>,
+        S: Deserialize<'de>,
+    {
+        Self::deserialize(deserializer).map(Some)
+    }
+}
+
+impl<S> From<String> for StringOrStructOrNull<S> {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl<S> From<&str> for StringOrStructOrNull<S> {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_owned())
+    }
+}
+
+impl<S> From<AsStruct<S>> for StringOrStructOrNull<S> {
+    fn from(value: AsStruct<S>) -> Self {
+        Self::Struct(value.0)
+    }
+}
+
+impl<S> From<StringOrStruct<S>> for StringOrStructOrNull<S> {
+    fn from(value: StringOrStruct<S>) -> Self {
+        match value {
+            StringOrStruct::String(s) => Self::String(s),
+            StringOrStruct::Struct(s) => Self::Struct(s),
+        }
+    }
+}
+
+impl<'de, S> Deserialize<'de> for StringOrStructOrNull<S>
+where
+    S: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let human_readable = deserializer.is_human_readable();
+        let policy = DispatchPolicy::default();
+        let value = match buffer(deserializer, &policy)? {
+            Value::Unit | Value::Option(None) => return Ok(Self::Null),
+            Value::Option(Some(value)) => *value,
+            value => value,
+        };
+        StringOrStruct::from_value(value, &policy, human_readable).map(Self::from)
+    }
+}
+
+impl<S> Serialize for StringOrStructOrNull<S>
+where
+    S: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            StringOrStructOrNull::String(s) => s.serialize(serializer),
+            StringOrStructOrNull::Struct(s) => s.serialize(serializer),
+            StringOrStructOrNull::Null => serializer.serialize_none(),
+        }
+    }
+}
+
+/// Like [`StringOrStructOrVec`], with a `Null` arm for explicit nulls, as
+/// OpenAPI-style documents allow for a reference, an inline object, a list
+/// or `null`.
+///
+/// Deserialization follows [`StringOrStructOrVec`], and `Null` is serialized
+/// back as `null`.
+///
+/// ```rust
+/// use serde_either::StringOrStructOrVecOrNull;
+/// use std::collections::HashMap;
+///
+/// type Schema = StringOrStructOrVecOrNull<HashMap<String, String>, Vec<String>>;
+///
+/// let reference: Schema = serde_json::from_str(r##""#/components/Pet""##).unwrap();
+/// let null: Schema = serde_json::from_str("null").unwrap();
+///
+/// assert!(matches!(reference, StringOrStructOrVecOrNull::String(_)));
+/// assert!(null.is_null());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum StringOrStructOrVecOrNull<S, V> {
+    String(String),
+    Struct(S),
+    Vec(V),
+    Null,
+}
+
+impl<S, V> StringOrStructOrVecOrNull<S, V> {
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    /// Turns the `Null` arm into `None`, and the others into the matching
+    /// arm of [`StringOrStructOrVec`].
+    pub fn into_option(self) -> Option<StringOrStructOrVec<S, V>> {
+        match self {
+            Self::String(s) => Some(StringOrStructOrVec::String(s)),
+            Self::Struct(s) => Some(StringOrStructOrVec::Struct(s)),
+            Self::Vec(v) => Some(StringOrStructOrVec::Vec(v)),
+            Self::Null => None,
+        }
+    }
+
+    /// Deserializes the value into `Some`, for use as
+    /// `#[serde(default, deserialize_with = "...")]` on an `Option` field,
+    /// so that `null` becomes `Some(Null)` rather than `None`.
+    pub fn deserialize_some<'de, D>(deserializer: D) -> Result<Option<Self>, D::Error>
+    where
+        D: Deserializer<'de>,
+        S: Deserialize<'de>,
+        V: Deserialize<'de>,
+    {
+        Self::deserialize(deserializer).map(Some)
+    }
+}
+
+impl<S, V> From<String> for StringOrStructOrVecOrNull<S, V> {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl<S, V> From<&str> for StringOrStructOrVecOrNull<S, V> {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_owned())
+    }
+}
+
+impl<S, V> From<AsStruct<S>> for StringOrStructOrVecOrNull<S, V> {
+    fn from(value: AsStruct<S>) -> Self {
+        Self::Struct(value.0)
+    }
+}
+
+impl<S, V> From<AsVec<V>> for StringOrStructOrVecOrNull<S, V> {
+    fn from(value: AsVec<V>) -> Self {
+        Self::Vec(value.0)
+    }
+}
+
+impl<S, V> From<StringOrStructOrVec<S, V>> for StringOrStructOrVecOrNull<S, V> {
+    fn from(value: StringOrStructOrVec<S, V>) -> Self {
+        match value {
+            StringOrStructOrVec::String(s) => Self::String(s),
+            StringOrStructOrVec::Struct(s) => Self::Struct(s),
+            StringOrStructOrVec::Vec(v) => Self::Vec(v),
+        }
+    }
+}
+
+impl<'de, S, V> Deserialize<'de> for StringOrStructOrVecOrNull<S, V>
+where
+    S: Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let human_readable = deserializer.is_human_readable();
+        let policy = DispatchPolicy::default();
+        let value = match buffer(deserializer, &policy)? {
+            Value::Unit | Value::Option(None) => return Ok(Self::Null),
+            Value::Option(Some(value)) => *value,
+            value => value,
+        };
+        StringOrStructOrVec::from_value(value, &policy, human_readable).map(Self::from)
+    }
+}
+
+impl<S, V> Serialize for StringOrStructOrVecOrNull<S, V>
+where
+    S: Serialize,
+    V: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            StringOrStructOrVecOrNull::String(s) => s.serialize(serializer),
+            StringOrStructOrVecOrNull::Struct(s) => s.serialize(serializer),
+            StringOrStructOrVecOrNull::Vec(v) => v.serialize(serializer),
+            StringOrStructOrVecOrNull::Null => serializer.serialize_none(),
+        }
+    }
+}
+
+/// Like [`SingleOrVec`], with a `Null` arm for explicit nulls, kept apart
+/// from empty sequences and serialized back as `null`.
+///
+/// As for [`StringOrStructOrNull`], wrap it in an `Option` with
+/// [`deserialize_some`](SingleOrVecOrNull::deserialize_some) to also tell
+/// missing fields apart.
+///
+/// Otherwise, `Null` reads as an empty collection: it is the default for
+/// missing fields, and has no elements. It can be written back as `null`,
+/// as `[]` with [`serialize_null_as_empty`](SingleOrVecOrNull::serialize_null_as_empty),
+/// or skipped with [`is_null`](SingleOrVecOrNull::is_null).
+///
+/// ```rust
+/// use serde::{Deserialize, Serialize};
+/// use serde_either::{SingleOrVec, SingleOrVecOrNull};
+///
+/// let null: SingleOrVecOrNull<u32> = serde_json::from_str("null").unwrap();
+/// let empty: SingleOrVecOrNull<u32> = serde_json::from_str("[]").unwrap();
+///
+/// assert!(null.is_null());
+/// assert_eq!(null.iter().count(), 0);
+/// assert_eq!(empty.into_option(), Some(SingleOrVec::Vec(vec![])));
+/// assert_eq!(serde_json::to_string(&null).unwrap(), "null");
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Task {
+///     #[serde(default, serialize_with = "SingleOrVecOrNull::serialize_null_as_empty")]
+///     tags: SingleOrVecOrNull<String>,
+///     #[serde(default, skip_serializing_if = "SingleOrVecOrNull::is_null")]
+///     owners: SingleOrVecOrNull<String>,
+/// }
+///
+/// let task: Task = serde_json::from_str("{}").unwrap();
+///
+/// assert!(task.tags.is_empty());
+/// assert_eq!(serde_json::to_string(&task).unwrap(), r#"{"tags":[]}"#);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub enum SingleOrVecOrNull<T> {
+    Single(T),
+    Vec(Vec<T>),
+    #[default]
+    Null,
+}
+
+impl<T> SingleOrVecOrNull<T> {
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    /// Turns the `Null` arm into `None`, and the others into the matching
+    /// arm of [`SingleOrVec`].
+    pub fn into_option(self) -> Option<SingleOrVec<T>> {
+        match self {
+            Self::Single(t) => Some(SingleOrVec::Single(t)),
+            Self::Vec(v) => Some(SingleOrVec::Vec(v)),
+            Self::Null => None,
+        }
+    }
+
+    /// Returns the elements, `Null` having none and `Single` one.
+    pub fn into_vec(self) -> Vec<T> {
+        match self {
+            Self::Single(t) => vec![t],
+            Self::Vec(v) => v,
+            Self::Null => Vec::new(),
+        }
+    }
+
+    /// Returns the elements, `Null` having none and `Single` one.
+    pub fn as_slice(&self) -> &[T] {
+        match self {
+            Self::Single(t) => std::slice::from_ref(t),
+            Self::Vec(v) => v,
+            Self::Null => &[],
+        }
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.as_slice().iter()
+    }
+
+    /// Returns the number of elements, `Null` counting as none.
+    pub fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.as_slice().is_empty()
+    }
+
+    /// Deserializes the value into `Some`, for use as
+    /// `#[serde(default, deserialize_with = "...")]` on an `Option` field,
+    /// so that `null` becomes `Some(Null)` rather than `None`.
+    pub fn deserialize_some<'de, D>(deserializer: D) -> Result<Option<Self>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Deserialize<'de>,
+    {
+        Self::deserialize(deserializer).map(Some)
+    }
+
+    /// Serializes `Null` as an empty sequence, for use as
+    /// `#[serde(serialize_with = "...")]` where `null` is not accepted.
+    pub fn serialize_null_as_empty<Se>(value: &Self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+        T: Serialize,
+    {
+        match value {
+            Self::Null => serializer.serialize_seq(Some(0))?.end(),
+            value => value.serialize(serializer),
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SingleOrVecOrNull<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> From<Vec<T>> for SingleOrVecOrNull<T> {
+    fn from(value: Vec<T>) -> Self {
+        Self::Vec(value)
+    }
+}
+
+impl<T> From<SingleOrVec<T>> for SingleOrVecOrNull<T> {
+    fn from(value: SingleOrVec<T>) -> Self {
+        match value {
+            SingleOrVec::Single(t) => Self::Single(t),
+            SingleOrVec::Vec(v) => Self::Vec(v),
+        }
+    }
+}
+
+impl<'de, T> Deserialize<'de> for SingleOrVecOrNull<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let human_readable = deserializer.is_human_readable();
+        let policy = DispatchPolicy::default();
+        let value = match buffer(deserializer, &policy)? {
+            Value::Unit | Value::Option(None) => return Ok(Self::Null),
+            Value::Option(Some(value)) => *value,
+            value => value,
+        };
+        SingleOrVec::from_value(value, &policy, human_readable).map(Self::from)
+    }
+}
+
+impl<T> Serialize for SingleOrVecOrNull<T>
+where
+    T: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            SingleOrVecOrNull::Single(t) => t.serialize(serializer),
+            SingleOrVecOrNull::Vec(v) => v.serialize(serializer),
+            SingleOrVecOrNull::Null => serializer.serialize_none(),
+        }
+    }
+}
+
+/// A null or a map, for plugins and features enabled with their defaults
+/// (`plugin: null`) or configured (`plugin: { ... }`).
+///
+/// Null and unit values are `Unit`, and so are empty maps when
+/// `EMPTY_MAP_AS_UNIT` is `true`. Other values are rejected. `Unit` is
+/// serialized back as a unit, which most formats write as null.
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use serde_either::UnitOrStruct;
+///
+/// #[derive(Deserialize, Debug, PartialEq)]
+/// struct Lint {
+///     strict: bool,
+/// }
+///
+/// let defaults: UnitOrStruct<Lint> = serde_yaml::from_str("~").unwrap();
+/// let strict: UnitOrStruct<Lint> = serde_yaml::from_str("strict: true").unwrap();
+/// let empty: UnitOrStruct<Lint, true> = serde_yaml::from_str("{}").unwrap();
+///
+/// assert_eq!(defaults, UnitOrStruct::Unit);
+/// assert_eq!(strict, UnitOrStruct::Struct(Lint { strict: true }));
+/// assert_eq!(empty, UnitOrStruct::Unit);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum UnitOrStruct<S, const EMPTY_MAP_AS_UNIT: bool = false> {
+    Unit,
+    Struct(S),
+}
+
+impl<S, const EMPTY_MAP_AS_UNIT: bool> UnitOrStruct<S, EMPTY_MAP_AS_UNIT> {
+    pub fn as_struct(&self) -> Option<&S> {
+        match self {
+            Self::Unit => None,
+            Self::Struct(s) => Some(s),
+        }
+    }
+
+    /// Returns the struct, or its default for the `Unit` arm.
+    pub fn unwrap_or_default(self) -> S
+    where
+        S: Default,
+    {
+        match self {
+            Self::Unit => S::default(),
+            Self::Struct(s) => s,
+        }
+    }
+}
+
+impl<'de, S, const EMPTY_MAP_AS_UNIT: bool> Deserialize<'de> for UnitOrStruct<S, EMPTY_MAP_AS_UNIT>
+where
+    S: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let human_readable = deserializer.is_human_readable();
+        let value = match buffer(deserializer, &DispatchPolicy::default())? {
+            Value::Option(Some(value)) => *value,
+            value => value,
+        };
+        match value {
+            Value::Unit | Value::Option(None) => Ok(Self::Unit),
+            Value::Map(map) if EMPTY_MAP_AS_UNIT && map.is_empty() => Ok(Self::Unit),
+            value @ Value::Map(_) => {
+                S::deserialize(Replay::new(value, human_readable)).map(Self::Struct)
+            }
+            value => Err(D::Error::invalid_type(unexpected(&value), &"null or a map")),
+        }
+    }
+}
+
+impl<S, const EMPTY_MAP_AS_UNIT: bool> Serialize for UnitOrStruct<S, EMPTY_MAP_AS_UNIT>
+where
+    S: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            UnitOrStruct::Unit => serializer.serialize_unit(),
+            UnitOrStruct::Struct(s) => s.serialize(serializer),
+        }
+    }
+}
@@ -0,0 +1,553 @@
+use crate::buffer::buffer;
+use crate::de::{number_token, unexpected, NUMBER_TOKEN};
+use crate::policy::DispatchPolicy;
+use crate::replay::Replay;
+use serde::de::{Deserialize, Deserializer, Error, IgnoredAny, MapAccess, Unexpected, Visitor};
+use serde::ser::{Serialize, Serializer};
+use serde_value::Value;
+use std::convert::TryFrom;
+use std::fmt;
+
+/// A number as found in self-describing formats.
+///
+/// Integers that fit in a `u64` are always `U64`, so `I64` only ever holds
+/// negative numbers, whichever way the format hands them over. Integers
+/// beyond 64 bits, as serde_json's `arbitrary_precision` feature can hand
+/// them over, are rejected rather than rounded to an `F64`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Number {
+    U64(u64),
+    I64(i64),
+    F64(f64),
+}
+
+impl Number {
+    pub fn as_u64(&self) -> Option<u64> {
+        match *self {
+            Number::U64(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Number::U64(n) => i64::try_from(n).ok(),
+            Number::I64(n) => Some(n),
+            Number::F64(_) => None,
+        }
+    }
+
+    /// Returns the number as a float, which may lose precision for integers
+    /// above 2^53.
+    pub fn as_f64(&self) -> f64 {
+        match *self {
+            Number::U64(n) => n as f64,
+            Number::I64(n) => n as f64,
+            Number::F64(n) => n,
+        }
+    }
+
+    fn from_i64(n: i64) -> Self {
+        match u64::try_from(n) {
+            Ok(n) => Number::U64(n),
+            Err(_) => Number::I64(n),
+        }
+    }
+
+    // parses numbers written as text, such as the ones encoded by
+    // serde_json's arbitrary_precision
+    fn parse(text: &str) -> Option<Self> {
+        if let Ok(n) = text.parse() {
+            return Some(Number::U64(n));
+        }
+        // through from_i64, so that "-0" is U64 like "0"
+        if let Ok(n) = text.parse() {
+            return Some(Number::from_i64(n));
+        }
+        text.parse().ok().map(Number::F64)
+    }
+
+    // parses numeric strings, which do not hold NaN or infinities
+    fn parse_finite(text: &str) -> Option<Self> {
+        Number::parse(text).filter(|n| match *n {
+            Number::F64(n) => n.is_finite(),
+            _ => true,
+        })
+    }
+
+    // reads integers, and floats without a fractional part
+    fn to_integer(self) -> Option<i128> {
+        match self {
+            Number::U64(n) => Some(n.into()),
+            Number::I64(n) => Some(n.into()),
+            // 2^64, the bound of the integers that fit in 64 bits
+            Number::F64(n) if n.fract() == 0.0 && n.abs() < 18446744073709551616.0 => {
+                Some(n as i128)
+            }
+            Number::F64(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Number::U64(n) => fmt::Display::fmt(n, f),
+            Number::I64(n) => fmt::Display::fmt(n, f),
+            Number::F64(n) => fmt::Display::fmt(n, f),
+        }
+    }
+}
+
+impl Serialize for Number {
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match *self {
+            Number::U64(n) => serializer.serialize_u64(n),
+            Number::I64(n) => serializer.serialize_i64(n),
+            Number::F64(n) => serializer.serialize_f64(n),
+        }
+    }
+}
+
+// whether the text of a number has no fraction or exponent
+fn is_integer(text: &str) -> bool {
+    let digits = text.strip_prefix(&['-', '+'][..]).unwrap_or(text);
+    !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Accepts the numbers of any visitor, `expecting` being used for errors.
+pub(crate) struct NumberVisitor<'a>(pub(crate) &'a str);
+
+impl<'de, 'a> Visitor<'de> for NumberVisitor<'a> {
+    type Value = Number;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.0)
+    }
+
+    fn visit_i64<E: Error>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(Number::from_i64(v))
+    }
+
+    fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(Number::U64(v))
+    }
+
+    fn visit_i128<E: Error>(self, v: i128) -> Result<Self::Value, E> {
+        match (i64::try_from(v), u64::try_from(v)) {
+            (Ok(v), _) => self.visit_i64(v),
+            (_, Ok(v)) => self.visit_u64(v),
+            _ => Err(E::custom(format!("{} does not fit in 64 bits", v))),
+        }
+    }
+
+    fn visit_u128<E: Error>(self, v: u128) -> Result<Self::Value, E> {
+        u64::try_from(v)
+            .map(Number::U64)
+            .map_err(|_| E::custom(format!("{} does not fit in 64 bits", v)))
+    }
+
+    fn visit_f64<E: Error>(self, v: f64) -> Result<Self::Value, E> {
+        Ok(Number::F64(v))
+    }
+
+    // serde_json's arbitrary_precision feature hands numbers over as a map
+    // with a single private key holding their text
+    fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        match access.next_key::<String>()? {
+            Some(key) if key == NUMBER_TOKEN => {
+                let text: String = access.next_value()?;
+                let number = match Number::parse(&text) {
+                    // integers beyond 64 bits would be rounded through f64
+                    Some(Number::F64(_)) if is_integer(&text) => {
+                        return Err(A::Error::custom(format!(
+                            "{} does not fit in 64 bits",
+                            text
+                        )))
+                    }
+                    Some(number) => number,
+                    None => return Err(A::Error::invalid_value(Unexpected::Str(&text), &self)),
+                };
+                match access.next_key::<IgnoredAny>()? {
+                    None => Ok(number),
+                    Some(_) => Err(A::Error::invalid_type(Unexpected::Map, &self)),
+                }
+            }
+            _ => Err(A::Error::invalid_type(Unexpected::Map, &self)),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Number {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(NumberVisitor("a number"))
+    }
+}
+
+/// A string or a number, for fields that APIs send both as `"42"` and `42`.
+///
+/// Strings are kept as they are, even when they hold a number.
+///
+/// ```rust
+/// use serde_either::{Number, StringOrNumber};
+///
+/// let quoted: StringOrNumber = serde_json::from_str(r#""42""#).unwrap();
+/// let number: StringOrNumber = serde_json::from_str("42").unwrap();
+///
+/// assert_eq!(quoted, StringOrNumber::String(String::from("42")));
+/// assert_eq!(number, StringOrNumber::Number(Number::U64(42)));
+/// assert_eq!(serde_json::to_string(&number).unwrap(), "42");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum StringOrNumber {
+    String(String),
+    Number(Number),
+}
+
+impl StringOrNumber {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s),
+            Self::Number(_) => None,
+        }
+    }
+
+    pub fn as_number(&self) -> Option<&Number> {
+        match self {
+            Self::String(_) => None,
+            Self::Number(n) => Some(n),
+        }
+    }
+
+    /// Reads the value as a number, parsing the `String` arm.
+    ///
+    /// Strings are trimmed before being parsed, and `None` is returned when
+    /// they do not hold a finite number.
+    pub fn to_number(&self) -> Option<Number> {
+        match self {
+            Self::String(s) => Number::parse_finite(s.trim()),
+            Self::Number(n) => Some(*n),
+        }
+    }
+
+    /// Reads the value as an `i64`, from an integer, a float without a
+    /// fractional part, or a string holding either of them.
+    pub fn as_i64(&self) -> Option<i64> {
+        i64::try_from(self.to_number()?.to_integer()?).ok()
+    }
+
+    /// Reads the value as a `u64`, like [`as_i64`](StringOrNumber::as_i64).
+    pub fn as_u64(&self) -> Option<u64> {
+        u64::try_from(self.to_number()?.to_integer()?).ok()
+    }
+
+    /// Reads the value as an `f64`, from any number or numeric string.
+    pub fn as_f64(&self) -> Option<f64> {
+        self.to_number().map(|n| n.as_f64())
+    }
+}
+
+impl fmt::Display for StringOrNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::String(s) => f.write_str(s),
+            Self::Number(n) => fmt::Display::fmt(n, f),
+        }
+    }
+}
+
+const STRING_OR_NUMBER: &str = "a string or a number";
+
+struct StringOrNumberVisitor;
+
+impl<'de> Visitor<'de> for StringOrNumberVisitor {
+    type Value = StringOrNumber;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(STRING_OR_NUMBER)
+    }
+
+    fn visit_i64<E: Error>(self, v: i64) -> Result<Self::Value, E> {
+        NumberVisitor(STRING_OR_NUMBER)
+            .visit_i64(v)
+            .map(StringOrNumber::Number)
+    }
+
+    fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+        NumberVisitor(STRING_OR_NUMBER)
+            .visit_u64(v)
+            .map(StringOrNumber::Number)
+    }
+
+    fn visit_i128<E: Error>(self, v: i128) -> Result<Self::Value, E> {
+        NumberVisitor(STRING_OR_NUMBER)
+            .visit_i128(v)
+            .map(StringOrNumber::Number)
+    }
+
+    fn visit_u128<E: Error>(self, v: u128) -> Result<Self::Value, E> {
+        NumberVisitor(STRING_OR_NUMBER)
+            .visit_u128(v)
+            .map(StringOrNumber::Number)
+    }
+
+    fn visit_f64<E: Error>(self, v: f64) -> Result<Self::Value, E> {
+        NumberVisitor(STRING_OR_NUMBER)
+            .visit_f64(v)
+            .map(StringOrNumber::Number)
+    }
+
+    fn visit_char<E: Error>(self, v: char) -> Result<Self::Value, E> {
+        Ok(StringOrNumber::String(v.to_string()))
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(StringOrNumber::String(v.to_owned()))
+    }
+
+    fn visit_string<E: Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(StringOrNumber::String(v))
+    }
+
+    fn visit_map<A>(self, access: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        NumberVisitor(STRING_OR_NUMBER)
+            .visit_map(access)
+            .map(StringOrNumber::Number)
+    }
+}
+
+impl<'de> Deserialize<'de> for StringOrNumber {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(StringOrNumberVisitor)
+    }
+}
+
+impl Serialize for StringOrNumber {
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            StringOrNumber::String(s) => s.serialize(serializer),
+            StringOrNumber::Number(n) => n.serialize(serializer),
+        }
+    }
+}
+
+/// A number, or a string holding one, for APIs that send `"1234"`, `1234`
+/// and `1234.0` alike.
+///
+/// Unlike [`StringOrNumber`], strings that do not hold a finite number, once
+/// trimmed, are rejected, so the accessors of deserialized values only fail
+/// on range. The original representation is kept, and serialized back as it
+/// was.
+///
+/// ```rust
+/// use serde_either::NumericStringOrNumber;
+///
+/// let quoted: NumericStringOrNumber = serde_json::from_str(r#""1234""#).unwrap();
+/// let float: NumericStringOrNumber = serde_json::from_str("1234.0").unwrap();
+///
+/// assert_eq!(quoted.as_i64(), Some(1234));
+/// assert_eq!(float.as_i64(), Some(1234));
+/// assert_eq!(serde_json::to_string(&quoted).unwrap(), r#""1234""#);
+/// assert!(serde_json::from_str::<NumericStringOrNumber>(r#""n/a""#).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum NumericStringOrNumber {
+    String(String),
+    Number(Number),
+}
+
+impl NumericStringOrNumber {
+    /// Returns the number, parsing the `String` arm.
+    ///
+    /// Only `String` arms built by hand can fail to parse.
+    pub fn to_number(&self) -> Option<Number> {
+        match self {
+            Self::String(s) => Number::parse_finite(s.trim()),
+            Self::Number(n) => Some(*n),
+        }
+    }
+
+    /// Reads the value as an `i64`, from an integer or a float without a
+    /// fractional part, whether it was quoted or not.
+    pub fn as_i64(&self) -> Option<i64> {
+        i64::try_from(self.to_number()?.to_integer()?).ok()
+    }
+
+    /// Reads the value as a `u64`, like [`as_i64`](NumericStringOrNumber::as_i64).
+    pub fn as_u64(&self) -> Option<u64> {
+        u64::try_from(self.to_number()?.to_integer()?).ok()
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        self.to_number().map(|n| n.as_f64())
+    }
+
+    pub fn is_string(&self) -> bool {
+        matches!(self, Self::String(_))
+    }
+}
+
+impl TryFrom<StringOrNumber> for NumericStringOrNumber {
+    type Error = String;
+
+    // fails with the string when it does not hold a number
+    fn try_from(value: StringOrNumber) -> Result<Self, String> {
+        match value {
+            StringOrNumber::String(s) if Number::parse_finite(s.trim()).is_some() => {
+                Ok(Self::String(s))
+            }
+            StringOrNumber::String(s) => Err(s),
+            StringOrNumber::Number(n) => Ok(Self::Number(n)),
+        }
+    }
+}
+
+impl From<Number> for NumericStringOrNumber {
+    fn from(n: Number) -> Self {
+        Self::Number(n)
+    }
+}
+
+impl fmt::Display for NumericStringOrNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::String(s) => f.write_str(s),
+            Self::Number(n) => fmt::Display::fmt(n, f),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for NumericStringOrNumber {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Self::try_from(StringOrNumber::deserialize(deserializer)?).map_err(|s| {
+            D::Error::invalid_value(Unexpected::Str(&s), &"a number or a numeric string")
+        })
+    }
+}
+
+impl Serialize for NumericStringOrNumber {
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            NumericStringOrNumber::String(s) => s.serialize(serializer),
+            NumericStringOrNumber::Number(n) => n.serialize(serializer),
+        }
+    }
+}
+
+/// A number or a map, for settings written either as a single count or in
+/// detail (`retry: 3` vs `retry: { count: 3, backoff: exp }`).
+///
+/// Numbers encoded by serde_json's `arbitrary_precision` feature are numbers
+/// too. Anything other than numbers and maps is rejected.
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use serde_either::{Number, NumberOrStruct};
+///
+/// #[derive(Deserialize, Debug, PartialEq)]
+/// struct Retry {
+///     count: u32,
+///     backoff: String,
+/// }
+///
+/// let short: NumberOrStruct<Retry> = serde_yaml::from_str("3").unwrap();
+/// let long: NumberOrStruct<Retry> = serde_yaml::from_str("{ count: 3, backoff: exp }").unwrap();
+///
+/// assert_eq!(short, NumberOrStruct::Number(Number::U64(3)));
+/// assert!(long.as_struct().is_some());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum NumberOrStruct<S> {
+    Number(Number),
+    Struct(S),
+}
+
+impl<S> NumberOrStruct<S> {
+    pub fn as_number(&self) -> Option<&Number> {
+        match self {
+            Self::Number(n) => Some(n),
+            Self::Struct(_) => None,
+        }
+    }
+
+    pub fn as_struct(&self) -> Option<&S> {
+        match self {
+            Self::Number(_) => None,
+            Self::Struct(s) => Some(s),
+        }
+    }
+}
+
+impl<'de, S> Deserialize<'de> for NumberOrStruct<S>
+where
+    S: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let human_readable = deserializer.is_human_readable();
+        let value = buffer(deserializer, &DispatchPolicy::default())?;
+        match value {
+            Value::Map(_) if number_token(&value).is_none() => {
+                S::deserialize(Replay::new(value, human_readable)).map(Self::Struct)
+            }
+            Value::Map(_)
+            | Value::U8(_)
+            | Value::U16(_)
+            | Value::U32(_)
+            | Value::U64(_)
+            | Value::I8(_)
+            | Value::I16(_)
+            | Value::I32(_)
+            | Value::I64(_)
+            | Value::F32(_)
+            | Value::F64(_) => {
+                Number::deserialize(Replay::new(value, human_readable)).map(Self::Number)
+            }
+            _ => Err(D::Error::invalid_type(
+                unexpected(&value),
+                &"a number or a map",
+            )),
+        }
+    }
+}
+
+impl<S> Serialize for NumberOrStruct<S>
+where
+    S: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            NumberOrStruct::Number(n) => n.serialize(serializer),
+            NumberOrStruct::Struct(s) => s.serialize(serializer),
+        }
+    }
+}
@@ -0,0 +1,477 @@
+use crate::buffer::buffer;
+use crate::de::unexpected;
+use crate::policy::DispatchPolicy;
+use crate::replay::Replay;
+use crate::warnings::scoped;
+use serde::de::{self, Deserialize, Deserializer, Error, Visitor};
+use serde::ser::{Serialize, Serializer};
+use serde_value::Value;
+use std::cell::Cell;
+use std::fmt;
+
+/// Tuples that can be used as the alternatives of a [`OneOf`] or an [`AnyOf`].
+pub trait Alternatives {
+    /// The tuple of options holding the value, e.g. `(Option<A>, Option<B>)`.
+    type Options;
+
+    /// Returns how many of the options are `Some`.
+    fn count_some(options: &Self::Options) -> usize;
+}
+
+// declares a union over the alternatives of a tuple, along with the impls
+// that do not depend on how the alternatives are tried
+macro_rules! union_type {
+    ($(#[$attr:meta])* $union:ident) => {
+        $(#[$attr])*
+        pub struct $union<T: Alternatives>(T::Options);
+
+        impl<T: Alternatives> $union<T> {
+            /// Wraps a tuple of options, returning `None` unless exactly one of them
+            /// is `Some`.
+            pub fn from_options(options: T::Options) -> Option<Self> {
+                if T::count_some(&options) == 1 {
+                    Some($union(options))
+                } else {
+                    None
+                }
+            }
+
+            pub fn as_options(&self) -> &T::Options {
+                &self.0
+            }
+
+            pub fn into_options(self) -> T::Options {
+                self.0
+            }
+        }
+
+        impl<T: Alternatives> fmt::Debug for $union<T>
+        where
+            T::Options: fmt::Debug,
+        {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_tuple(stringify!($union)).field(&self.0).finish()
+            }
+        }
+
+        impl<T: Alternatives> Clone for $union<T>
+        where
+            T::Options: Clone,
+        {
+            fn clone(&self) -> Self {
+                $union(self.0.clone())
+            }
+        }
+
+        impl<T: Alternatives> PartialEq for $union<T>
+        where
+            T::Options: PartialEq,
+        {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+    };
+}
+
+union_type! {
+    /// An experimental union of any number of types, written as a tuple:
+    /// `OneOf<(A, B, C)>`.
+    ///
+    /// Each alternative is probed for the shape of input it asks its deserializer
+    /// for (a string, a number, a map, a sequence, ...). The input is buffered
+    /// once and handed to the first alternative, in declared order, whose shape
+    /// matches it. Alternatives that accept any shape, such as untagged enums or
+    /// the enums of this crate, are tried in turn until one succeeds.
+    ///
+    /// The value is stored as a tuple of options, exactly one of which is
+    /// `Some`. Tuples of 2 to 12 alternatives are supported.
+    ///
+    /// ```rust
+    /// use serde_either::OneOf;
+    /// use std::collections::HashMap;
+    ///
+    /// type Field = OneOf<(bool, u64, String, Vec<u64>, HashMap<String, u64>)>;
+    ///
+    /// let res: Field = serde_json::from_str("[1, 2]").unwrap();
+    /// assert_eq!(res.index(), 3);
+    ///
+    /// match res.into_options() {
+    ///     (_, _, _, Some(numbers), _) => assert_eq!(numbers, vec![1, 2]),
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    OneOf
+}
+
+union_type! {
+    /// A union of any number of types, written as a tuple: `AnyOf<(A, B, C)>`.
+    ///
+    /// The input is buffered once, and each alternative is tried in declared
+    /// order, the first one to succeed holding the value. Unlike [`OneOf`], no
+    /// alternative is skipped based on the shape it asks for, so types accepting
+    /// several shapes, or coercing one into another, are given every value.
+    ///
+    /// The value is stored as a tuple of options, exactly one of which is
+    /// `Some`. Tuples of 2 to 12 alternatives are supported.
+    ///
+    /// ```rust
+    /// use serde_either::AnyOf;
+    /// use std::net::IpAddr;
+    ///
+    /// type Host = AnyOf<(IpAddr, String)>;
+    ///
+    /// let ip: Host = serde_json::from_str(r#""127.0.0.1""#).unwrap();
+    /// let name: Host = serde_json::from_str(r#""localhost""#).unwrap();
+    ///
+    /// assert_eq!(ip.index(), 0);
+    /// assert_eq!(name.index(), 1);
+    /// ```
+    AnyOf
+}
+
+// the shape of input a type asks its deserializer for
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Shape {
+    Any,
+    Bool,
+    Integer,
+    Float,
+    Char,
+    String,
+    Bytes,
+    Unit,
+    Seq,
+    Map,
+    Enum,
+}
+
+#[derive(Clone, Copy)]
+struct Probed {
+    shape: Shape,
+    nullable: bool,
+}
+
+impl Probed {
+    fn matches(&self, value: &Value) -> bool {
+        if let Value::Unit | Value::Option(None) = value {
+            if self.nullable {
+                return true;
+            }
+        }
+        match (self.shape, value) {
+            (Shape::Any, _) => true,
+            (Shape::Bool, Value::Bool(_)) => true,
+            (Shape::Integer, value) | (Shape::Float, value) if is_integer(value) => true,
+            (Shape::Float, Value::F32(_)) | (Shape::Float, Value::F64(_)) => true,
+            (Shape::Char, Value::Char(_)) | (Shape::Char, Value::String(_)) => true,
+            (Shape::String, Value::String(_)) | (Shape::String, Value::Char(_)) => true,
+            (Shape::Bytes, Value::Bytes(_))
+            | (Shape::Bytes, Value::String(_))
+            | (Shape::Bytes, Value::Seq(_)) => true,
+            (Shape::String, Value::Bytes(_)) => true,
+            (Shape::Unit, Value::Unit) => true,
+            (Shape::Seq, Value::Seq(_)) => true,
+            (Shape::Map, Value::Map(_)) => true,
+            (Shape::Enum, Value::String(_)) | (Shape::Enum, Value::Map(_)) => true,
+            _ => false,
+        }
+    }
+}
+
+fn is_integer(value: &Value) -> bool {
+    matches!(
+        value,
+        Value::U8(_)
+            | Value::U16(_)
+            | Value::U32(_)
+            | Value::U64(_)
+            | Value::I8(_)
+            | Value::I16(_)
+            | Value::I32(_)
+            | Value::I64(_)
+    )
+}
+
+#[derive(Debug)]
+struct ProbeError;
+
+impl fmt::Display for ProbeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("probe")
+    }
+}
+
+impl std::error::Error for ProbeError {}
+
+impl de::Error for ProbeError {
+    fn custom<T: fmt::Display>(_msg: T) -> Self {
+        ProbeError
+    }
+}
+
+// a deserializer that only records which deserialize method was called
+struct Probe<'a> {
+    shape: &'a Cell<Option<Shape>>,
+    nullable: &'a Cell<bool>,
+}
+
+impl<'a> Probe<'a> {
+    fn record<T>(&self, shape: Shape) -> Result<T, ProbeError> {
+        self.shape.set(Some(shape));
+        Err(ProbeError)
+    }
+}
+
+macro_rules! probe_methods {
+    ($($method:ident => $shape:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, ProbeError> {
+                self.record(Shape::$shape)
+            }
+        )*
+    };
+}
+
+impl<'de, 'a> Deserializer<'de> for Probe<'a> {
+    type Error = ProbeError;
+
+    probe_methods! {
+        deserialize_any => Any,
+        deserialize_ignored_any => Any,
+        deserialize_bool => Bool,
+        deserialize_i8 => Integer,
+        deserialize_i16 => Integer,
+        deserialize_i32 => Integer,
+        deserialize_i64 => Integer,
+        deserialize_u8 => Integer,
+        deserialize_u16 => Integer,
+        deserialize_u32 => Integer,
+        deserialize_u64 => Integer,
+        deserialize_f32 => Float,
+        deserialize_f64 => Float,
+        deserialize_char => Char,
+        deserialize_str => String,
+        deserialize_string => String,
+        deserialize_identifier => String,
+        deserialize_bytes => Bytes,
+        deserialize_byte_buf => Bytes,
+        deserialize_unit => Unit,
+        deserialize_seq => Seq,
+        deserialize_map => Map,
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ProbeError> {
+        self.nullable.set(true);
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, ProbeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _visitor: V,
+    ) -> Result<V::Value, ProbeError> {
+        self.record(Shape::Unit)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        _visitor: V,
+    ) -> Result<V::Value, ProbeError> {
+        self.record(Shape::Seq)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        _visitor: V,
+    ) -> Result<V::Value, ProbeError> {
+        self.record(Shape::Seq)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, ProbeError> {
+        self.record(Shape::Map)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, ProbeError> {
+        self.record(Shape::Enum)
+    }
+}
+
+fn probe<'de, T: Deserialize<'de>>() -> Probed {
+    let shape = Cell::new(None);
+    let nullable = Cell::new(false);
+    let _ = T::deserialize(Probe {
+        shape: &shape,
+        nullable: &nullable,
+    });
+    Probed {
+        // types that never ask for anything are given every value
+        shape: shape.get().unwrap_or(Shape::Any),
+        nullable: nullable.get(),
+    }
+}
+
+// tries an alternative when its probed shape matches the buffered value,
+// returning `None` when it is skipped
+fn attempt_matching<'de, T: Deserialize<'de>, E: Error>(
+    value: &Value,
+    human_readable: bool,
+) -> Option<Result<T, E>> {
+    if probe::<T>().matches(value) {
+        attempt_any(value, human_readable)
+    } else {
+        None
+    }
+}
+
+// tries an alternative whatever shape it asks for
+fn attempt_any<'de, T: Deserialize<'de>, E: Error>(
+    value: &Value,
+    human_readable: bool,
+) -> Option<Result<T, E>> {
+    Some(scoped(|| {
+        T::deserialize(Replay::new(value.clone(), human_readable))
+    }))
+}
+
+struct ExpectedAlternatives {
+    quantifier: &'static str,
+    len: usize,
+}
+
+impl de::Expected for ExpectedAlternatives {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "{} of {} alternatives",
+            self.quantifier, self.len
+        )
+    }
+}
+
+// the error returned when no alternative holds the value, keeping the error
+// of the last one tried
+fn no_alternative<E: Error>(value: &Value, expected: ExpectedAlternatives, last: Option<E>) -> E {
+    let error = E::invalid_type(unexpected(value), &expected);
+    match last {
+        Some(last) => E::custom(format_args!(
+            "{}, the last one tried failed with: {}",
+            error, last
+        )),
+        None => error,
+    }
+}
+
+// implements a union for a tuple, `$attempt` deciding which alternatives are
+// tried and `$quantifier` naming the union in errors
+macro_rules! union_impls {
+    ($union:ident, $attempt:ident, $quantifier:literal; $len:expr => $($name:ident $index:tt),+) => {
+        impl<$($name),+> $union<($($name,)+)> {
+            /// Returns the position of the alternative holding the value.
+            pub fn index(&self) -> usize {
+                $(
+                    if self.0.$index.is_some() {
+                        return $index;
+                    }
+                )+
+                unreachable!(concat!(stringify!($union), " holds no value"))
+            }
+        }
+
+        impl<'de, $($name),+> Deserialize<'de> for $union<($($name,)+)>
+        where
+            $($name: Deserialize<'de>,)+
+        {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                let human_readable = deserializer.is_human_readable();
+                let value = buffer(deserializer, &DispatchPolicy::default())?;
+                let mut last = None;
+                #[allow(unused_mut)]
+                let mut options: ($(Option<$name>,)+) = Default::default();
+                $(
+                    match $attempt::<$name, D::Error>(&value, human_readable) {
+                        Some(Ok(found)) => {
+                            options.$index = Some(found);
+                            return Ok($union(options));
+                        }
+                        Some(Err(e)) => last = Some(e),
+                        None => {}
+                    }
+                )+
+                let expected = ExpectedAlternatives {
+                    quantifier: $quantifier,
+                    len: $len,
+                };
+                Err(no_alternative(&value, expected, last))
+            }
+        }
+
+        impl<$($name),+> Serialize for $union<($($name,)+)>
+        where
+            $($name: Serialize,)+
+        {
+            fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+            where
+                Se: Serializer,
+            {
+                $(
+                    if let Some(value) = &self.0.$index {
+                        return value.serialize(serializer);
+                    }
+                )+
+                Err(serde::ser::Error::custom(concat!(stringify!($union), " holds no value")))
+            }
+        }
+    };
+}
+
+macro_rules! one_of_impls {
+    ($len:expr => $($name:ident $index:tt),+) => {
+        impl<$($name),+> Alternatives for ($($name,)+) {
+            type Options = ($(Option<$name>,)+);
+
+            fn count_some(options: &Self::Options) -> usize {
+                0 $(+ usize::from(options.$index.is_some()))+
+            }
+        }
+
+        union_impls!(OneOf, attempt_matching, "one"; $len => $($name $index),+);
+        union_impls!(AnyOf, attempt_any, "any"; $len => $($name $index),+);
+    };
+}
+
+one_of_impls!(2 => T0 0, T1 1);
+one_of_impls!(3 => T0 0, T1 1, T2 2);
+one_of_impls!(4 => T0 0, T1 1, T2 2, T3 3);
+one_of_impls!(5 => T0 0, T1 1, T2 2, T3 3, T4 4);
+one_of_impls!(6 => T0 0, T1 1, T2 2, T3 3, T4 4, T5 5);
+one_of_impls!(7 => T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6);
+one_of_impls!(8 => T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7);
+one_of_impls!(9 => T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8);
+one_of_impls!(10 => T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9);
+one_of_impls!(11 => T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10);
+one_of_impls!(12 => T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10, T11 11);
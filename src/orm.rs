@@ -0,0 +1,73 @@
+use crate::enums::{OneOrMany, SingleOrVec, StringOrStruct, StringOrStructOrVec};
+use sea_orm::sea_query::{ArrayType, ColumnType, Nullable, ValueType, ValueTypeErr};
+use sea_orm::{ActiveValue, IntoActiveValue, TryGetableFromJson, Value};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+// stores the enums in JSON columns, like sea-orm's FromJsonQueryResult derive
+macro_rules! json_column {
+    ($name:ident<$($param:ident),+>) => {
+        impl<$($param),+> TryGetableFromJson for $name<$($param),+>
+        where
+            $($param: DeserializeOwned,)+
+        {
+        }
+
+        impl<$($param),+> From<$name<$($param),+>> for Value
+        where
+            $($param: Serialize,)+
+        {
+            fn from(source: $name<$($param),+>) -> Self {
+                Value::Json(Some(Box::new(serde_json::to_value(&source).expect(concat!(
+                    "Failed to serialize '",
+                    stringify!($name),
+                    "'"
+                )))))
+            }
+        }
+
+        impl<$($param),+> ValueType for $name<$($param),+>
+        where
+            $($param: DeserializeOwned,)+
+        {
+            fn try_from(v: Value) -> Result<Self, ValueTypeErr> {
+                match v {
+                    Value::Json(Some(json)) => serde_json::from_value(*json).map_err(|_| ValueTypeErr),
+                    _ => Err(ValueTypeErr),
+                }
+            }
+
+            fn type_name() -> String {
+                stringify!($name).to_owned()
+            }
+
+            fn array_type() -> ArrayType {
+                ArrayType::Json
+            }
+
+            fn column_type() -> ColumnType {
+                ColumnType::Json
+            }
+        }
+
+        impl<$($param),+> Nullable for $name<$($param),+> {
+            fn null() -> Value {
+                Value::Json(None)
+            }
+        }
+
+        impl<$($param),+> IntoActiveValue<$name<$($param),+>> for $name<$($param),+>
+        where
+            $($param: Serialize,)+
+        {
+            fn into_active_value(self) -> ActiveValue<Self> {
+                ActiveValue::set(self)
+            }
+        }
+    };
+}
+
+json_column!(StringOrStruct<S>);
+json_column!(StringOrStructOrVec<S, V>);
+json_column!(SingleOrVec<S>);
+json_column!(OneOrMany<S>);
@@ -0,0 +1,84 @@
+use crate::enums::StringOrStruct;
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+use std::path::PathBuf;
+
+/// Either a path written as a string, or a structured form, for build-tool
+/// configurations such as `entry: src/main.ts` vs
+/// `entry: { path: src/main.ts, name: app }`.
+///
+/// Strings are kept as `P`, a [`PathBuf`] by default, or any type built from
+/// a `String` such as camino's `Utf8PathBuf` (see `Utf8PathOrStruct` with the
+/// `camino` feature).
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use serde_either::PathOrStruct;
+/// use std::path::PathBuf;
+///
+/// #[derive(Deserialize)]
+/// struct Entry {
+///     path: String,
+/// }
+///
+/// let res: PathOrStruct<Entry> = serde_json::from_str(r#""src/main.ts""#).unwrap();
+///
+/// assert_eq!(res.as_path(), Some(&PathBuf::from("src/main.ts")));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PathOrStruct<S, P = PathBuf> {
+    Path(P),
+    Struct(S),
+}
+
+/// A [`PathOrStruct`] keeping paths as UTF-8 paths.
+#[cfg(feature = "camino")]
+pub type Utf8PathOrStruct<S> = PathOrStruct<S, camino::Utf8PathBuf>;
+
+impl<S, P> PathOrStruct<S, P> {
+    pub fn as_path(&self) -> Option<&P> {
+        match self {
+            Self::Path(path) => Some(path),
+            Self::Struct(_) => None,
+        }
+    }
+
+    pub fn as_struct(&self) -> Option<&S> {
+        match self {
+            Self::Path(_) => None,
+            Self::Struct(s) => Some(s),
+        }
+    }
+}
+
+impl<'de, S, P> Deserialize<'de> for PathOrStruct<S, P>
+where
+    S: Deserialize<'de>,
+    P: From<String>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match StringOrStruct::<S>::deserialize(deserializer)? {
+            StringOrStruct::String(s) => Ok(Self::Path(P::from(s))),
+            StringOrStruct::Struct(s) => Ok(Self::Struct(s)),
+        }
+    }
+}
+
+impl<S, P> Serialize for PathOrStruct<S, P>
+where
+    S: Serialize,
+    P: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            PathOrStruct::Path(path) => path.serialize(serializer),
+            PathOrStruct::Struct(s) => s.serialize(serializer),
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use crate::buffer::buffer;
+use crate::de::classify;
+use crate::kind::Kind;
+use crate::policy::DispatchPolicy;
+use crate::warnings::silenced;
+use serde::de::Deserializer;
+
+const KINDS: [Kind; 3] = [Kind::String, Kind::Struct, Kind::Vec];
+
+/// Reports which arm of a [`StringOrStructOrVec`](crate::StringOrStructOrVec)
+/// a value would land in, without constructing any arm.
+///
+/// The value is buffered and classified exactly as deserialization does, so
+/// values that would be rejected, including by the length and depth limits
+/// of the policy, return the same error. The deserializer is left in the
+/// same state as after deserializing the value. Pass a
+/// `serde_value::ValueDeserializer` to classify an already buffered value.
+///
+/// ```rust
+/// use serde_either::{peek_kind, Kind};
+///
+/// let mut deserializer = serde_json::Deserializer::from_str(r#"{"a": [1, 2]}"#);
+/// assert_eq!(peek_kind(&mut deserializer).unwrap(), Kind::Struct);
+///
+/// let mut deserializer = serde_json::Deserializer::from_str("false");
+/// assert!(peek_kind(&mut deserializer).is_err());
+/// ```
+pub fn peek_kind<'de, D>(deserializer: D) -> Result<Kind, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    DispatchPolicy::default().peek_kind(deserializer)
+}
+
+impl DispatchPolicy {
+    /// Like [`peek_kind`], applying this policy.
+    pub fn peek_kind<'de, D>(&self, deserializer: D) -> Result<Kind, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = buffer(deserializer, self)?;
+        silenced(|| classify::<D::Error>(value, &KINDS, self)).map(|(kind, _)| kind)
+    }
+}
@@ -0,0 +1,238 @@
+use crate::kind::Kind;
+use serde::de::{DeserializeSeed, Deserializer};
+use std::marker::PhantomData;
+
+/// Runtime configuration of how a value is dispatched to the arms of the
+/// enums in this crate.
+///
+/// The default policy behaves exactly like the plain `Deserialize`
+/// implementations. Use it through [`DispatchPolicy::seed`]:
+///
+/// ```rust
+/// use serde::de::DeserializeSeed;
+/// use serde_either::{DispatchPolicy, StringOrStruct};
+/// use std::collections::HashMap;
+///
+/// let policy = DispatchPolicy::new().numbers_as_string(true);
+///
+/// let mut deserializer = serde_json::Deserializer::from_str("42");
+/// let value = policy
+///     .seed::<StringOrStruct<HashMap<String, String>>>()
+///     .deserialize(&mut deserializer)
+///     .unwrap();
+///
+/// assert_eq!(value, StringOrStruct::String(String::from("42")));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DispatchPolicy {
+    allow_string: bool,
+    allow_struct: bool,
+    allow_vec: bool,
+    numbers_as_string: bool,
+    seq_of_one_as_struct: bool,
+    stream_structs: bool,
+    max_string_len: Option<usize>,
+    max_seq_len: Option<usize>,
+    max_depth: usize,
+}
+
+/// The nesting depth allowed by default, matching the recursion limit of
+/// serde_json.
+pub const DEFAULT_MAX_DEPTH: usize = 128;
+
+impl Default for DispatchPolicy {
+    fn default() -> Self {
+        DispatchPolicy {
+            allow_string: true,
+            allow_struct: true,
+            allow_vec: true,
+            numbers_as_string: false,
+            seq_of_one_as_struct: false,
+            stream_structs: false,
+            max_string_len: None,
+            max_seq_len: None,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+}
+
+impl DispatchPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accepts values of the given kind. All kinds are allowed by default.
+    pub fn allow(self, kind: Kind) -> Self {
+        self.set_allowed(kind, true)
+    }
+
+    /// Rejects values of the given kind with an invalid type error.
+    ///
+    /// Only types with an arm of that kind are affected. [`SingleOrVec`]
+    /// has no String arm, and hands strings to its Single arm as the Struct
+    /// kind, so denying [`Kind::String`] leaves it unchanged; deny
+    /// [`Kind::Struct`] to keep it from reading single values. Likewise,
+    /// [`StringOrStruct`] has no Vec arm, and hands sequences to its Struct
+    /// arm, so they are rejected by denying [`Kind::Struct`], not
+    /// [`Kind::Vec`].
+    ///
+    /// [`SingleOrVec`]: crate::SingleOrVec
+    /// [`StringOrStruct`]: crate::StringOrStruct
+    pub fn deny(self, kind: Kind) -> Self {
+        self.set_allowed(kind, false)
+    }
+
+    /// Sends numbers to the String arm, formatted as their decimal text.
+    pub fn numbers_as_string(mut self, enabled: bool) -> Self {
+        self.numbers_as_string = enabled;
+        self
+    }
+
+    /// Treats a sequence with exactly one element as that element, which then
+    /// lands in the Struct (or Single) arm.
+    pub fn seq_of_one_as_struct(mut self, enabled: bool) -> Self {
+        self.seq_of_one_as_struct = enabled;
+        self
+    }
+
+    /// Hands maps to the Struct arm while they are being read, instead of
+    /// buffering them first.
+    ///
+    /// Buffered maps are deserialized again from their buffered form, out of
+    /// reach of the original deserializer. Streaming them keeps wrappers such
+    /// as `serde_ignored` or `serde_path_to_error` working inside the Struct
+    /// arm, and saves an allocation per value. The length and depth limits
+    /// do not apply to streamed maps, and toml datetimes can no longer fall
+    /// back to the String arm when the Struct arm rejects them.
+    ///
+    /// ```rust
+    /// use serde::de::DeserializeSeed;
+    /// use serde::Deserialize;
+    /// use serde_either::{DispatchPolicy, StringOrStruct};
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Output {
+    ///     path: String,
+    /// }
+    ///
+    /// let policy = DispatchPolicy::new().stream_structs(true);
+    /// let json = r#"{"path": "dist", "verbose": true}"#;
+    ///
+    /// let mut ignored = Vec::new();
+    /// let mut deserializer = serde_json::Deserializer::from_str(json);
+    /// let mut callback = |path: serde_ignored::Path| ignored.push(path.to_string());
+    /// let deserializer = serde_ignored::Deserializer::new(&mut deserializer, &mut callback);
+    /// policy
+    ///     .seed::<StringOrStruct<Output>>()
+    ///     .deserialize(deserializer)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(ignored, vec!["verbose"]);
+    /// ```
+    pub fn stream_structs(mut self, enabled: bool) -> Self {
+        self.stream_structs = enabled;
+        self
+    }
+
+    /// Rejects strings and byte strings longer than `max` bytes while the
+    /// input is being buffered.
+    pub fn max_string_len(mut self, max: usize) -> Self {
+        self.max_string_len = Some(max);
+        self
+    }
+
+    /// Rejects sequences and maps with more than `max` entries while the
+    /// input is being buffered.
+    pub fn max_seq_len(mut self, max: usize) -> Self {
+        self.max_seq_len = Some(max);
+        self
+    }
+
+    /// Rejects values nested more than `max` sequences, maps, options or
+    /// newtypes deep while the input is being buffered, so that deeply
+    /// nested input produces an error instead of overflowing the stack.
+    /// Defaults to [`DEFAULT_MAX_DEPTH`].
+    pub fn max_depth(mut self, max: usize) -> Self {
+        self.max_depth = max;
+        self
+    }
+
+    pub fn is_allowed(&self, kind: Kind) -> bool {
+        match kind {
+            Kind::String => self.allow_string,
+            Kind::Struct => self.allow_struct,
+            Kind::Vec => self.allow_vec,
+        }
+    }
+
+    pub(crate) fn is_numbers_as_string(&self) -> bool {
+        self.numbers_as_string
+    }
+
+    pub(crate) fn is_seq_of_one_as_struct(&self) -> bool {
+        self.seq_of_one_as_struct
+    }
+
+    pub(crate) fn is_streaming_structs(&self) -> bool {
+        self.stream_structs
+    }
+
+    pub(crate) fn string_len_limit(&self) -> Option<usize> {
+        self.max_string_len
+    }
+
+    pub(crate) fn seq_len_limit(&self) -> Option<usize> {
+        self.max_seq_len
+    }
+
+    pub(crate) fn depth_limit(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Returns a `DeserializeSeed` that deserializes `T` using this policy.
+    pub fn seed<T>(&self) -> DispatchSeed<'_, T> {
+        DispatchSeed {
+            policy: self,
+            marker: PhantomData,
+        }
+    }
+
+    fn set_allowed(mut self, kind: Kind, allowed: bool) -> Self {
+        match kind {
+            Kind::String => self.allow_string = allowed,
+            Kind::Struct => self.allow_struct = allowed,
+            Kind::Vec => self.allow_vec = allowed,
+        }
+        self
+    }
+}
+
+/// Types whose deserialization can be driven by a [`DispatchPolicy`].
+pub trait DeserializeWithPolicy<'de>: Sized {
+    fn deserialize_with_policy<D>(
+        deserializer: D,
+        policy: &DispatchPolicy,
+    ) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>;
+}
+
+/// A `DeserializeSeed` created by [`DispatchPolicy::seed`].
+pub struct DispatchSeed<'a, T> {
+    policy: &'a DispatchPolicy,
+    marker: PhantomData<T>,
+}
+
+impl<'de, 'a, T> DeserializeSeed<'de> for DispatchSeed<'a, T>
+where
+    T: DeserializeWithPolicy<'de>,
+{
+    type Value = T;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::deserialize_with_policy(deserializer, self.policy)
+    }
+}
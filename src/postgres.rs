@@ -0,0 +1,43 @@
+use crate::enums::{OneOrMany, SingleOrVec, StringOrStruct, StringOrStructOrVec};
+use bytes::BytesMut;
+use postgres_types::{accepts, to_sql_checked, FromSql, IsNull, Json, ToSql, Type};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt::Debug;
+
+// stores the enums in JSON and JSONB columns, through the Json wrapper of postgres-types
+macro_rules! json_sql {
+    ($name:ident<$($param:ident),+>) => {
+        impl<'a, $($param),+> FromSql<'a> for $name<$($param),+>
+        where
+            $($param: Deserialize<'a>,)+
+        {
+            fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+                Json::<Self>::from_sql(ty, raw).map(|json| json.0)
+            }
+
+            accepts!(JSON, JSONB);
+        }
+
+        impl<$($param),+> ToSql for $name<$($param),+>
+        where
+            $($param: Serialize + Debug,)+
+        {
+            fn to_sql(
+                &self,
+                ty: &Type,
+                out: &mut BytesMut,
+            ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+                Json(self).to_sql(ty, out)
+            }
+
+            accepts!(JSON, JSONB);
+            to_sql_checked!();
+        }
+    };
+}
+
+json_sql!(StringOrStruct<S>);
+json_sql!(StringOrStructOrVec<S, V>);
+json_sql!(SingleOrVec<S>);
+json_sql!(OneOrMany<S>);
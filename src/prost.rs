@@ -0,0 +1,153 @@
+use crate::enums::{OneOrMany, SingleOrVec, StringOrStruct, StringOrStructOrVec};
+use crate::error::Error;
+use crate::replay::Replay;
+use prost_types::value::Kind;
+use prost_types::{ListValue, NullValue, Struct, Value as ProstValue};
+use serde::de::DeserializeOwned;
+use serde::ser::{self, Serialize};
+use serde_value::Value;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+// integral doubles within this bound are exact, and can be handed over as integers
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+fn from_prost(value: ProstValue) -> Value {
+    match value.kind {
+        None | Some(Kind::NullValue(_)) => Value::Unit,
+        Some(Kind::BoolValue(b)) => Value::Bool(b),
+        // protobuf only has doubles, which would be refused by integer fields
+        Some(Kind::NumberValue(n)) if n.fract() == 0.0 && n.abs() <= MAX_SAFE_INTEGER => {
+            if n < 0.0 {
+                Value::I64(n as i64)
+            } else {
+                Value::U64(n as u64)
+            }
+        }
+        Some(Kind::NumberValue(n)) => Value::F64(n),
+        Some(Kind::StringValue(s)) => Value::String(s),
+        Some(Kind::StructValue(s)) => from_prost_struct(s),
+        Some(Kind::ListValue(l)) => from_prost_list(l),
+    }
+}
+
+fn from_prost_struct(value: Struct) -> Value {
+    Value::Map(
+        value
+            .fields
+            .into_iter()
+            .map(|(k, v)| (Value::String(k), from_prost(v)))
+            .collect(),
+    )
+}
+
+fn from_prost_list(value: ListValue) -> Value {
+    Value::Seq(value.values.into_iter().map(from_prost).collect())
+}
+
+fn kind(kind: Kind) -> ProstValue {
+    ProstValue { kind: Some(kind) }
+}
+
+fn to_prost(value: Value) -> Result<ProstValue, Error> {
+    let number = |n: f64| Ok(kind(Kind::NumberValue(n)));
+    match value {
+        Value::Unit | Value::Option(None) => Ok(kind(Kind::NullValue(NullValue::NullValue as i32))),
+        Value::Bool(b) => Ok(kind(Kind::BoolValue(b))),
+        Value::U8(n) => number(n.into()),
+        Value::U16(n) => number(n.into()),
+        Value::U32(n) => number(n.into()),
+        Value::U64(n) => number(n as f64),
+        Value::I8(n) => number(n.into()),
+        Value::I16(n) => number(n.into()),
+        Value::I32(n) => number(n.into()),
+        Value::I64(n) => number(n as f64),
+        Value::F32(n) => number(n.into()),
+        Value::F64(n) => number(n),
+        Value::Char(c) => Ok(kind(Kind::StringValue(c.to_string()))),
+        Value::String(s) => Ok(kind(Kind::StringValue(s))),
+        Value::Option(Some(v)) | Value::Newtype(v) => to_prost(*v),
+        Value::Seq(values) => Ok(kind(Kind::ListValue(ListValue {
+            values: values.into_iter().map(to_prost).collect::<Result<_, _>>()?,
+        }))),
+        Value::Map(map) => Ok(kind(Kind::StructValue(to_prost_struct(map)?))),
+        Value::Bytes(_) => Err(<Error as ser::Error>::custom(
+            "bytes cannot be represented as a protobuf Value",
+        )),
+    }
+}
+
+fn to_prost_struct(map: BTreeMap<Value, Value>) -> Result<Struct, Error> {
+    let mut fields = BTreeMap::new();
+    for (key, value) in map {
+        let key = match key {
+            Value::String(key) => key,
+            Value::Char(key) => key.to_string(),
+            _ => {
+                return Err(<Error as ser::Error>::custom(
+                    "protobuf Struct keys must be strings",
+                ))
+            }
+        };
+        fields.insert(key, to_prost(value)?);
+    }
+    Ok(Struct { fields })
+}
+
+fn dispatch<T: DeserializeOwned>(value: Value) -> Result<T, Error> {
+    T::deserialize(Replay::new(value, true))
+}
+
+// converts protobuf's dynamic values, classifying them like any other input
+macro_rules! prost_conversions {
+    ($name:ident<$($param:ident),+>) => {
+        impl<$($param),+> TryFrom<ProstValue> for $name<$($param),+>
+        where
+            $($param: DeserializeOwned,)+
+        {
+            type Error = Error;
+
+            fn try_from(value: ProstValue) -> Result<Self, Error> {
+                dispatch(from_prost(value))
+            }
+        }
+
+        impl<$($param),+> TryFrom<Struct> for $name<$($param),+>
+        where
+            $($param: DeserializeOwned,)+
+        {
+            type Error = Error;
+
+            fn try_from(value: Struct) -> Result<Self, Error> {
+                dispatch(from_prost_struct(value))
+            }
+        }
+
+        impl<$($param),+> TryFrom<ListValue> for $name<$($param),+>
+        where
+            $($param: DeserializeOwned,)+
+        {
+            type Error = Error;
+
+            fn try_from(value: ListValue) -> Result<Self, Error> {
+                dispatch(from_prost_list(value))
+            }
+        }
+
+        impl<$($param),+> TryFrom<$name<$($param),+>> for ProstValue
+        where
+            $($param: Serialize,)+
+        {
+            type Error = Error;
+
+            fn try_from(value: $name<$($param),+>) -> Result<Self, Error> {
+                to_prost(serde_value::to_value(value).map_err(<Error as ser::Error>::custom)?)
+            }
+        }
+    };
+}
+
+prost_conversions!(StringOrStruct<S>);
+prost_conversions!(StringOrStructOrVec<S, V>);
+prost_conversions!(SingleOrVec<S>);
+prost_conversions!(OneOrMany<S>);
@@ -0,0 +1,327 @@
+use crate::buffer::buffer;
+use crate::de::{number_token, unexpected};
+use crate::enums::StringOrStruct;
+use crate::policy::DispatchPolicy;
+use crate::replay::Replay;
+use crate::stream::Dispatch;
+use crate::warnings::scoped;
+use serde::de::{Deserialize, Deserializer, Error};
+use serde::ser::{Serialize, Serializer};
+use serde_value::Value;
+
+/// A map that is deserialized into `S` when it matches, and kept as its raw
+/// buffered value otherwise.
+///
+/// This lets a gateway strongly type the objects it understands while
+/// passing any other object through untouched. Inputs other than maps are
+/// still rejected. The `Raw` arm serializes back to the same map.
+///
+/// ```rust
+/// use serde::{Deserialize, Serialize};
+/// use serde_either::StructOrRaw;
+///
+/// #[derive(Serialize, Deserialize, Debug, PartialEq)]
+/// #[serde(deny_unknown_fields)]
+/// struct Ping {
+///     id: u32,
+/// }
+///
+/// let known: StructOrRaw<Ping> = serde_json::from_str(r#"{"id": 1}"#).unwrap();
+/// let unknown: StructOrRaw<Ping> = serde_json::from_str(r#"{"op": "sync"}"#).unwrap();
+///
+/// assert_eq!(known.as_struct(), Some(&Ping { id: 1 }));
+/// assert_eq!(serde_json::to_string(&unknown).unwrap(), r#"{"op":"sync"}"#);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum StructOrRaw<S> {
+    Struct(S),
+    Raw(Value),
+}
+
+impl<S> StructOrRaw<S> {
+    pub fn as_struct(&self) -> Option<&S> {
+        match self {
+            Self::Struct(s) => Some(s),
+            Self::Raw(_) => None,
+        }
+    }
+
+    pub fn as_raw(&self) -> Option<&Value> {
+        match self {
+            Self::Struct(_) => None,
+            Self::Raw(value) => Some(value),
+        }
+    }
+}
+
+impl<'de, S> Deserialize<'de> for StructOrRaw<S>
+where
+    S: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let human_readable = deserializer.is_human_readable();
+        let value = buffer(deserializer, &DispatchPolicy::default())?;
+        if !matches!(value, Value::Map(_)) {
+            return Err(D::Error::invalid_type(unexpected(&value), &"a map"));
+        }
+        match scoped(|| S::deserialize(Replay::<D::Error>::new(value.clone(), human_readable))) {
+            Ok(s) => Ok(Self::Struct(s)),
+            Err(_) => Ok(Self::Raw(value)),
+        }
+    }
+}
+
+impl<S> Serialize for StructOrRaw<S>
+where
+    S: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            StructOrRaw::Struct(s) => s.serialize(serializer),
+            StructOrRaw::Raw(value) => value.serialize(serializer),
+        }
+    }
+}
+
+/// A value that is deserialized into `T` when it matches, and kept as `V`
+/// otherwise, whatever its shape.
+///
+/// Unlike [`StructOrRaw`], any value is accepted, so evolving APIs can be
+/// parsed without losing the parts that do not match yet. `V` defaults to
+/// the buffered [`Value`], and can be any self-describing value type such as
+/// `serde_json::Value`. The `Other` arm serializes back to the same value.
+///
+/// ```rust
+/// use serde_either::ValueOr;
+///
+/// let known: ValueOr<u32> = serde_json::from_str("1").unwrap();
+/// let other: ValueOr<u32, serde_json::Value> = serde_json::from_str(r#""one""#).unwrap();
+///
+/// assert_eq!(known.as_parsed(), Some(&1));
+/// assert_eq!(other.as_other(), Some(&serde_json::json!("one")));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ValueOr<T, V = Value> {
+    Parsed(T),
+    Other(V),
+}
+
+/// A [`ValueOr`] keeping the values that do not match as JSON.
+#[cfg(feature = "serde_json")]
+pub type JsonValueOr<T> = ValueOr<T, serde_json::Value>;
+
+impl<T, V> ValueOr<T, V> {
+    pub fn as_parsed(&self) -> Option<&T> {
+        match self {
+            Self::Parsed(t) => Some(t),
+            Self::Other(_) => None,
+        }
+    }
+
+    pub fn as_other(&self) -> Option<&V> {
+        match self {
+            Self::Parsed(_) => None,
+            Self::Other(v) => Some(v),
+        }
+    }
+}
+
+impl<'de, T, V> Deserialize<'de> for ValueOr<T, V>
+where
+    T: Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let human_readable = deserializer.is_human_readable();
+        let value = buffer(deserializer, &DispatchPolicy::default())?;
+        match scoped(|| T::deserialize(Replay::<D::Error>::new(value.clone(), human_readable))) {
+            Ok(t) => Ok(Self::Parsed(t)),
+            Err(_) => V::deserialize(Replay::new(value, human_readable)).map(Self::Other),
+        }
+    }
+}
+
+impl<T, V> Serialize for ValueOr<T, V>
+where
+    T: Serialize,
+    V: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            ValueOr::Parsed(t) => t.serialize(serializer),
+            ValueOr::Other(v) => v.serialize(serializer),
+        }
+    }
+}
+
+/// A string, a map deserialized into `S`, or anything else deserialized into
+/// `O`, such as `serde_json::Value`, instead of an error.
+///
+/// Maps that do not match `S` are still rejected: only the values that are
+/// neither strings nor maps fall through to `O`.
+///
+/// ```rust
+/// use serde_either::StringOrStructOrOther;
+/// use std::collections::HashMap;
+///
+/// type Field = StringOrStructOrOther<HashMap<String, u32>, serde_json::Value>;
+///
+/// let other: Field = serde_json::from_str("[1, 2]").unwrap();
+///
+/// assert_eq!(other, StringOrStructOrOther::Other(serde_json::json!([1, 2])));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum StringOrStructOrOther<S, O> {
+    String(String),
+    Struct(S),
+    Other(O),
+}
+
+impl<S, O> From<StringOrStruct<S>> for StringOrStructOrOther<S, O> {
+    fn from(value: StringOrStruct<S>) -> Self {
+        match value {
+            StringOrStruct::String(s) => Self::String(s),
+            StringOrStruct::Struct(s) => Self::Struct(s),
+        }
+    }
+}
+
+impl<'de, S, O> Deserialize<'de> for StringOrStructOrOther<S, O>
+where
+    S: Deserialize<'de>,
+    O: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let human_readable = deserializer.is_human_readable();
+        let policy = DispatchPolicy::default();
+        let value = buffer(deserializer, &policy)?;
+        match value {
+            Value::String(_) | Value::Char(_) => {
+                StringOrStruct::from_value(value, &policy, human_readable).map(Self::from)
+            }
+            Value::Map(_) if number_token(&value).is_none() => {
+                StringOrStruct::from_value(value, &policy, human_readable).map(Self::from)
+            }
+            _ => O::deserialize(Replay::new(value, human_readable)).map(Self::Other),
+        }
+    }
+}
+
+impl<S, O> Serialize for StringOrStructOrOther<S, O>
+where
+    S: Serialize,
+    O: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            StringOrStructOrOther::String(s) => s.serialize(serializer),
+            StringOrStructOrOther::Struct(s) => s.serialize(serializer),
+            StringOrStructOrOther::Other(o) => o.serialize(serializer),
+        }
+    }
+}
+
+/// A value deserialized into `T`, typically a fieldless enum, or the string
+/// that did not match any of its variants.
+///
+/// This keeps APIs that add new enum values over time readable by older
+/// clients. Only strings fall back to `Unknown`: other values that do not
+/// match `T` are still rejected. The `Unknown` arm serializes back to the
+/// same string.
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use serde_either::UnknownOr;
+///
+/// #[derive(Deserialize, Debug, PartialEq)]
+/// #[serde(rename_all = "lowercase")]
+/// enum Status {
+///     Active,
+///     Suspended,
+/// }
+///
+/// let known: UnknownOr<Status> = serde_json::from_str(r#""active""#).unwrap();
+/// let unknown: UnknownOr<Status> = serde_json::from_str(r#""archived""#).unwrap();
+///
+/// assert_eq!(known.as_known(), Some(&Status::Active));
+/// assert_eq!(unknown.as_unknown(), Some("archived"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum UnknownOr<T> {
+    Known(T),
+    Unknown(String),
+}
+
+impl<T> UnknownOr<T> {
+    pub fn as_known(&self) -> Option<&T> {
+        match self {
+            Self::Known(t) => Some(t),
+            Self::Unknown(_) => None,
+        }
+    }
+
+    pub fn as_unknown(&self) -> Option<&str> {
+        match self {
+            Self::Known(_) => None,
+            Self::Unknown(s) => Some(s),
+        }
+    }
+
+    pub fn is_unknown(&self) -> bool {
+        matches!(self, Self::Unknown(_))
+    }
+}
+
+impl<'de, T> Deserialize<'de> for UnknownOr<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let human_readable = deserializer.is_human_readable();
+        let value = buffer(deserializer, &DispatchPolicy::default())?;
+        match scoped(|| T::deserialize(Replay::<D::Error>::new(value.clone(), human_readable))) {
+            Ok(t) => Ok(Self::Known(t)),
+            Err(err) => match value {
+                Value::String(s) => Ok(Self::Unknown(s)),
+                Value::Char(c) => Ok(Self::Unknown(c.to_string())),
+                _ => Err(err),
+            },
+        }
+    }
+}
+
+impl<T> Serialize for UnknownOr<T>
+where
+    T: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            UnknownOr::Known(t) => t.serialize(serializer),
+            UnknownOr::Unknown(s) => serializer.serialize_str(s),
+        }
+    }
+}
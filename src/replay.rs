@@ -0,0 +1,258 @@
+use serde::de::value::{MapDeserializer, SeqDeserializer};
+use serde::de::{
+    self, Deserialize, DeserializeSeed, Deserializer, EnumAccess, IntoDeserializer, Unexpected,
+    VariantAccess, Visitor,
+};
+use serde::forward_to_deserialize_any;
+use serde_value::Value;
+use std::marker::PhantomData;
+
+use crate::de::unexpected;
+
+/// Deserializes a buffered value the same way serde-value's
+/// `ValueDeserializer` does, but reporting the `is_human_readable` of the
+/// deserializer the value was buffered from.
+///
+/// Types such as `IpAddr` pick a different representation for binary
+/// formats, so replaying their buffered form as human readable would fail.
+pub(crate) struct Replay<E> {
+    value: Value,
+    human_readable: bool,
+    error: PhantomData<fn() -> E>,
+}
+
+impl<E> Replay<E> {
+    pub(crate) fn new(value: Value, human_readable: bool) -> Self {
+        Replay {
+            value,
+            human_readable,
+            error: PhantomData,
+        }
+    }
+}
+
+impl<'de, E> IntoDeserializer<'de, E> for Replay<E>
+where
+    E: de::Error,
+{
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+// mirrors serde-value's ValueDeserializer, see https://github.com/arcnmx/serde-value/blob/master/src/de.rs
+impl<'de, E> Deserializer<'de> for Replay<E>
+where
+    E: de::Error,
+{
+    type Error = E;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        let human_readable = self.human_readable;
+        match self.value {
+            Value::Bool(v) => visitor.visit_bool(v),
+            Value::U8(v) => visitor.visit_u8(v),
+            Value::U16(v) => visitor.visit_u16(v),
+            Value::U32(v) => visitor.visit_u32(v),
+            Value::U64(v) => visitor.visit_u64(v),
+            Value::I8(v) => visitor.visit_i8(v),
+            Value::I16(v) => visitor.visit_i16(v),
+            Value::I32(v) => visitor.visit_i32(v),
+            Value::I64(v) => visitor.visit_i64(v),
+            Value::F32(v) => visitor.visit_f32(v),
+            Value::F64(v) => visitor.visit_f64(v),
+            Value::Char(v) => visitor.visit_char(v),
+            Value::String(v) => visitor.visit_string(v),
+            Value::Unit => visitor.visit_unit(),
+            Value::Option(None) => visitor.visit_none(),
+            Value::Option(Some(v)) => visitor.visit_some(Replay::new(*v, human_readable)),
+            Value::Newtype(v) => visitor.visit_newtype_struct(Replay::new(*v, human_readable)),
+            Value::Seq(v) => visitor.visit_seq(SeqDeserializer::new(
+                v.into_iter().map(|v| Replay::new(v, human_readable)),
+            )),
+            Value::Map(v) => {
+                visitor.visit_map(MapDeserializer::new(v.into_iter().map(|(k, v)| {
+                    (
+                        Replay::new(k, human_readable),
+                        Replay::new(v, human_readable),
+                    )
+                })))
+            }
+            Value::Bytes(v) => visitor.visit_byte_buf(v),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        match self.value {
+            Value::Option(..) => self.deserialize_any(visitor),
+            Value::Unit => visitor.visit_unit(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, E> {
+        let human_readable = self.human_readable;
+        let (variant, value) = match self.value {
+            Value::Map(value) => {
+                let mut iter = value.into_iter();
+                // enums are encoded as maps with a single key:value pair
+                match (iter.next(), iter.next()) {
+                    (Some((variant, value)), None) => (variant, Some(value)),
+                    _ => {
+                        return Err(de::Error::invalid_value(
+                            Unexpected::Map,
+                            &"map with a single key",
+                        ))
+                    }
+                }
+            }
+            Value::String(variant) => (Value::String(variant), None),
+            other => {
+                return Err(de::Error::invalid_type(
+                    unexpected(&other),
+                    &"string or map",
+                ));
+            }
+        };
+
+        visitor.visit_enum(ReplayEnum {
+            variant: Replay::new(variant, human_readable),
+            value: value.map(|value| Replay::new(value, human_readable)),
+        })
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, E> {
+        match self.value {
+            Value::Newtype(v) => visitor.visit_newtype_struct(Replay::new(*v, self.human_readable)),
+            _ => visitor.visit_newtype_struct(self),
+        }
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.human_readable
+    }
+
+    forward_to_deserialize_any! {
+        bool u8 u16 u32 u64 i8 i16 i32 i64 f32 f64 char str string unit
+        seq bytes byte_buf map unit_struct
+        tuple_struct struct tuple ignored_any identifier
+    }
+}
+
+struct ReplayEnum<E> {
+    variant: Replay<E>,
+    value: Option<Replay<E>>,
+}
+
+impl<'de, E> EnumAccess<'de> for ReplayEnum<E>
+where
+    E: de::Error,
+{
+    type Error = E;
+    type Variant = ReplayVariant<E>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, ReplayVariant<E>), E>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let variant = ReplayVariant { value: self.value };
+        seed.deserialize(self.variant).map(|v| (v, variant))
+    }
+}
+
+struct ReplayVariant<E> {
+    value: Option<Replay<E>>,
+}
+
+impl<'de, E> VariantAccess<'de> for ReplayVariant<E>
+where
+    E: de::Error,
+{
+    type Error = E;
+
+    fn unit_variant(self) -> Result<(), E> {
+        match self.value {
+            Some(value) => Deserialize::deserialize(value),
+            None => Ok(()),
+        }
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, E>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.value {
+            Some(value) => seed.deserialize(value),
+            None => Err(de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"newtype variant",
+            )),
+        }
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Some(replay) => {
+                let human_readable = replay.human_readable;
+                match replay.value {
+                    Value::Seq(v) => Deserializer::deserialize_any(
+                        SeqDeserializer::new(v.into_iter().map(|v| Replay::new(v, human_readable))),
+                        visitor,
+                    ),
+                    other => Err(de::Error::invalid_type(
+                        unexpected(&other),
+                        &"tuple variant",
+                    )),
+                }
+            }
+            None => Err(de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"tuple variant",
+            )),
+        }
+    }
+
+    fn struct_variant<V>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Some(replay) => {
+                let human_readable = replay.human_readable;
+                match replay.value {
+                    Value::Map(v) => Deserializer::deserialize_any(
+                        MapDeserializer::new(v.into_iter().map(|(k, v)| {
+                            (
+                                Replay::new(k, human_readable),
+                                Replay::new(v, human_readable),
+                            )
+                        })),
+                        visitor,
+                    ),
+                    other => Err(de::Error::invalid_type(
+                        unexpected(&other),
+                        &"struct variant",
+                    )),
+                }
+            }
+            None => Err(de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"struct variant",
+            )),
+        }
+    }
+}
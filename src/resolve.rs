@@ -0,0 +1,46 @@
+use crate::enums::StringOrStruct;
+use std::future::Future;
+
+/// Resolves the String arm of a [`StringOrStruct`] into its Struct form,
+/// for strings that reference a resource (a URL, a registry name, ...).
+///
+/// Implementations can use `async fn`:
+///
+/// ```rust
+/// use serde_either::{Resolve, StringOrStruct};
+///
+/// struct Registry;
+///
+/// impl Resolve<u32> for Registry {
+///     type Error = String;
+///
+///     async fn resolve(&self, reference: &str) -> Result<u32, String> {
+///         match reference {
+///             "default" => Ok(8080),
+///             _ => Err(format!("unknown port {}", reference)),
+///         }
+///     }
+/// }
+///
+/// async fn port(value: StringOrStruct<u32>) -> Result<u32, String> {
+///     value.resolve_async(&Registry).await
+/// }
+/// ```
+pub trait Resolve<S> {
+    type Error;
+
+    fn resolve(&self, reference: &str) -> impl Future<Output = Result<S, Self::Error>>;
+}
+
+impl<S> StringOrStruct<S> {
+    /// Returns the Struct arm, resolving the String arm through `resolver`.
+    pub async fn resolve_async<R>(self, resolver: &R) -> Result<S, R::Error>
+    where
+        R: Resolve<S>,
+    {
+        match self {
+            Self::String(reference) => resolver.resolve(&reference).await,
+            Self::Struct(as_struct) => Ok(as_struct),
+        }
+    }
+}
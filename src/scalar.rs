@@ -0,0 +1,210 @@
+use crate::buffer::buffer;
+use crate::de::number_token;
+use crate::number::{Number, NumberVisitor};
+use crate::policy::DispatchPolicy;
+use crate::replay::Replay;
+use serde::de::{Deserialize, Deserializer, Error, MapAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+use serde_value::Value;
+use std::fmt;
+
+/// A string, a boolean or a number, whichever the format hands over.
+///
+/// Numbers encoded by serde_json's `arbitrary_precision` feature are numbers
+/// too. Characters are read as strings.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Scalar {
+    String(String),
+    Bool(bool),
+    Number(Number),
+}
+
+impl Scalar {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            Self::Bool(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    pub fn as_number(&self) -> Option<&Number> {
+        match self {
+            Self::Number(n) => Some(n),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Scalar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::String(s) => f.write_str(s),
+            Self::Bool(b) => fmt::Display::fmt(b, f),
+            Self::Number(n) => fmt::Display::fmt(n, f),
+        }
+    }
+}
+
+/// Accepts the scalars of any visitor, `expecting` being used for errors.
+struct ScalarVisitor(&'static str);
+
+impl<'de> Visitor<'de> for ScalarVisitor {
+    type Value = Scalar;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.0)
+    }
+
+    fn visit_bool<E: Error>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(Scalar::Bool(v))
+    }
+
+    fn visit_i64<E: Error>(self, v: i64) -> Result<Self::Value, E> {
+        NumberVisitor(self.0).visit_i64(v).map(Scalar::Number)
+    }
+
+    fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+        NumberVisitor(self.0).visit_u64(v).map(Scalar::Number)
+    }
+
+    fn visit_i128<E: Error>(self, v: i128) -> Result<Self::Value, E> {
+        NumberVisitor(self.0).visit_i128(v).map(Scalar::Number)
+    }
+
+    fn visit_u128<E: Error>(self, v: u128) -> Result<Self::Value, E> {
+        NumberVisitor(self.0).visit_u128(v).map(Scalar::Number)
+    }
+
+    fn visit_f64<E: Error>(self, v: f64) -> Result<Self::Value, E> {
+        NumberVisitor(self.0).visit_f64(v).map(Scalar::Number)
+    }
+
+    fn visit_char<E: Error>(self, v: char) -> Result<Self::Value, E> {
+        Ok(Scalar::String(v.to_string()))
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(Scalar::String(v.to_owned()))
+    }
+
+    fn visit_string<E: Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(Scalar::String(v))
+    }
+
+    fn visit_map<A>(self, access: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        NumberVisitor(self.0).visit_map(access).map(Scalar::Number)
+    }
+}
+
+impl<'de> Deserialize<'de> for Scalar {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ScalarVisitor("a string, a boolean or a number"))
+    }
+}
+
+impl Serialize for Scalar {
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            Scalar::String(s) => s.serialize(serializer),
+            Scalar::Bool(b) => b.serialize(serializer),
+            Scalar::Number(n) => n.serialize(serializer),
+        }
+    }
+}
+
+/// Any scalar, or a map deserialized into `S`, for settings whose shorthand
+/// may be a string, a boolean or a number alike
+/// (`cache: true`, `cache: 60`, `cache: { ttl: 60 }`).
+///
+/// Sequences and nulls are rejected.
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use serde_either::{Number, Scalar, ScalarOrStruct};
+///
+/// #[derive(Deserialize, Debug, PartialEq)]
+/// struct Cache {
+///     ttl: u32,
+/// }
+///
+/// let values: Vec<ScalarOrStruct<Cache>> =
+///     serde_yaml::from_str("[true, 60, 1.5, daily, { ttl: 60 }]").unwrap();
+///
+/// assert_eq!(values[0].as_scalar(), Some(&Scalar::Bool(true)));
+/// assert_eq!(values[1].as_scalar(), Some(&Scalar::Number(Number::U64(60))));
+/// assert_eq!(values[2].as_scalar(), Some(&Scalar::Number(Number::F64(1.5))));
+/// assert_eq!(values[3].as_scalar(), Some(&Scalar::String(String::from("daily"))));
+/// assert_eq!(values[4].as_struct(), Some(&Cache { ttl: 60 }));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScalarOrStruct<S> {
+    Scalar(Scalar),
+    Struct(S),
+}
+
+impl<S> ScalarOrStruct<S> {
+    pub fn as_scalar(&self) -> Option<&Scalar> {
+        match self {
+            Self::Scalar(s) => Some(s),
+            Self::Struct(_) => None,
+        }
+    }
+
+    pub fn as_struct(&self) -> Option<&S> {
+        match self {
+            Self::Scalar(_) => None,
+            Self::Struct(s) => Some(s),
+        }
+    }
+}
+
+impl<'de, S> Deserialize<'de> for ScalarOrStruct<S>
+where
+    S: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let human_readable = deserializer.is_human_readable();
+        match buffer(deserializer, &DispatchPolicy::default())? {
+            value @ Value::Map(_) if number_token(&value).is_none() => {
+                S::deserialize(Replay::new(value, human_readable)).map(Self::Struct)
+            }
+            value => Replay::<D::Error>::new(value, human_readable)
+                .deserialize_any(ScalarVisitor("a scalar or a map"))
+                .map(Self::Scalar),
+        }
+    }
+}
+
+impl<S> Serialize for ScalarOrStruct<S>
+where
+    S: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            ScalarOrStruct::Scalar(s) => s.serialize(serializer),
+            ScalarOrStruct::Struct(s) => s.serialize(serializer),
+        }
+    }
+}
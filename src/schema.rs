@@ -0,0 +1,93 @@
+use crate::buffer::buffer;
+use crate::policy::{DeserializeWithPolicy, DispatchPolicy};
+use crate::replay::Replay;
+use jsonschema::Validator;
+use serde::de::{DeserializeSeed, Deserializer, Error};
+use serde_value::Value;
+use std::marker::PhantomData;
+
+/// A `DeserializeSeed` validating maps against a JSON Schema before they
+/// are dispatched, so that the Struct arm is only built from valid input.
+///
+/// Every schema violation is reported in a single error, each one prefixed
+/// with the JSON pointer of the offending value. Strings and sequences are
+/// dispatched without validation.
+///
+/// ```rust
+/// use serde::de::DeserializeSeed;
+/// use serde::Deserialize;
+/// use serde_either::{SchemaSeed, StringOrStruct};
+///
+/// #[derive(Deserialize)]
+/// struct Server {
+///     port: u16,
+/// }
+///
+/// let schema = serde_json::json!({
+///     "type": "object",
+///     "properties": {"port": {"type": "integer", "minimum": 1024}},
+/// });
+/// let validator = jsonschema::validator_for(&schema).unwrap();
+///
+/// let mut deserializer = serde_json::Deserializer::from_str(r#"{"port": 80}"#);
+/// let err = SchemaSeed::<StringOrStruct<Server>>::new(&validator)
+///     .deserialize(&mut deserializer)
+///     .err()
+///     .unwrap();
+///
+/// assert!(err.to_string().starts_with("/port: 80 is less than the minimum of 1024"));
+/// ```
+pub struct SchemaSeed<'a, T> {
+    validator: &'a Validator,
+    policy: DispatchPolicy,
+    marker: PhantomData<T>,
+}
+
+impl<'a, T> SchemaSeed<'a, T> {
+    pub fn new(validator: &'a Validator) -> Self {
+        SchemaSeed {
+            validator,
+            policy: DispatchPolicy::default(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Dispatches the validated value using `policy`.
+    pub fn policy(mut self, policy: DispatchPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    fn validate<E: Error>(&self, value: &Value) -> Result<(), E> {
+        let instance = serde_json::to_value(value).map_err(E::custom)?;
+        let violations: Vec<String> = self
+            .validator
+            .iter_errors(&instance)
+            .map(|error| format!("{}: {}", error.instance_path(), error))
+            .collect();
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(E::custom(violations.join("; ")))
+        }
+    }
+}
+
+impl<'de, 'a, T> DeserializeSeed<'de> for SchemaSeed<'a, T>
+where
+    T: DeserializeWithPolicy<'de>,
+{
+    type Value = T;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let human_readable = deserializer.is_human_readable();
+        let value = buffer(deserializer, &self.policy)?;
+        if let Value::Map(_) = value {
+            self.validate::<D::Error>(&value)?;
+        }
+        T::deserialize_with_policy(Replay::new(value, human_readable), &self.policy)
+    }
+}
@@ -1,5 +1,5 @@
-use crate::enums::{SingleOrVec, StringOrStruct, StringOrStructOrVec};
-use serde::ser::{Serialize, Serializer};
+use crate::enums::{OneOrMany, SingleOrVec, StringOrStruct, StringOrStructOrVec};
+use serde::ser::{Serialize, SerializeSeq, Serializer};
 
 impl<S, V> Serialize for StringOrStructOrVec<S, V>
 where
@@ -47,3 +47,24 @@ where
         }
     }
 }
+
+impl<S> Serialize for OneOrMany<S>
+where
+    S: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            OneOrMany::One(s) => s.serialize(serializer),
+            OneOrMany::Many(..) => {
+                let mut seq = serializer.serialize_seq(Some(self.len()))?;
+                for element in self.iter() {
+                    seq.serialize_element(element)?;
+                }
+                seq.end()
+            }
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use serde::de::{Deserialize, Deserializer, Error, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+use std::fmt::{self, Display};
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::str::FromStr;
+
+/// A list written either as a sequence or as a single string of elements
+/// separated by `SEPARATOR`, such as `"a,b,c"`, as environment variables
+/// and command lines usually do.
+///
+/// Elements of sequences are deserialized as they are, while the pieces of
+/// strings are trimmed and parsed through `FromStr`. An empty string is an
+/// empty list. The list is always serialized as a sequence.
+///
+/// ```rust
+/// use serde_either::CommaSeparatedOrVec;
+///
+/// let split: CommaSeparatedOrVec<u16> = serde_json::from_str(r#""80, 443""#).unwrap();
+/// let listed: CommaSeparatedOrVec<u16> = serde_json::from_str("[80, 443]").unwrap();
+/// let piped: CommaSeparatedOrVec<u16, '|'> = serde_json::from_str(r#""80|443""#).unwrap();
+///
+/// assert_eq!(*split, vec![80, 443]);
+/// assert_eq!(split, listed);
+/// assert_eq!(*piped, vec![80, 443]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct CommaSeparatedOrVec<T, const SEPARATOR: char = ','>(pub Vec<T>);
+
+impl<T, const SEPARATOR: char> CommaSeparatedOrVec<T, SEPARATOR> {
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T, const SEPARATOR: char> Deref for CommaSeparatedOrVec<T, SEPARATOR> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Vec<T> {
+        &self.0
+    }
+}
+
+impl<T, const SEPARATOR: char> DerefMut for CommaSeparatedOrVec<T, SEPARATOR> {
+    fn deref_mut(&mut self) -> &mut Vec<T> {
+        &mut self.0
+    }
+}
+
+impl<T, const SEPARATOR: char> From<Vec<T>> for CommaSeparatedOrVec<T, SEPARATOR> {
+    fn from(elements: Vec<T>) -> Self {
+        CommaSeparatedOrVec(elements)
+    }
+}
+
+struct SeparatedVisitor<T, const SEPARATOR: char>(PhantomData<T>);
+
+impl<'de, T, const SEPARATOR: char> Visitor<'de> for SeparatedVisitor<T, SEPARATOR>
+where
+    T: Deserialize<'de> + FromStr,
+    T::Err: Display,
+{
+    type Value = CommaSeparatedOrVec<T, SEPARATOR>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "a sequence or a string separated by {:?}",
+            SEPARATOR
+        )
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        if v.trim().is_empty() {
+            return Ok(CommaSeparatedOrVec(Vec::new()));
+        }
+        v.split(SEPARATOR)
+            .map(|piece| {
+                let piece = piece.trim();
+                piece
+                    .parse()
+                    .map_err(|err| E::custom(format!("invalid element {:?}: {}", piece, err)))
+            })
+            .collect::<Result<_, _>>()
+            .map(CommaSeparatedOrVec)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut elements = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(element) = seq.next_element()? {
+            elements.push(element);
+        }
+        Ok(CommaSeparatedOrVec(elements))
+    }
+}
+
+impl<'de, T, const SEPARATOR: char> Deserialize<'de> for CommaSeparatedOrVec<T, SEPARATOR>
+where
+    T: Deserialize<'de> + FromStr,
+    T::Err: Display,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(SeparatedVisitor(PhantomData))
+    }
+}
+
+impl<T, const SEPARATOR: char> Serialize for CommaSeparatedOrVec<T, SEPARATOR>
+where
+    T: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
@@ -0,0 +1,1035 @@
+use crate::buffer::buffer;
+use crate::de::{classify, number_token, struct_or_passthrough, unexpected};
+use crate::enums::{AsStruct, AsVec, StringOrStructOrVec};
+use crate::kind::Kind;
+use crate::policy::DispatchPolicy;
+use crate::replay::Replay;
+use crate::warnings::scoped;
+use serde::de::value::MapAccessDeserializer;
+use serde::de::{Deserialize, DeserializeSeed, Deserializer, Error, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use serde_value::Value;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::marker::PhantomData;
+
+/// A map or a sequence, like [`StringOrStructOrVec`](crate::StringOrStructOrVec)
+/// without its String arm: strings and other scalars are rejected.
+///
+/// ```rust
+/// use serde_either::StructOrVec;
+/// use std::collections::HashMap;
+///
+/// type Hooks = StructOrVec<HashMap<String, String>, Vec<String>>;
+///
+/// let named: Hooks = serde_json::from_str(r#"{"build": "make"}"#).unwrap();
+/// let listed: Hooks = serde_json::from_str(r#"["make"]"#).unwrap();
+///
+/// assert!(named.as_struct().is_some());
+/// assert!(listed.as_vec().is_some());
+/// assert!(serde_json::from_str::<Hooks>(r#""make""#).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum StructOrVec<S, V> {
+    Struct(S),
+    Vec(V),
+}
+
+impl<S, V> StructOrVec<S, V> {
+    pub fn as_struct(&self) -> Option<&S> {
+        match self {
+            Self::Struct(s) => Some(s),
+            Self::Vec(_) => None,
+        }
+    }
+
+    pub fn as_vec(&self) -> Option<&V> {
+        match self {
+            Self::Struct(_) => None,
+            Self::Vec(v) => Some(v),
+        }
+    }
+}
+
+impl<'de, S, V> Deserialize<'de> for StructOrVec<S, V>
+where
+    S: Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let human_readable = deserializer.is_human_readable();
+        match buffer(deserializer, &DispatchPolicy::default())? {
+            value @ Value::Map(_) => {
+                S::deserialize(Replay::new(value, human_readable)).map(Self::Struct)
+            }
+            value @ Value::Seq(_) => {
+                V::deserialize(Replay::new(value, human_readable)).map(Self::Vec)
+            }
+            value => Err(D::Error::invalid_type(
+                unexpected(&value),
+                &"a map or a sequence",
+            )),
+        }
+    }
+}
+
+impl<S, V> Serialize for StructOrVec<S, V>
+where
+    S: Serialize,
+    V: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            StructOrVec::Struct(s) => s.serialize(serializer),
+            StructOrVec::Vec(v) => v.serialize(serializer),
+        }
+    }
+}
+
+/// A string or a sequence, for lists that may be written as a single string
+/// (`tags: a` vs `tags: [a, b]`). Maps and other scalars are rejected.
+///
+/// ```rust
+/// use serde_either::StringOrVec;
+///
+/// let one: StringOrVec<Vec<String>> = serde_json::from_str(r#""a""#).unwrap();
+/// let many: StringOrVec<Vec<String>> = serde_json::from_str(r#"["a", "b"]"#).unwrap();
+///
+/// assert_eq!(one.as_str(), Some("a"));
+/// assert_eq!(many.as_vec().map(Vec::len), Some(2));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum StringOrVec<V> {
+    String(String),
+    Vec(V),
+}
+
+impl<V> StringOrVec<V> {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s),
+            Self::Vec(_) => None,
+        }
+    }
+
+    pub fn as_vec(&self) -> Option<&V> {
+        match self {
+            Self::String(_) => None,
+            Self::Vec(v) => Some(v),
+        }
+    }
+}
+
+impl<V> From<String> for StringOrVec<V> {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl<V> From<&str> for StringOrVec<V> {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_owned())
+    }
+}
+
+impl<V> From<AsVec<V>> for StringOrVec<V> {
+    fn from(value: AsVec<V>) -> Self {
+        Self::Vec(value.0)
+    }
+}
+
+impl<'de, V> Deserialize<'de> for StringOrVec<V>
+where
+    V: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let human_readable = deserializer.is_human_readable();
+        match buffer(deserializer, &DispatchPolicy::default())? {
+            Value::String(s) => Ok(Self::String(s)),
+            Value::Char(c) => Ok(Self::String(c.to_string())),
+            // read like strings, as for StringOrStruct
+            value @ Value::Bytes(_) => {
+                String::deserialize(Replay::new(value, human_readable)).map(Self::String)
+            }
+            value @ Value::Seq(_) => {
+                V::deserialize(Replay::new(value, human_readable)).map(Self::Vec)
+            }
+            value => Err(D::Error::invalid_type(
+                unexpected(&value),
+                &"a string or a sequence",
+            )),
+        }
+    }
+}
+
+impl<V> Serialize for StringOrVec<V>
+where
+    V: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            StringOrVec::String(s) => s.serialize(serializer),
+            StringOrVec::Vec(v) => v.serialize(serializer),
+        }
+    }
+}
+
+/// A map or a sequence, for collections that may be keyed by name or listed,
+/// as docker-compose allows for `services` or `environment`.
+///
+/// This is [`StructOrVec`] with its first arm named after maps, `M` usually
+/// being a map type itself. Scalars are rejected.
+///
+/// ```rust
+/// use serde_either::MapOrVec;
+/// use std::collections::BTreeMap;
+///
+/// type Environment = MapOrVec<BTreeMap<String, String>, Vec<String>>;
+///
+/// let keyed: Environment = serde_yaml::from_str("DEBUG: '1'").unwrap();
+/// let listed: Environment = serde_yaml::from_str("- DEBUG=1").unwrap();
+///
+/// assert_eq!(keyed.as_map().map(BTreeMap::len), Some(1));
+/// assert_eq!(listed.as_vec().map(Vec::len), Some(1));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum MapOrVec<M, V> {
+    Map(M),
+    Vec(V),
+}
+
+impl<M, V> MapOrVec<M, V> {
+    pub fn as_map(&self) -> Option<&M> {
+        match self {
+            Self::Map(m) => Some(m),
+            Self::Vec(_) => None,
+        }
+    }
+
+    pub fn as_vec(&self) -> Option<&V> {
+        match self {
+            Self::Map(_) => None,
+            Self::Vec(v) => Some(v),
+        }
+    }
+}
+
+impl<'de, M, V> Deserialize<'de> for MapOrVec<M, V>
+where
+    M: Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match StructOrVec::<M, V>::deserialize(deserializer)? {
+            StructOrVec::Struct(m) => Ok(Self::Map(m)),
+            StructOrVec::Vec(v) => Ok(Self::Vec(v)),
+        }
+    }
+}
+
+impl<M, V> Serialize for MapOrVec<M, V>
+where
+    M: Serialize,
+    V: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            MapOrVec::Map(m) => m.serialize(serializer),
+            MapOrVec::Vec(v) => v.serialize(serializer),
+        }
+    }
+}
+
+/// A positional sequence or a named map, for values such as coordinates that
+/// are written as `[lat, lon]` or as `{ "lat": .., "lon": .. }`.
+///
+/// `T` is usually a tuple and `S` a struct with the same fields.
+/// [`into_struct`](TupleOrStruct::into_struct) reads both as `S` when `T`
+/// converts into it. Scalars are rejected.
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use serde_either::TupleOrStruct;
+///
+/// #[derive(Deserialize, Debug, PartialEq)]
+/// struct Point {
+///     lat: f64,
+///     lon: f64,
+/// }
+///
+/// impl From<(f64, f64)> for Point {
+///     fn from((lat, lon): (f64, f64)) -> Self {
+///         Point { lat, lon }
+///     }
+/// }
+///
+/// type Coordinates = TupleOrStruct<(f64, f64), Point>;
+///
+/// let positional: Coordinates = serde_json::from_str("[52.5, 13.4]").unwrap();
+/// let named: Coordinates = serde_json::from_str(r#"{"lat": 52.5, "lon": 13.4}"#).unwrap();
+///
+/// assert_eq!(positional.into_struct(), named.into_struct());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum TupleOrStruct<T, S> {
+    Tuple(T),
+    Struct(S),
+}
+
+impl<T, S> TupleOrStruct<T, S> {
+    pub fn as_tuple(&self) -> Option<&T> {
+        match self {
+            Self::Tuple(t) => Some(t),
+            Self::Struct(_) => None,
+        }
+    }
+
+    pub fn as_struct(&self) -> Option<&S> {
+        match self {
+            Self::Tuple(_) => None,
+            Self::Struct(s) => Some(s),
+        }
+    }
+
+    pub fn into_struct(self) -> S
+    where
+        T: Into<S>,
+    {
+        match self {
+            Self::Tuple(t) => t.into(),
+            Self::Struct(s) => s,
+        }
+    }
+}
+
+impl<'de, T, S> Deserialize<'de> for TupleOrStruct<T, S>
+where
+    T: Deserialize<'de>,
+    S: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match StructOrVec::<S, T>::deserialize(deserializer)? {
+            StructOrVec::Struct(s) => Ok(Self::Struct(s)),
+            StructOrVec::Vec(t) => Ok(Self::Tuple(t)),
+        }
+    }
+}
+
+impl<T, S> Serialize for TupleOrStruct<T, S>
+where
+    T: Serialize,
+    S: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            TupleOrStruct::Tuple(t) => t.serialize(serializer),
+            TupleOrStruct::Struct(s) => s.serialize(serializer),
+        }
+    }
+}
+
+/// A map or a string, for fields such as `headers` that are written inline
+/// (`headers: "X-Id: 1"`) or as entries (`headers: { X-Id: "1" }`), without
+/// defining a struct.
+///
+/// `M` defaults to a `BTreeMap<String, String>`. With the `indexmap`
+/// feature, [`IndexMapOrString`] keeps the entries in document order.
+/// Anything other than maps and strings is rejected.
+///
+/// ```rust
+/// use serde_either::MapOrString;
+///
+/// let inline: MapOrString = serde_yaml::from_str("'X-Id: 1'").unwrap();
+/// let entries: MapOrString = serde_yaml::from_str("X-Id: '1'").unwrap();
+///
+/// assert_eq!(inline.as_str(), Some("X-Id: 1"));
+/// assert_eq!(entries.as_map().map(|map| map["X-Id"].as_str()), Some("1"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum MapOrString<M = BTreeMap<String, String>> {
+    Map(M),
+    String(String),
+}
+
+/// A [`MapOrString`] keeping the entries of maps in document order.
+#[cfg(feature = "indexmap")]
+pub type IndexMapOrString = MapOrString<indexmap::IndexMap<String, String>>;
+
+impl<M> MapOrString<M> {
+    pub fn as_map(&self) -> Option<&M> {
+        match self {
+            Self::Map(m) => Some(m),
+            Self::String(_) => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::Map(_) => None,
+            Self::String(s) => Some(s),
+        }
+    }
+}
+
+// maps are not buffered, so that they keep their order
+struct MapOrStringVisitor<M>(PhantomData<M>);
+
+impl<'de, M> Visitor<'de> for MapOrStringVisitor<M>
+where
+    M: Deserialize<'de>,
+{
+    type Value = MapOrString<M>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map or a string")
+    }
+
+    fn visit_char<E: Error>(self, v: char) -> Result<Self::Value, E> {
+        Ok(MapOrString::String(v.to_string()))
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(MapOrString::String(v.to_owned()))
+    }
+
+    fn visit_string<E: Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(MapOrString::String(v))
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        M::deserialize(MapAccessDeserializer::new(map)).map(MapOrString::Map)
+    }
+}
+
+impl<'de, M> Deserialize<'de> for MapOrString<M>
+where
+    M: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(MapOrStringVisitor(PhantomData))
+    }
+}
+
+impl<M> Serialize for MapOrString<M>
+where
+    M: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            MapOrString::Map(m) => m.serialize(serializer),
+            MapOrString::String(s) => serializer.serialize_str(s),
+        }
+    }
+}
+
+/// A collection of structs keyed by name, or listed with their name inside,
+/// as docker-compose or Terraform documents allow:
+/// `{ web: { image: .. } }` vs `[ { name: web, image: .. } ]`.
+///
+/// The entries of maps are kept in the order of the document. Use
+/// [`into_vec`](KeyedMapOrVec::into_vec) to fold their keys into the structs.
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use serde_either::KeyedMapOrVec;
+///
+/// #[derive(Deserialize)]
+/// struct Service {
+///     #[serde(default)]
+///     name: String,
+///     image: String,
+/// }
+///
+/// let res: KeyedMapOrVec<Service> =
+///     serde_yaml::from_str("web:\n  image: nginx\ndb:\n  image: postgres\n").unwrap();
+///
+/// let services = res.into_vec(|service, name| service.name = name);
+/// assert_eq!(services[0].name, "web");
+/// assert_eq!(services[1].image, "postgres");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum KeyedMapOrVec<S> {
+    Map(Vec<(String, S)>),
+    Vec(Vec<S>),
+}
+
+impl<S> KeyedMapOrVec<S> {
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Map(entries) => entries.len(),
+            Self::Vec(v) => v.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the structs, handing each one of a map to `set_key` along
+    /// with its key first.
+    pub fn into_vec<F>(self, mut set_key: F) -> Vec<S>
+    where
+        F: FnMut(&mut S, String),
+    {
+        match self {
+            Self::Map(entries) => entries
+                .into_iter()
+                .map(|(key, mut s)| {
+                    set_key(&mut s, key);
+                    s
+                })
+                .collect(),
+            Self::Vec(v) => v,
+        }
+    }
+}
+
+struct KeyedMapOrVecVisitor<S>(PhantomData<S>);
+
+impl<'de, S> Visitor<'de> for KeyedMapOrVecVisitor<S>
+where
+    S: Deserialize<'de>,
+{
+    type Value = KeyedMapOrVec<S>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map or a sequence")
+    }
+
+    fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entries = Vec::with_capacity(access.size_hint().unwrap_or(0).min(4096));
+        while let Some(entry) = access.next_entry()? {
+            entries.push(entry);
+        }
+        Ok(KeyedMapOrVec::Map(entries))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut elements = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(element) = seq.next_element()? {
+            elements.push(element);
+        }
+        Ok(KeyedMapOrVec::Vec(elements))
+    }
+}
+
+impl<'de, S> Deserialize<'de> for KeyedMapOrVec<S>
+where
+    S: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(KeyedMapOrVecVisitor(PhantomData))
+    }
+}
+
+impl<S> Serialize for KeyedMapOrVec<S>
+where
+    S: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            KeyedMapOrVec::Map(entries) => {
+                let mut map = serializer.serialize_map(Some(entries.len()))?;
+                for (key, s) in entries {
+                    map.serialize_entry(key, s)?;
+                }
+                map.end()
+            }
+            KeyedMapOrVec::Vec(v) => v.serialize(serializer),
+        }
+    }
+}
+
+/// A single element or a set of them, for fields that are unordered and
+/// deduplicated when they are listed (`role: admin` vs `role: [admin, ops]`).
+///
+/// Sequences are deserialized into `C`, a `HashSet` by default, and anything
+/// else into `T`. Use a `BTreeSet` for `C` to keep the elements sorted.
+///
+/// ```rust
+/// use serde_either::SingleOrSet;
+/// use std::collections::BTreeSet;
+///
+/// let one: SingleOrSet<String> = serde_json::from_str(r#""admin""#).unwrap();
+/// let many: SingleOrSet<String, BTreeSet<String>> =
+///     serde_json::from_str(r#"["ops", "admin", "ops"]"#).unwrap();
+///
+/// assert_eq!(one.into_set().len(), 1);
+/// assert_eq!(many.into_set().into_iter().collect::<Vec<_>>(), ["admin", "ops"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SingleOrSet<T, C = HashSet<T>> {
+    Single(T),
+    Set(C),
+}
+
+impl<T, C> SingleOrSet<T, C> {
+    pub fn as_single(&self) -> Option<&T> {
+        match self {
+            Self::Single(t) => Some(t),
+            Self::Set(_) => None,
+        }
+    }
+
+    pub fn as_set(&self) -> Option<&C> {
+        match self {
+            Self::Single(_) => None,
+            Self::Set(c) => Some(c),
+        }
+    }
+
+    /// Returns the elements as a set, `Single` becoming a set of one.
+    pub fn into_set(self) -> C
+    where
+        C: Default + Extend<T>,
+    {
+        match self {
+            Self::Single(t) => {
+                let mut set = C::default();
+                set.extend(Some(t));
+                set
+            }
+            Self::Set(c) => c,
+        }
+    }
+}
+
+impl<'de, T, C> Deserialize<'de> for SingleOrSet<T, C>
+where
+    T: Deserialize<'de>,
+    C: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let human_readable = deserializer.is_human_readable();
+        match buffer(deserializer, &DispatchPolicy::default())? {
+            value @ Value::Seq(_) => {
+                C::deserialize(Replay::new(value, human_readable)).map(Self::Set)
+            }
+            value => T::deserialize(Replay::new(value, human_readable)).map(Self::Single),
+        }
+    }
+}
+
+impl<T, C> Serialize for SingleOrSet<T, C>
+where
+    T: Serialize,
+    C: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            SingleOrSet::Single(t) => t.serialize(serializer),
+            SingleOrSet::Set(c) => c.serialize(serializer),
+        }
+    }
+}
+
+/// A single value or a map of named ones, for settings that may be given
+/// once or per entry (`output: dist` vs `output: { js: dist/js, css: dist/css }`).
+///
+/// Maps are deserialized into `M` and anything else into `S`, so `S` is
+/// usually a scalar or a sequence: a struct written as a map is taken as `M`.
+///
+/// ```rust
+/// use serde_either::SingleOrMap;
+/// use std::collections::BTreeMap;
+///
+/// type Output = SingleOrMap<String, BTreeMap<String, String>>;
+///
+/// let single: Output = serde_yaml::from_str("dist").unwrap();
+/// let named: Output = serde_yaml::from_str("{ js: dist/js, css: dist/css }").unwrap();
+///
+/// assert_eq!(single.as_single().map(String::as_str), Some("dist"));
+/// assert_eq!(named.as_map().map(BTreeMap::len), Some(2));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SingleOrMap<S, M> {
+    Single(S),
+    Map(M),
+}
+
+impl<S, M> SingleOrMap<S, M> {
+    pub fn as_single(&self) -> Option<&S> {
+        match self {
+            Self::Single(s) => Some(s),
+            Self::Map(_) => None,
+        }
+    }
+
+    pub fn as_map(&self) -> Option<&M> {
+        match self {
+            Self::Single(_) => None,
+            Self::Map(m) => Some(m),
+        }
+    }
+}
+
+impl<'de, S, M> Deserialize<'de> for SingleOrMap<S, M>
+where
+    S: Deserialize<'de>,
+    M: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let human_readable = deserializer.is_human_readable();
+        match buffer(deserializer, &DispatchPolicy::default())? {
+            value @ Value::Map(_) if number_token(&value).is_none() => {
+                M::deserialize(Replay::new(value, human_readable)).map(Self::Map)
+            }
+            value => S::deserialize(Replay::new(value, human_readable)).map(Self::Single),
+        }
+    }
+}
+
+impl<S, M> Serialize for SingleOrMap<S, M>
+where
+    S: Serialize,
+    M: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            SingleOrMap::Single(s) => s.serialize(serializer),
+            SingleOrMap::Map(m) => m.serialize(serializer),
+        }
+    }
+}
+
+/// A string or a sequence of strings, always read as a `Vec<String>` through
+/// [`into_vec`](StringOrStrings::into_vec).
+///
+/// Unlike `SingleOrVec<String>`, elements of sequences that are not strings
+/// are reported with their index.
+///
+/// ```rust
+/// use serde_either::StringOrStrings;
+///
+/// let one: StringOrStrings = serde_json::from_str(r#""a""#).unwrap();
+/// let many: StringOrStrings = serde_json::from_str(r#"["a", "b"]"#).unwrap();
+/// let err = serde_json::from_str::<StringOrStrings>(r#"["a", 1]"#).unwrap_err();
+///
+/// assert_eq!(one.into_vec(), ["a"]);
+/// assert_eq!(many.into_vec(), ["a", "b"]);
+/// assert_eq!(
+///     err.to_string(),
+///     "invalid type: integer `1`, expected a string as element 1 at line 1 column 7"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum StringOrStrings {
+    String(String),
+    Vec(Vec<String>),
+}
+
+impl StringOrStrings {
+    pub fn into_vec(self) -> Vec<String> {
+        match self {
+            Self::String(s) => vec![s],
+            Self::Vec(v) => v,
+        }
+    }
+
+    /// Returns the strings, `String` counting as one.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        let strings = match self {
+            Self::String(s) => std::slice::from_ref(s),
+            Self::Vec(v) => v.as_slice(),
+        };
+        strings.iter().map(String::as_str)
+    }
+}
+
+// a string element of a sequence, reported with its index
+struct Element(usize);
+
+impl<'de> DeserializeSeed<'de> for Element {
+    type Value = String;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<String, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_string(self)
+    }
+}
+
+impl<'de> Visitor<'de> for Element {
+    type Value = String;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a string as element {}", self.0)
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<String, E> {
+        Ok(v.to_owned())
+    }
+
+    fn visit_string<E: Error>(self, v: String) -> Result<String, E> {
+        Ok(v)
+    }
+}
+
+struct StringOrStringsVisitor;
+
+impl<'de> Visitor<'de> for StringOrStringsVisitor {
+    type Value = StringOrStrings;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string or a sequence of strings")
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(StringOrStrings::String(v.to_owned()))
+    }
+
+    fn visit_string<E: Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(StringOrStrings::String(v))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut strings = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(s) = seq.next_element_seed(Element(strings.len()))? {
+            strings.push(s);
+        }
+        Ok(StringOrStrings::Vec(strings))
+    }
+}
+
+impl<'de> Deserialize<'de> for StringOrStrings {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(StringOrStringsVisitor)
+    }
+}
+
+impl Serialize for StringOrStrings {
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            StringOrStrings::String(s) => serializer.serialize_str(s),
+            StringOrStrings::Vec(v) => v.serialize(serializer),
+        }
+    }
+}
+
+/// Like [`StringOrStructOrVec`], with a `Map` arm for maps that `S` rejects,
+/// as CI pipelines allow for jobs written as a command, a job, a list of
+/// jobs or jobs keyed by name.
+///
+/// Maps are deserialized into `S` first, and into `M` when `S` rejects them,
+/// so `S` should reject the maps meant for `M`, through required fields or
+/// `#[serde(deny_unknown_fields)]`. When `M` rejects them too, the error of
+/// `S` is returned.
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use serde_either::StringOrStructOrVecOrMap;
+/// use std::collections::BTreeMap;
+///
+/// #[derive(Deserialize, Debug, PartialEq)]
+/// struct Job {
+///     script: String,
+/// }
+///
+/// type Jobs = StringOrStructOrVecOrMap<Job, Vec<Job>, BTreeMap<String, Job>>;
+///
+/// let jobs: Vec<Jobs> = serde_yaml::from_str(
+///     "[make, { script: make }, [{ script: make }], { build: { script: make } }]",
+/// )
+/// .unwrap();
+///
+/// assert_eq!(jobs[0], StringOrStructOrVecOrMap::String(String::from("make")));
+/// assert!(matches!(jobs[1], StringOrStructOrVecOrMap::Struct(_)));
+/// assert!(matches!(jobs[2], StringOrStructOrVecOrMap::Vec(_)));
+/// assert!(matches!(jobs[3], StringOrStructOrVecOrMap::Map(_)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum StringOrStructOrVecOrMap<S, V, M> {
+    String(String),
+    Struct(S),
+    Vec(V),
+    Map(M),
+}
+
+impl<S, V, M> StringOrStructOrVecOrMap<S, V, M> {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_struct(&self) -> Option<&S> {
+        match self {
+            Self::Struct(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_vec(&self) -> Option<&V> {
+        match self {
+            Self::Vec(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_map(&self) -> Option<&M> {
+        match self {
+            Self::Map(m) => Some(m),
+            _ => None,
+        }
+    }
+}
+
+impl<S, V, M> From<String> for StringOrStructOrVecOrMap<S, V, M> {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl<S, V, M> From<&str> for StringOrStructOrVecOrMap<S, V, M> {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_owned())
+    }
+}
+
+impl<S, V, M> From<AsStruct<S>> for StringOrStructOrVecOrMap<S, V, M> {
+    fn from(value: AsStruct<S>) -> Self {
+        Self::Struct(value.0)
+    }
+}
+
+impl<S, V, M> From<AsVec<V>> for StringOrStructOrVecOrMap<S, V, M> {
+    fn from(value: AsVec<V>) -> Self {
+        Self::Vec(value.0)
+    }
+}
+
+impl<S, V, M> From<StringOrStructOrVec<S, V>> for StringOrStructOrVecOrMap<S, V, M> {
+    fn from(value: StringOrStructOrVec<S, V>) -> Self {
+        match value {
+            StringOrStructOrVec::String(s) => Self::String(s),
+            StringOrStructOrVec::Struct(s) => Self::Struct(s),
+            StringOrStructOrVec::Vec(v) => Self::Vec(v),
+        }
+    }
+}
+
+impl<'de, S, V, M> Deserialize<'de> for StringOrStructOrVecOrMap<S, V, M>
+where
+    S: Deserialize<'de>,
+    V: Deserialize<'de>,
+    M: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let human_readable = deserializer.is_human_readable();
+        let policy = DispatchPolicy::default();
+        let value = buffer(deserializer, &policy)?;
+        scoped(
+            || match classify(value, &[Kind::String, Kind::Struct, Kind::Vec], &policy)? {
+                (Kind::String, value) => {
+                    String::deserialize(Replay::new(value, human_readable)).map(Self::String)
+                }
+                (Kind::Struct, value) => {
+                    let attempt = scoped(|| {
+                        struct_or_passthrough::<S, D::Error>(value.clone(), human_readable)
+                    });
+                    let struct_err = match attempt {
+                        Ok(Ok(s)) => return Ok(Self::Struct(s)),
+                        Ok(Err(text)) => return Ok(Self::String(text)),
+                        Err(e) => e,
+                    };
+                    M::deserialize(Replay::<D::Error>::new(value, human_readable))
+                        .map(Self::Map)
+                        .map_err(|_| struct_err)
+                }
+                (Kind::Vec, value) => {
+                    V::deserialize(Replay::new(value, human_readable)).map(Self::Vec)
+                }
+            },
+        )
+    }
+}
+
+impl<S, V, M> Serialize for StringOrStructOrVecOrMap<S, V, M>
+where
+    S: Serialize,
+    V: Serialize,
+    M: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            StringOrStructOrVecOrMap::String(s) => s.serialize(serializer),
+            StringOrStructOrVecOrMap::Struct(s) => s.serialize(serializer),
+            StringOrStructOrVecOrMap::Vec(v) => v.serialize(serializer),
+            StringOrStructOrVecOrMap::Map(m) => m.serialize(serializer),
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use crate::buffer::buffer;
+use crate::policy::DispatchPolicy;
+use crate::replay::Replay;
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+use serde_value::Value;
+use smallvec::SmallVec;
+
+/// A [`SingleOrVec`](crate::SingleOrVec) whose sequences of up to `N`
+/// elements are stored inline, sparing the heap allocation of the many "one
+/// or many" fields that only ever hold a few elements.
+///
+/// Longer sequences spill to the heap, as with any [`SmallVec`].
+///
+/// ```rust
+/// use serde_either::SingleOrSmallVec;
+///
+/// let res: SingleOrSmallVec<String, 3> = serde_json::from_str(r#"["a", "b"]"#).unwrap();
+///
+/// assert_eq!(res.as_slice(), ["a", "b"]);
+/// assert!(!res.spilled());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SingleOrSmallVec<T, const N: usize> {
+    Single(T),
+    Vec(SmallVec<[T; N]>),
+}
+
+impl<T, const N: usize> SingleOrSmallVec<T, N> {
+    pub fn as_slice(&self) -> &[T] {
+        match self {
+            Self::Single(t) => std::slice::from_ref(t),
+            Self::Vec(v) => v,
+        }
+    }
+
+    /// Returns the number of elements, `Single` counting as one.
+    pub fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns whether the elements have been moved to the heap, which only
+    /// happens to sequences longer than `N`.
+    pub fn spilled(&self) -> bool {
+        match self {
+            Self::Single(_) => false,
+            Self::Vec(v) => v.spilled(),
+        }
+    }
+
+    pub fn into_smallvec(self) -> SmallVec<[T; N]> {
+        match self {
+            Self::Single(t) => {
+                let mut v = SmallVec::new();
+                v.push(t);
+                v
+            }
+            Self::Vec(v) => v,
+        }
+    }
+}
+
+impl<'de, T, const N: usize> Deserialize<'de> for SingleOrSmallVec<T, N>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let human_readable = deserializer.is_human_readable();
+        match buffer(deserializer, &DispatchPolicy::default())? {
+            value @ Value::Seq(_) => {
+                SmallVec::deserialize(Replay::new(value, human_readable)).map(Self::Vec)
+            }
+            value => T::deserialize(Replay::new(value, human_readable)).map(Self::Single),
+        }
+    }
+}
+
+impl<T, const N: usize> Serialize for SingleOrSmallVec<T, N>
+where
+    T: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            SingleOrSmallVec::Single(t) => t.serialize(serializer),
+            SingleOrSmallVec::Vec(v) => v.serialize(serializer),
+        }
+    }
+}
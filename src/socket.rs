@@ -0,0 +1,199 @@
+use crate::enums::StringOrStruct;
+use serde::de::{Deserialize, Deserializer, Error, IgnoredAny, MapAccess, Visitor};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use std::fmt;
+use std::io;
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
+use std::str::FromStr;
+use std::vec;
+
+/// A host and a port: `{"host": "localhost", "port": 8080}`, or
+/// `localhost:8080` when parsed, with IPv6 hosts in brackets (`[::1]:8080`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct HostPort {
+    pub host: String,
+    pub port: u16,
+}
+
+impl HostPort {
+    /// Returns the address when the host is an IP address, without resolving
+    /// names.
+    pub fn to_socket_addr(&self) -> Option<SocketAddr> {
+        let ip: IpAddr = self.host.parse().ok()?;
+        Some(SocketAddr::new(ip, self.port))
+    }
+}
+
+impl FromStr for HostPort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        if let Ok(addr) = s.parse::<SocketAddr>() {
+            return Ok(HostPort {
+                host: addr.ip().to_string(),
+                port: addr.port(),
+            });
+        }
+        let invalid = || format!("invalid address {:?}: expected host:port", s);
+        let (host, port) = s.rsplit_once(':').ok_or_else(invalid)?;
+        if host.is_empty() || host.contains(':') {
+            return Err(invalid());
+        }
+        let port = port
+            .parse()
+            .map_err(|e| format!("invalid port in {:?}: {}", s, e))?;
+        Ok(HostPort {
+            host: host.to_owned(),
+            port,
+        })
+    }
+}
+
+impl fmt::Display for HostPort {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.host.contains(':') {
+            write!(f, "[{}]:{}", self.host, self.port)
+        } else {
+            write!(f, "{}:{}", self.host, self.port)
+        }
+    }
+}
+
+impl ToSocketAddrs for HostPort {
+    type Iter = vec::IntoIter<SocketAddr>;
+
+    fn to_socket_addrs(&self) -> io::Result<Self::Iter> {
+        (self.host.as_str(), self.port).to_socket_addrs()
+    }
+}
+
+struct HostPortVisitor;
+
+impl<'de> Visitor<'de> for HostPortVisitor {
+    type Value = HostPort;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map with a host and a port")
+    }
+
+    fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut host = None;
+        let mut port = None;
+        while let Some(key) = access.next_key::<String>()? {
+            match key.as_str() {
+                "host" => host = Some(access.next_value()?),
+                "port" => port = Some(access.next_value()?),
+                _ => {
+                    access.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(HostPort {
+            host: host.ok_or_else(|| A::Error::missing_field("host"))?,
+            port: port.ok_or_else(|| A::Error::missing_field("port"))?,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for HostPort {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(HostPortVisitor)
+    }
+}
+
+impl Serialize for HostPort {
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        let mut state = serializer.serialize_struct("HostPort", 2)?;
+        state.serialize_field("host", &self.host)?;
+        state.serialize_field("port", &self.port)?;
+        state.end()
+    }
+}
+
+/// A network address written either as a `"host:port"` string or as a
+/// `{host, port}` map, as service configurations allow.
+///
+/// Both arms hold a [`HostPort`], the string being parsed during
+/// deserialization, and each is serialized back in its own shape. Hosts
+/// are not resolved: use [`to_socket_addr`](StringOrSocketAddr::to_socket_addr)
+/// for IP addresses, or [`ToSocketAddrs`] for names.
+///
+/// ```rust
+/// use serde_either::StringOrSocketAddr;
+///
+/// let text: StringOrSocketAddr = serde_json::from_str(r#""127.0.0.1:8080""#).unwrap();
+/// let map: StringOrSocketAddr =
+///     serde_json::from_str(r#"{"host": "127.0.0.1", "port": 8080}"#).unwrap();
+///
+/// assert_eq!(text.host_port(), map.host_port());
+/// assert_eq!(text.to_socket_addr(), Some(([127, 0, 0, 1], 8080).into()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum StringOrSocketAddr {
+    String(HostPort),
+    Struct(HostPort),
+}
+
+impl StringOrSocketAddr {
+    /// Returns the host and port, whichever form they were written in.
+    pub fn host_port(&self) -> &HostPort {
+        match self {
+            Self::String(addr) | Self::Struct(addr) => addr,
+        }
+    }
+
+    pub fn host(&self) -> &str {
+        &self.host_port().host
+    }
+
+    pub fn port(&self) -> u16 {
+        self.host_port().port
+    }
+
+    /// Returns the address when the host is an IP address, without resolving
+    /// names.
+    pub fn to_socket_addr(&self) -> Option<SocketAddr> {
+        self.host_port().to_socket_addr()
+    }
+}
+
+impl ToSocketAddrs for StringOrSocketAddr {
+    type Iter = vec::IntoIter<SocketAddr>;
+
+    fn to_socket_addrs(&self) -> io::Result<Self::Iter> {
+        self.host_port().to_socket_addrs()
+    }
+}
+
+impl<'de> Deserialize<'de> for StringOrSocketAddr {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match StringOrStruct::<HostPort>::deserialize(deserializer)? {
+            StringOrStruct::String(s) => s.parse().map(Self::String).map_err(D::Error::custom),
+            StringOrStruct::Struct(addr) => Ok(Self::Struct(addr)),
+        }
+    }
+}
+
+impl Serialize for StringOrSocketAddr {
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            StringOrSocketAddr::String(addr) => serializer.collect_str(addr),
+            StringOrSocketAddr::Struct(addr) => addr.serialize(serializer),
+        }
+    }
+}
@@ -0,0 +1,372 @@
+use crate::buffer::{buffer, BufferSeed};
+use crate::de::{is_private_key, Expecting};
+use crate::kind::Kind;
+use crate::policy::DispatchPolicy;
+use crate::replay::Replay;
+use crate::warnings::scoped;
+use serde::de::{
+    DeserializeSeed, Deserializer, EnumAccess, Error, Expected, MapAccess, SeqAccess, Unexpected,
+    VariantAccess, Visitor,
+};
+use serde::forward_to_deserialize_any;
+use serde_value::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// The enums whose deserialization is driven by a [`DispatchPolicy`].
+pub(crate) trait Dispatch<'de>: Sized {
+    /// The kinds the type accepts, used in error messages.
+    const KINDS: &'static [Kind];
+
+    /// Builds the value from its buffered form.
+    fn from_buffered<E: Error>(
+        value: Value,
+        policy: &DispatchPolicy,
+        human_readable: bool,
+    ) -> Result<Self, E>;
+
+    /// Like [`from_buffered`](Dispatch::from_buffered), keeping the warnings
+    /// raised on the way only when the value is built.
+    fn from_value<E: Error>(
+        value: Value,
+        policy: &DispatchPolicy,
+        human_readable: bool,
+    ) -> Result<Self, E> {
+        scoped(|| Self::from_buffered(value, policy, human_readable))
+    }
+
+    /// Builds the Struct arm straight from the deserializer of a map.
+    fn from_struct<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>;
+}
+
+/// Deserializes `T`, buffering the input unless the policy streams maps
+/// straight to the Struct arm.
+pub(crate) fn dispatch<'de, T, D>(deserializer: D, policy: &DispatchPolicy) -> Result<T, D::Error>
+where
+    T: Dispatch<'de>,
+    D: Deserializer<'de>,
+{
+    let human_readable = deserializer.is_human_readable();
+    if policy.is_streaming_structs() {
+        scoped(|| {
+            deserializer.deserialize_any(StreamVisitor {
+                policy,
+                human_readable,
+                marker: PhantomData,
+            })
+        })
+    } else {
+        let value = buffer(deserializer, policy)?;
+        T::from_value(value, policy, human_readable)
+    }
+}
+
+// hands maps to the Struct arm as they are read, and buffers anything else
+struct StreamVisitor<'a, T> {
+    policy: &'a DispatchPolicy,
+    human_readable: bool,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T> StreamVisitor<'a, T> {
+    fn seed(&self) -> BufferSeed<'a> {
+        BufferSeed::new(self.policy)
+    }
+
+    fn buffered<'de, E: Error>(&self, value: Result<Value, E>) -> Result<T, E>
+    where
+        T: Dispatch<'de>,
+    {
+        T::from_value(value?, self.policy, self.human_readable)
+    }
+}
+
+macro_rules! buffered {
+    ($($method:ident($ty:ty)),* $(,)?) => {
+        $(
+            fn $method<E: Error>(self, v: $ty) -> Result<T, E> {
+                let value = self.seed().$method(v);
+                self.buffered(value)
+            }
+        )*
+    };
+}
+
+impl<'de, 'a, T> Visitor<'de> for StreamVisitor<'a, T>
+where
+    T: Dispatch<'de>,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let expecting = Expecting {
+            kinds: T::KINDS,
+            policy: self.policy,
+        };
+        Expected::fmt(&expecting, formatter)
+    }
+
+    buffered! {
+        visit_bool(bool),
+        visit_i8(i8),
+        visit_i16(i16),
+        visit_i32(i32),
+        visit_i64(i64),
+        visit_u8(u8),
+        visit_u16(u16),
+        visit_u32(u32),
+        visit_u64(u64),
+        visit_f32(f32),
+        visit_f64(f64),
+        visit_char(char),
+        visit_str(&str),
+        visit_string(String),
+        visit_bytes(&[u8]),
+        visit_byte_buf(Vec<u8>),
+    }
+
+    fn visit_unit<E: Error>(self) -> Result<T, E> {
+        let value = self.seed().visit_unit();
+        self.buffered(value)
+    }
+
+    fn visit_none<E: Error>(self) -> Result<T, E> {
+        let value = self.seed().visit_none();
+        self.buffered(value)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = self.seed().visit_some(deserializer);
+        self.buffered(value)
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = self.seed().visit_newtype_struct(deserializer);
+        self.buffered(value)
+    }
+
+    fn visit_seq<A>(self, access: A) -> Result<T, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let value = self.seed().visit_seq(access);
+        self.buffered(value)
+    }
+
+    fn visit_enum<A>(self, access: A) -> Result<T, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        let value = self.seed().visit_enum(access);
+        self.buffered(value)
+    }
+
+    fn visit_map<A>(self, mut access: A) -> Result<T, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let seed = self.seed().nested()?;
+        let key = match access.next_key_seed(seed)? {
+            Some(key) => key,
+            None => return self.buffered(Ok(Value::Map(BTreeMap::new()))),
+        };
+
+        // format-specific encodings of scalars are dispatched like any buffered value
+        if is_private_key(&key) {
+            let mut values = BTreeMap::new();
+            values.insert(key, access.next_value_seed(seed)?);
+            while let Some((key, value)) = access.next_entry_seed(seed, seed)? {
+                values.insert(key, value);
+            }
+            return self.buffered(Ok(Value::Map(values)));
+        }
+
+        if !self.policy.is_allowed(Kind::Struct) {
+            return Err(Error::invalid_type(Unexpected::Map, &self));
+        }
+        T::from_struct(StreamedMap {
+            key: Some(key),
+            access,
+            human_readable: self.human_readable,
+        })
+    }
+}
+
+// a map whose first key was already read, deserializing like the map it wraps
+struct StreamedMap<A> {
+    key: Option<Value>,
+    access: A,
+    human_readable: bool,
+}
+
+impl<'de, A> MapAccess<'de> for StreamedMap<A>
+where
+    A: MapAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, A::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.key.take() {
+            Some(key) => seed
+                .deserialize(Replay::new(key, self.human_readable))
+                .map(Some),
+            None => self.access.next_key_seed(seed),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, A::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        self.access.next_value_seed(seed)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.access.size_hint()
+    }
+}
+
+// mirrors serde's MapAccessDeserializer, see https://github.com/serde-rs/serde/blob/master/serde/src/de/value.rs
+impl<'de, A> Deserializer<'de> for StreamedMap<A>
+where
+    A: MapAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, A::Error> {
+        visitor.visit_map(self)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, A::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, A::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, A::Error> {
+        visitor.visit_enum(self)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.human_readable
+    }
+
+    forward_to_deserialize_any! {
+        bool u8 u16 u32 u64 i8 i16 i32 i64 f32 f64 char str string unit
+        seq bytes byte_buf map unit_struct
+        tuple_struct struct tuple ignored_any identifier
+    }
+}
+
+// an externally tagged enum, written as a map with a single entry
+impl<'de, A> EnumAccess<'de> for StreamedMap<A>
+where
+    A: MapAccess<'de>,
+{
+    type Error = A::Error;
+    type Variant = Self;
+
+    fn variant_seed<V>(mut self, seed: V) -> Result<(V::Value, Self), A::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        match self.next_key_seed(seed)? {
+            Some(variant) => Ok((variant, self)),
+            None => Err(Error::invalid_type(Unexpected::Map, &"enum")),
+        }
+    }
+}
+
+impl<'de, A> VariantAccess<'de> for StreamedMap<A>
+where
+    A: MapAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn unit_variant(mut self) -> Result<(), A::Error> {
+        self.next_value()
+    }
+
+    fn newtype_variant_seed<T>(mut self, seed: T) -> Result<T::Value, A::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        self.next_value_seed(seed)
+    }
+
+    fn tuple_variant<V>(mut self, len: usize, visitor: V) -> Result<V::Value, A::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.next_value_seed(TupleVariant { len, visitor })
+    }
+
+    fn struct_variant<V>(
+        mut self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, A::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.next_value_seed(StructVariant { visitor })
+    }
+}
+
+struct TupleVariant<V> {
+    len: usize,
+    visitor: V,
+}
+
+impl<'de, V> DeserializeSeed<'de> for TupleVariant<V>
+where
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<V::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(self.len, self.visitor)
+    }
+}
+
+struct StructVariant<V> {
+    visitor: V,
+}
+
+impl<'de, V> DeserializeSeed<'de> for StructVariant<V>
+where
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<V::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self.visitor)
+    }
+}
@@ -0,0 +1,391 @@
+use crate::buffer::buffer;
+use crate::policy::DispatchPolicy;
+use crate::replay::Replay;
+use crate::warnings::scoped;
+use serde::de::{self, Deserialize, Deserializer, Error, Visitor};
+use serde::ser::{
+    Serialize, SerializeStruct, SerializeStructVariant, SerializeTuple, SerializeTupleVariant,
+    Serializer,
+};
+use serde_value::Value;
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+/// An externally tagged enum (`{"Circle": {"radius": 1}}`), also accepted in
+/// its untagged form (`{"radius": 1}`), for APIs moving from one to the
+/// other.
+///
+/// The tagged form is tried first. Otherwise, the variants are tried in
+/// order, as `#[serde(untagged)]` would, and the first one to match wins.
+/// The value is serialized back in the form it was read in, unless
+/// [`serialize_tagged`](TaggedOrUntagged::serialize_tagged) or
+/// [`serialize_untagged`](TaggedOrUntagged::serialize_untagged) pick one.
+///
+/// ```rust
+/// use serde::{Deserialize, Serialize};
+/// use serde_either::TaggedOrUntagged;
+///
+/// #[derive(Serialize, Deserialize, Debug, PartialEq)]
+/// enum Shape {
+///     Circle { radius: u32 },
+///     Square { side: u32 },
+/// }
+///
+/// let tagged: TaggedOrUntagged<Shape> =
+///     serde_json::from_str(r#"{"Square": {"side": 2}}"#).unwrap();
+/// let untagged: TaggedOrUntagged<Shape> = serde_json::from_str(r#"{"side": 2}"#).unwrap();
+///
+/// assert!(tagged.is_tagged());
+/// assert_eq!(tagged.as_inner(), untagged.as_inner());
+/// assert_eq!(serde_json::to_string(&untagged).unwrap(), r#"{"side":2}"#);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum TaggedOrUntagged<T> {
+    Tagged(T),
+    Untagged(T),
+}
+
+impl<T> TaggedOrUntagged<T> {
+    pub fn is_tagged(&self) -> bool {
+        matches!(self, Self::Tagged(_))
+    }
+
+    pub fn as_inner(&self) -> &T {
+        match self {
+            Self::Tagged(t) | Self::Untagged(t) => t,
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        match self {
+            Self::Tagged(t) | Self::Untagged(t) => t,
+        }
+    }
+}
+
+impl<T: Serialize> TaggedOrUntagged<T> {
+    /// Serializes the enum with its tag, whichever form it was read in.
+    pub fn serialize_tagged<Se>(value: &Self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        value.as_inner().serialize(serializer)
+    }
+
+    /// Serializes the enum without its tag, whichever form it was read in.
+    pub fn serialize_untagged<Se>(value: &Self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        value.as_inner().serialize(Untagged(serializer))
+    }
+}
+
+impl<'de, T> Deserialize<'de> for TaggedOrUntagged<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let human_readable = deserializer.is_human_readable();
+        let value = buffer(deserializer, &DispatchPolicy::default())?;
+        let tagged_err =
+            match scoped(|| T::deserialize(Replay::<D::Error>::new(value.clone(), human_readable)))
+            {
+                Ok(t) => return Ok(Self::Tagged(t)),
+                Err(e) => e,
+            };
+
+        let variants = variants::<T>();
+        if let Value::Map(map) = &value {
+            let mut keys = map.keys();
+            // a single entry named after a variant is meant to be tagged
+            if let (Some(Value::String(key)), None) = (keys.next(), keys.next()) {
+                if variants.contains(&key.as_str()) {
+                    return Err(tagged_err);
+                }
+            }
+        }
+        for variant in variants {
+            let mut map = BTreeMap::new();
+            map.insert(Value::String(variant.to_string()), value.clone());
+            if let Ok(t) =
+                scoped(|| T::deserialize(Replay::<D::Error>::new(Value::Map(map), human_readable)))
+            {
+                return Ok(Self::Untagged(t));
+            }
+        }
+        Err(D::Error::custom(
+            "data did not match any variant, tagged or untagged",
+        ))
+    }
+}
+
+impl<T> Serialize for TaggedOrUntagged<T>
+where
+    T: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            TaggedOrUntagged::Tagged(t) => t.serialize(serializer),
+            TaggedOrUntagged::Untagged(t) => t.serialize(Untagged(serializer)),
+        }
+    }
+}
+
+// the names of the variants of `T`, as its `Deserialize` implementation
+// hands them to `deserialize_enum`
+fn variants<'de, T: Deserialize<'de>>() -> &'static [&'static str] {
+    let variants = Cell::new(&[][..]);
+    let _ = T::deserialize(VariantNames(&variants));
+    variants.get()
+}
+
+struct VariantNames<'a>(&'a Cell<&'static [&'static str]>);
+
+impl<'de, 'a> Deserializer<'de> for VariantNames<'a> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(Error::custom("not an enum"))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.0.set(variants);
+        Err(Error::custom("variant names only"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+// serializes enum variants without their tag, as `#[serde(untagged)]` does,
+// and everything else as is
+struct Untagged<S>(S);
+
+impl<S: Serializer> Serializer for Untagged<S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+    type SerializeSeq = S::SerializeSeq;
+    type SerializeTuple = S::SerializeTuple;
+    type SerializeTupleStruct = S::SerializeTupleStruct;
+    type SerializeTupleVariant = UntaggedTuple<S::SerializeTuple>;
+    type SerializeMap = S::SerializeMap;
+    type SerializeStruct = S::SerializeStruct;
+    type SerializeStructVariant = UntaggedStruct<S::SerializeStruct>;
+
+    fn serialize_bool(self, v: bool) -> Result<S::Ok, S::Error> {
+        self.0.serialize_bool(v)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<S::Ok, S::Error> {
+        self.0.serialize_i8(v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<S::Ok, S::Error> {
+        self.0.serialize_i16(v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<S::Ok, S::Error> {
+        self.0.serialize_i32(v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<S::Ok, S::Error> {
+        self.0.serialize_i64(v)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<S::Ok, S::Error> {
+        self.0.serialize_i128(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<S::Ok, S::Error> {
+        self.0.serialize_u8(v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<S::Ok, S::Error> {
+        self.0.serialize_u16(v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<S::Ok, S::Error> {
+        self.0.serialize_u32(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<S::Ok, S::Error> {
+        self.0.serialize_u64(v)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<S::Ok, S::Error> {
+        self.0.serialize_u128(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<S::Ok, S::Error> {
+        self.0.serialize_f32(v)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<S::Ok, S::Error> {
+        self.0.serialize_f64(v)
+    }
+
+    fn serialize_char(self, v: char) -> Result<S::Ok, S::Error> {
+        self.0.serialize_char(v)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<S::Ok, S::Error> {
+        self.0.serialize_str(v)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<S::Ok, S::Error> {
+        self.0.serialize_bytes(v)
+    }
+
+    fn serialize_none(self) -> Result<S::Ok, S::Error> {
+        self.0.serialize_none()
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<S::Ok, S::Error> {
+        self.0.serialize_some(value)
+    }
+
+    fn serialize_unit(self) -> Result<S::Ok, S::Error> {
+        self.0.serialize_unit()
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<S::Ok, S::Error> {
+        self.0.serialize_unit_struct(name)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<S::Ok, S::Error> {
+        self.0.serialize_unit()
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<S::Ok, S::Error> {
+        self.0.serialize_newtype_struct(name, value)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<S::Ok, S::Error> {
+        value.serialize(self.0)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<S::SerializeSeq, S::Error> {
+        self.0.serialize_seq(len)
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<S::SerializeTuple, S::Error> {
+        self.0.serialize_tuple(len)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<S::SerializeTupleStruct, S::Error> {
+        self.0.serialize_tuple_struct(name, len)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, S::Error> {
+        self.0.serialize_tuple(len).map(UntaggedTuple)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<S::SerializeMap, S::Error> {
+        self.0.serialize_map(len)
+    }
+
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<S::SerializeStruct, S::Error> {
+        self.0.serialize_struct(name, len)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, S::Error> {
+        self.0.serialize_struct(variant, len).map(UntaggedStruct)
+    }
+
+    fn collect_str<T: ?Sized + Display>(self, value: &T) -> Result<S::Ok, S::Error> {
+        self.0.collect_str(value)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.0.is_human_readable()
+    }
+}
+
+struct UntaggedTuple<S>(S);
+
+impl<S: SerializeTuple> SerializeTupleVariant for UntaggedTuple<S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), S::Error> {
+        self.0.serialize_element(value)
+    }
+
+    fn end(self) -> Result<S::Ok, S::Error> {
+        self.0.end()
+    }
+}
+
+struct UntaggedStruct<S>(S);
+
+impl<S: SerializeStruct> SerializeStructVariant for UntaggedStruct<S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), S::Error> {
+        self.0.serialize_field(key, value)
+    }
+
+    fn skip_field(&mut self, key: &'static str) -> Result<(), S::Error> {
+        self.0.skip_field(key)
+    }
+
+    fn end(self) -> Result<S::Ok, S::Error> {
+        self.0.end()
+    }
+}
@@ -0,0 +1,206 @@
+use serde::de::{Deserialize, Deserializer, Error, Unexpected, Visitor};
+use serde::ser::{self, Serialize, Serializer};
+use std::convert::TryFrom;
+use std::fmt;
+use std::marker::PhantomData;
+
+const NANOS_PER_SEC: i128 = 1_000_000_000;
+
+/// The date-time types a [`TimestampOrString`] can hold: chrono's
+/// `DateTime<Utc>` with the `chrono` feature, and time's `OffsetDateTime`
+/// with the `time` feature.
+pub trait Timestamp: Sized {
+    /// Parses an RFC 3339 string such as `"2024-05-01T12:00:00Z"`.
+    fn parse_rfc3339(s: &str) -> Result<Self, String>;
+
+    /// Formats the date-time as an RFC 3339 string, or returns why it cannot
+    /// be written as one.
+    fn to_rfc3339(&self) -> Result<String, String>;
+
+    /// Builds the date-time from nanoseconds since the Unix epoch, or returns
+    /// `None` when they are out of range.
+    fn from_unix_nanos(nanos: i128) -> Option<Self>;
+
+    /// Returns the nanoseconds since the Unix epoch.
+    fn unix_nanos(&self) -> i128;
+}
+
+#[cfg(feature = "chrono")]
+impl Timestamp for chrono::DateTime<chrono::Utc> {
+    fn parse_rfc3339(s: &str) -> Result<Self, String> {
+        chrono::DateTime::parse_from_rfc3339(s)
+            .map(|datetime| datetime.with_timezone(&chrono::Utc))
+            .map_err(|e| e.to_string())
+    }
+
+    fn to_rfc3339(&self) -> Result<String, String> {
+        Ok(self.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true))
+    }
+
+    fn from_unix_nanos(nanos: i128) -> Option<Self> {
+        let secs = i64::try_from(nanos.div_euclid(NANOS_PER_SEC)).ok()?;
+        chrono::DateTime::from_timestamp(secs, nanos.rem_euclid(NANOS_PER_SEC) as u32)
+    }
+
+    fn unix_nanos(&self) -> i128 {
+        i128::from(self.timestamp()) * NANOS_PER_SEC + i128::from(self.timestamp_subsec_nanos())
+    }
+}
+
+#[cfg(feature = "time")]
+impl Timestamp for time::OffsetDateTime {
+    fn parse_rfc3339(s: &str) -> Result<Self, String> {
+        time::OffsetDateTime::parse(s, &time::format_description::well_known::Rfc3339)
+            .map_err(|e| e.to_string())
+    }
+
+    fn to_rfc3339(&self) -> Result<String, String> {
+        self.format(&time::format_description::well_known::Rfc3339)
+            .map_err(|e| e.to_string())
+    }
+
+    fn from_unix_nanos(nanos: i128) -> Option<Self> {
+        time::OffsetDateTime::from_unix_timestamp_nanos(nanos).ok()
+    }
+
+    fn unix_nanos(&self) -> i128 {
+        self.unix_timestamp_nanos()
+    }
+}
+
+/// A date-time written either as an RFC 3339 string or as a number of
+/// seconds since the Unix epoch, possibly fractional.
+///
+/// Both arms hold the parsed [`Timestamp`], and each is serialized back in
+/// its own shape by default. Use [`serialize_as_string`] or
+/// [`serialize_as_number`] with `#[serde(serialize_with)]` to always write
+/// one form.
+///
+/// [`serialize_as_string`]: TimestampOrString::serialize_as_string
+/// [`serialize_as_number`]: TimestampOrString::serialize_as_number
+///
+/// ```rust
+/// # #[cfg(feature = "chrono")] {
+/// use chrono::{DateTime, Utc};
+/// use serde_either::TimestampOrString;
+///
+/// let text: TimestampOrString<DateTime<Utc>> =
+///     serde_json::from_str(r#""2024-05-01T12:00:00Z""#).unwrap();
+/// let epoch: TimestampOrString<DateTime<Utc>> = serde_json::from_str("1714564800").unwrap();
+///
+/// assert_eq!(text.timestamp(), epoch.timestamp());
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum TimestampOrString<T> {
+    Number(T),
+    String(T),
+}
+
+impl<T> TimestampOrString<T> {
+    /// Returns the date-time, whichever form it was written in.
+    pub fn timestamp(&self) -> &T {
+        match self {
+            Self::Number(t) | Self::String(t) => t,
+        }
+    }
+
+    pub fn into_timestamp(self) -> T {
+        match self {
+            Self::Number(t) | Self::String(t) => t,
+        }
+    }
+}
+
+impl<T: Timestamp> TimestampOrString<T> {
+    /// Serializes the date-time as an RFC 3339 string, whichever form it was
+    /// written in.
+    pub fn serialize_as_string<Se>(value: &Self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        let text = value
+            .timestamp()
+            .to_rfc3339()
+            .map_err(|e| <Se::Error as ser::Error>::custom(format!("invalid timestamp: {}", e)))?;
+        serializer.serialize_str(&text)
+    }
+
+    /// Serializes the date-time as seconds since the Unix epoch, whichever
+    /// form it was written in. Seconds are fractional only when needed.
+    pub fn serialize_as_number<Se>(value: &Self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        let nanos = value.timestamp().unix_nanos();
+        match i64::try_from(nanos / NANOS_PER_SEC) {
+            Ok(secs) if nanos % NANOS_PER_SEC == 0 => serializer.serialize_i64(secs),
+            _ => serializer.serialize_f64(nanos as f64 / NANOS_PER_SEC as f64),
+        }
+    }
+}
+
+struct TimestampOrStringVisitor<T>(PhantomData<T>);
+
+impl<T: Timestamp> TimestampOrStringVisitor<T> {
+    fn from_nanos<E: Error>(nanos: Option<i128>, unexp: Unexpected) -> Result<T, E> {
+        nanos
+            .and_then(T::from_unix_nanos)
+            .ok_or_else(|| E::invalid_value(unexp, &"a timestamp in range"))
+    }
+}
+
+impl<'de, T: Timestamp> Visitor<'de> for TimestampOrStringVisitor<T> {
+    type Value = TimestampOrString<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an RFC 3339 string or a Unix timestamp")
+    }
+
+    fn visit_i64<E: Error>(self, v: i64) -> Result<Self::Value, E> {
+        let nanos = i128::from(v).checked_mul(NANOS_PER_SEC);
+        Self::from_nanos(nanos, Unexpected::Signed(v)).map(TimestampOrString::Number)
+    }
+
+    fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+        let nanos = i128::from(v).checked_mul(NANOS_PER_SEC);
+        Self::from_nanos(nanos, Unexpected::Unsigned(v)).map(TimestampOrString::Number)
+    }
+
+    fn visit_f64<E: Error>(self, v: f64) -> Result<Self::Value, E> {
+        let nanos = (v * NANOS_PER_SEC as f64).round();
+        let nanos = if nanos.is_finite() && nanos.abs() < i128::MAX as f64 {
+            Some(nanos as i128)
+        } else {
+            None
+        };
+        Self::from_nanos(nanos, Unexpected::Float(v)).map(TimestampOrString::Number)
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        T::parse_rfc3339(v)
+            .map(TimestampOrString::String)
+            .map_err(|e| E::custom(format!("invalid timestamp {:?}: {}", v, e)))
+    }
+}
+
+impl<'de, T: Timestamp> Deserialize<'de> for TimestampOrString<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(TimestampOrStringVisitor(PhantomData))
+    }
+}
+
+impl<T: Timestamp> Serialize for TimestampOrString<T> {
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            TimestampOrString::Number(_) => Self::serialize_as_number(self, serializer),
+            TimestampOrString::String(_) => Self::serialize_as_string(self, serializer),
+        }
+    }
+}
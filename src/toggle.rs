@@ -0,0 +1,304 @@
+use crate::buffer::buffer;
+use crate::de::unexpected;
+use crate::enums::AsStruct;
+use crate::policy::DispatchPolicy;
+use crate::replay::Replay;
+use serde::de::{Deserialize, Deserializer, Error, Unexpected, Visitor};
+use serde::ser::{Serialize, Serializer};
+use serde_value::Value;
+use std::convert::TryFrom;
+use std::fmt;
+
+/// A boolean or a map, for settings that can be switched on or off as a
+/// whole, or configured in detail (`cache: false` vs `cache: { ttl: 60 }`).
+///
+/// Anything other than booleans and maps is rejected.
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use serde_either::BoolOrStruct;
+///
+/// #[derive(Deserialize, Debug, PartialEq)]
+/// struct Cache {
+///     ttl: u32,
+/// }
+///
+/// let off: BoolOrStruct<Cache> = serde_json::from_str("false").unwrap();
+/// let on: BoolOrStruct<Cache> = serde_json::from_str(r#"{"ttl": 60}"#).unwrap();
+///
+/// assert!(!off.is_enabled());
+/// assert!(on.is_enabled());
+/// assert_eq!(on.as_struct(), Some(&Cache { ttl: 60 }));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum BoolOrStruct<S> {
+    Bool(bool),
+    Struct(S),
+}
+
+impl<S> BoolOrStruct<S> {
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            Self::Bool(b) => Some(b),
+            Self::Struct(_) => None,
+        }
+    }
+
+    pub fn as_struct(&self) -> Option<&S> {
+        match self {
+            Self::Bool(_) => None,
+            Self::Struct(s) => Some(s),
+        }
+    }
+
+    /// Tells whether the setting is on: `true` and any struct are, `false`
+    /// is not.
+    pub fn is_enabled(&self) -> bool {
+        match *self {
+            Self::Bool(b) => b,
+            Self::Struct(_) => true,
+        }
+    }
+}
+
+impl<S> From<bool> for BoolOrStruct<S> {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl<S> From<AsStruct<S>> for BoolOrStruct<S> {
+    fn from(value: AsStruct<S>) -> Self {
+        Self::Struct(value.0)
+    }
+}
+
+impl<'de, S> Deserialize<'de> for BoolOrStruct<S>
+where
+    S: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let human_readable = deserializer.is_human_readable();
+        match buffer(deserializer, &DispatchPolicy::default())? {
+            Value::Bool(b) => Ok(Self::Bool(b)),
+            value @ Value::Map(_) => {
+                S::deserialize(Replay::new(value, human_readable)).map(Self::Struct)
+            }
+            value => Err(D::Error::invalid_type(
+                unexpected(&value),
+                &"a boolean or a map",
+            )),
+        }
+    }
+}
+
+impl<S> Serialize for BoolOrStruct<S>
+where
+    S: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            BoolOrStruct::Bool(b) => serializer.serialize_bool(*b),
+            BoolOrStruct::Struct(s) => s.serialize(serializer),
+        }
+    }
+}
+
+/// A string or a boolean, for settings such as `color: auto` vs
+/// `color: false`.
+///
+/// Strings are kept as they are, so `"true"` stays a string.
+///
+/// ```rust
+/// use serde_either::StringOrBool;
+///
+/// let auto: StringOrBool = serde_yaml::from_str("auto").unwrap();
+/// let off: StringOrBool = serde_yaml::from_str("false").unwrap();
+///
+/// assert_eq!(auto.as_str(), Some("auto"));
+/// assert_eq!(off.as_bool(), Some(false));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum StringOrBool {
+    String(String),
+    Bool(bool),
+}
+
+impl StringOrBool {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s),
+            Self::Bool(_) => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            Self::String(_) => None,
+            Self::Bool(b) => Some(b),
+        }
+    }
+}
+
+impl fmt::Display for StringOrBool {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::String(s) => f.write_str(s),
+            Self::Bool(b) => fmt::Display::fmt(b, f),
+        }
+    }
+}
+
+struct StringOrBoolVisitor;
+
+impl<'de> Visitor<'de> for StringOrBoolVisitor {
+    type Value = StringOrBool;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string or a boolean")
+    }
+
+    fn visit_bool<E: Error>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(StringOrBool::Bool(v))
+    }
+
+    fn visit_char<E: Error>(self, v: char) -> Result<Self::Value, E> {
+        Ok(StringOrBool::String(v.to_string()))
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(StringOrBool::String(v.to_owned()))
+    }
+
+    fn visit_string<E: Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(StringOrBool::String(v))
+    }
+}
+
+impl<'de> Deserialize<'de> for StringOrBool {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(StringOrBoolVisitor)
+    }
+}
+
+impl Serialize for StringOrBool {
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            StringOrBool::String(s) => serializer.serialize_str(s),
+            StringOrBool::Bool(b) => serializer.serialize_bool(*b),
+        }
+    }
+}
+
+/// A boolean or a count, for settings such as `retries: false` vs
+/// `retries: 5`.
+///
+/// Counts are non-negative integers, negative ones being rejected.
+///
+/// ```rust
+/// use serde_either::CountOrBool;
+///
+/// let off: CountOrBool = serde_json::from_str("false").unwrap();
+/// let on: CountOrBool = serde_json::from_str("true").unwrap();
+/// let five: CountOrBool = serde_json::from_str("5").unwrap();
+///
+/// assert_eq!(off.to_count(3), 0);
+/// assert_eq!(on.to_count(3), 3);
+/// assert_eq!(five.to_count(3), 5);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum CountOrBool {
+    Count(u64),
+    Bool(bool),
+}
+
+impl CountOrBool {
+    pub fn as_count(&self) -> Option<u64> {
+        match *self {
+            Self::Count(n) => Some(n),
+            Self::Bool(_) => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            Self::Count(_) => None,
+            Self::Bool(b) => Some(b),
+        }
+    }
+
+    /// Tells whether the setting is on: `true` and non-zero counts are.
+    pub fn is_enabled(&self) -> bool {
+        match *self {
+            Self::Count(n) => n > 0,
+            Self::Bool(b) => b,
+        }
+    }
+
+    /// Returns the count, `false` counting as zero and `true` as
+    /// `default_when_true`.
+    pub fn to_count(&self, default_when_true: u64) -> u64 {
+        match *self {
+            Self::Count(n) => n,
+            Self::Bool(true) => default_when_true,
+            Self::Bool(false) => 0,
+        }
+    }
+}
+
+struct CountOrBoolVisitor;
+
+impl<'de> Visitor<'de> for CountOrBoolVisitor {
+    type Value = CountOrBool;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a non-negative integer or a boolean")
+    }
+
+    fn visit_bool<E: Error>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(CountOrBool::Bool(v))
+    }
+
+    fn visit_i64<E: Error>(self, v: i64) -> Result<Self::Value, E> {
+        u64::try_from(v)
+            .map(CountOrBool::Count)
+            .map_err(|_| E::invalid_value(Unexpected::Signed(v), &self))
+    }
+
+    fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(CountOrBool::Count(v))
+    }
+}
+
+impl<'de> Deserialize<'de> for CountOrBool {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(CountOrBoolVisitor)
+    }
+}
+
+impl Serialize for CountOrBool {
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            CountOrBool::Count(n) => serializer.serialize_u64(*n),
+            CountOrBool::Bool(b) => serializer.serialize_bool(*b),
+        }
+    }
+}
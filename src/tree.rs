@@ -0,0 +1,158 @@
+use crate::de::{classify, struct_or_passthrough, unexpected};
+use crate::enums::StringOrStructOrVec;
+use crate::kind::Kind;
+use crate::policy::{DeserializeWithPolicy, DispatchPolicy};
+use crate::replay::Replay;
+use crate::stream::{dispatch, Dispatch};
+use serde::de::{Deserialize, Deserializer, Error};
+use serde::ser::{Serialize, Serializer};
+use serde_value::Value;
+use std::ops::{Deref, DerefMut};
+
+/// A recursive [`StringOrStructOrVec`], where the Vec arm holds further trees.
+///
+/// This models AST-like documents such as `["a", {...}, ["b", "c"]]`. The
+/// whole input is buffered once and the tree is then built directly from the
+/// buffered value, instead of re-buffering every nested level.
+///
+/// ```rust
+/// use serde_either::{StringOrStructOrVec, Tree};
+/// use std::collections::HashMap;
+///
+/// let tree: Tree<HashMap<String, u8>> =
+///     serde_json::from_str(r#"["a", {"b": 1}, ["c", ["d"]]]"#).unwrap();
+///
+/// assert_eq!(tree.depth(), 3);
+/// assert_eq!(tree.leaves().count(), 4);
+/// assert!(matches!(*tree, StringOrStructOrVec::Vec(_)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Tree<S>(pub StringOrStructOrVec<S, Vec<Tree<S>>>);
+
+impl<S> Tree<S> {
+    pub fn into_inner(self) -> StringOrStructOrVec<S, Vec<Tree<S>>> {
+        self.0
+    }
+
+    /// Returns the number of nested Vec levels, a leaf having a depth of 0.
+    pub fn depth(&self) -> usize {
+        match &self.0 {
+            StringOrStructOrVec::Vec(children) => {
+                1 + children.iter().map(Tree::depth).max().unwrap_or(0)
+            }
+            _ => 0,
+        }
+    }
+
+    /// Iterates depth-first over the String and Struct leaves of the tree.
+    pub fn leaves(&self) -> Leaves<'_, S> {
+        Leaves { stack: vec![self] }
+    }
+}
+
+impl<S> Deref for Tree<S> {
+    type Target = StringOrStructOrVec<S, Vec<Tree<S>>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<S> DerefMut for Tree<S> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// Iterator over the leaves of a [`Tree`], created by [`Tree::leaves`].
+pub struct Leaves<'a, S> {
+    stack: Vec<&'a Tree<S>>,
+}
+
+impl<'a, S> Iterator for Leaves<'a, S> {
+    type Item = &'a Tree<S>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(tree) = self.stack.pop() {
+            match &tree.0 {
+                StringOrStructOrVec::Vec(children) => self.stack.extend(children.iter().rev()),
+                _ => return Some(tree),
+            }
+        }
+        None
+    }
+}
+
+impl<'de, S> Dispatch<'de> for Tree<S>
+where
+    S: Deserialize<'de>,
+{
+    const KINDS: &'static [Kind] = &[Kind::String, Kind::Struct, Kind::Vec];
+
+    fn from_buffered<E: Error>(
+        value: Value,
+        policy: &DispatchPolicy,
+        human_readable: bool,
+    ) -> Result<Self, E> {
+        let inner = match classify(value, Self::KINDS, policy)? {
+            (Kind::String, value) => StringOrStructOrVec::String(String::deserialize(
+                Replay::new(value, human_readable),
+            )?),
+            (Kind::Struct, value) => match struct_or_passthrough(value, human_readable)? {
+                Ok(s) => StringOrStructOrVec::Struct(s),
+                Err(text) => StringOrStructOrVec::String(text),
+            },
+            (Kind::Vec, Value::Seq(items)) => StringOrStructOrVec::Vec(
+                items
+                    .into_iter()
+                    .map(|item| Self::from_value(item, policy, human_readable))
+                    .collect::<Result<_, _>>()?,
+            ),
+            (Kind::Vec, value) => return Err(Error::invalid_type(unexpected(&value), &"Vec")),
+        };
+        Ok(Tree(inner))
+    }
+
+    fn from_struct<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        S::deserialize(deserializer).map(|s| Tree(StringOrStructOrVec::Struct(s)))
+    }
+}
+
+impl<'de, S> DeserializeWithPolicy<'de> for Tree<S>
+where
+    S: Deserialize<'de>,
+{
+    fn deserialize_with_policy<D>(
+        deserializer: D,
+        policy: &DispatchPolicy,
+    ) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        dispatch(deserializer, policy)
+    }
+}
+
+impl<'de, S> Deserialize<'de> for Tree<S>
+where
+    S: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Self::deserialize_with_policy(deserializer, &DispatchPolicy::default())
+    }
+}
+
+impl<S> Serialize for Tree<S>
+where
+    S: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
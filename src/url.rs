@@ -0,0 +1,82 @@
+use crate::enums::StringOrStruct;
+use ::url::Url;
+use serde::de::{Deserializer, Error};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+
+/// Either a URL written as a string, or a structured form, like the
+/// `repository` of a package manifest: `"https://github.com/a/b"` vs
+/// `{"type": "git", "url": "https://github.com/a/b"}`.
+///
+/// Strings are parsed into a [`Url`] during deserialization, so invalid URLs
+/// are rejected with the rest of the document. The `Url` arm is serialized
+/// back to a string.
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use serde_either::UrlOrStruct;
+///
+/// #[derive(Deserialize)]
+/// struct Repository {
+///     url: String,
+/// }
+///
+/// let res: UrlOrStruct<Repository> =
+///     serde_json::from_str(r#""https://github.com/serde-rs/serde""#).unwrap();
+///
+/// assert_eq!(res.as_url().unwrap().host_str(), Some("github.com"));
+/// assert!(serde_json::from_str::<UrlOrStruct<Repository>>(r#""github.com""#).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum UrlOrStruct<S> {
+    Url(Url),
+    Struct(S),
+}
+
+impl<S> UrlOrStruct<S> {
+    pub fn as_url(&self) -> Option<&Url> {
+        match self {
+            Self::Url(url) => Some(url),
+            Self::Struct(_) => None,
+        }
+    }
+
+    pub fn as_struct(&self) -> Option<&S> {
+        match self {
+            Self::Url(_) => None,
+            Self::Struct(s) => Some(s),
+        }
+    }
+}
+
+impl<'de, S> Deserialize<'de> for UrlOrStruct<S>
+where
+    S: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match StringOrStruct::<S>::deserialize(deserializer)? {
+            StringOrStruct::String(s) => Url::parse(&s)
+                .map(Self::Url)
+                .map_err(|e| D::Error::custom(format!("invalid URL {:?}: {}", s, e))),
+            StringOrStruct::Struct(s) => Ok(Self::Struct(s)),
+        }
+    }
+}
+
+impl<S> Serialize for UrlOrStruct<S>
+where
+    S: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            UrlOrStruct::Url(url) => serializer.serialize_str(url.as_str()),
+            UrlOrStruct::Struct(s) => s.serialize(serializer),
+        }
+    }
+}
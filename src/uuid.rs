@@ -0,0 +1,112 @@
+use ::uuid::Uuid;
+use serde::de::{Deserialize, Deserializer, Error, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+use std::fmt;
+use std::ops::Deref;
+
+/// A UUID written either as its canonical string or as 16 bytes, given as a
+/// bytes value or a sequence of integers.
+///
+/// Both forms are accepted by every format, which helps when documents are
+/// converted between text and binary formats. The UUID is serialized as a
+/// hyphenated string to human-readable formats, and as bytes otherwise.
+///
+/// ```rust
+/// use serde_either::UuidStringOrBytes;
+///
+/// let text: UuidStringOrBytes =
+///     serde_json::from_str(r#""67e55044-10b1-426f-9247-bb680e5fe0c8""#).unwrap();
+/// let bytes: UuidStringOrBytes =
+///     serde_json::from_str("[103,229,80,68,16,177,66,111,146,71,187,104,14,95,224,200]").unwrap();
+///
+/// assert_eq!(text, bytes);
+/// assert_eq!(text.get_version_num(), 4);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct UuidStringOrBytes(pub Uuid);
+
+impl UuidStringOrBytes {
+    pub fn into_inner(self) -> Uuid {
+        self.0
+    }
+}
+
+impl Deref for UuidStringOrBytes {
+    type Target = Uuid;
+
+    fn deref(&self) -> &Uuid {
+        &self.0
+    }
+}
+
+impl From<Uuid> for UuidStringOrBytes {
+    fn from(uuid: Uuid) -> Self {
+        UuidStringOrBytes(uuid)
+    }
+}
+
+impl From<UuidStringOrBytes> for Uuid {
+    fn from(uuid: UuidStringOrBytes) -> Self {
+        uuid.0
+    }
+}
+
+struct UuidStringOrBytesVisitor;
+
+impl<'de> Visitor<'de> for UuidStringOrBytesVisitor {
+    type Value = UuidStringOrBytes;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a UUID string or 16 bytes")
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        Uuid::parse_str(v)
+            .map(UuidStringOrBytes)
+            .map_err(|e| E::custom(format!("invalid UUID {:?}: {}", v, e)))
+    }
+
+    fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Uuid::from_slice(v)
+            .map(UuidStringOrBytes)
+            .map_err(|_| E::invalid_length(v.len(), &self))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut bytes = [0u8; 16];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = seq
+                .next_element()?
+                .ok_or_else(|| A::Error::invalid_length(i, &self))?;
+        }
+        if seq.next_element::<u8>()?.is_some() {
+            return Err(A::Error::invalid_length(17, &self));
+        }
+        Ok(UuidStringOrBytes(Uuid::from_bytes(bytes)))
+    }
+}
+
+impl<'de> Deserialize<'de> for UuidStringOrBytes {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(UuidStringOrBytesVisitor)
+    }
+}
+
+impl Serialize for UuidStringOrBytes {
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.collect_str(&self.0.hyphenated())
+        } else {
+            serializer.serialize_bytes(self.0.as_bytes())
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use crate::buffer::buffer;
+use crate::de::unexpected;
+use crate::policy::DispatchPolicy;
+use crate::replay::Replay;
+use serde::de::{Deserialize, Deserializer, Error};
+use serde::ser::{Serialize, Serializer};
+use serde_value::Value;
+
+/// The name of one of `E`'s unit variants, or a map deserialized into `S`,
+/// for settings with presets and a custom form: `mode: fast` vs
+/// `mode: { custom: { ... } }`.
+///
+/// Strings are deserialized into `E`, so unknown names are reported with the
+/// variants `E` expects. Anything other than strings and maps is rejected.
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use serde_either::VariantNameOrStruct;
+/// use std::collections::BTreeMap;
+///
+/// #[derive(Deserialize, Debug, PartialEq)]
+/// #[serde(rename_all = "lowercase")]
+/// enum Mode {
+///     Fast,
+///     Safe,
+/// }
+///
+/// type Setting = VariantNameOrStruct<Mode, BTreeMap<String, u32>>;
+///
+/// let preset: Setting = serde_yaml::from_str("fast").unwrap();
+/// let custom: Setting = serde_yaml::from_str("{ custom: 3 }").unwrap();
+/// let err = serde_yaml::from_str::<Setting>("slow").unwrap_err();
+///
+/// assert_eq!(preset.as_variant(), Some(&Mode::Fast));
+/// assert_eq!(custom.as_struct().map(BTreeMap::len), Some(1));
+/// assert_eq!(err.to_string(), "unknown variant `slow`, expected `fast` or `safe`");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum VariantNameOrStruct<E, S> {
+    Variant(E),
+    Struct(S),
+}
+
+impl<E, S> VariantNameOrStruct<E, S> {
+    pub fn as_variant(&self) -> Option<&E> {
+        match self {
+            Self::Variant(e) => Some(e),
+            Self::Struct(_) => None,
+        }
+    }
+
+    pub fn as_struct(&self) -> Option<&S> {
+        match self {
+            Self::Variant(_) => None,
+            Self::Struct(s) => Some(s),
+        }
+    }
+}
+
+impl<'de, E, S> Deserialize<'de> for VariantNameOrStruct<E, S>
+where
+    E: Deserialize<'de>,
+    S: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let human_readable = deserializer.is_human_readable();
+        match buffer(deserializer, &DispatchPolicy::default())? {
+            value @ Value::String(_) => {
+                E::deserialize(Replay::new(value, human_readable)).map(Self::Variant)
+            }
+            value @ Value::Map(_) => {
+                S::deserialize(Replay::new(value, human_readable)).map(Self::Struct)
+            }
+            value => Err(D::Error::invalid_type(
+                unexpected(&value),
+                &"a variant name or a map",
+            )),
+        }
+    }
+}
+
+impl<E, S> Serialize for VariantNameOrStruct<E, S>
+where
+    E: Serialize,
+    S: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            VariantNameOrStruct::Variant(e) => e.serialize(serializer),
+            VariantNameOrStruct::Struct(s) => s.serialize(serializer),
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use crate::enums::StringOrStruct;
+use semver::VersionReq;
+use serde::de::{Deserializer, Error};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Either a version written as a string, or a structured form, like the
+/// dependencies of a Cargo manifest: `serde = "1.0"` vs
+/// `serde = { version = "1.0", features = ["derive"] }`.
+///
+/// Strings are parsed into `V` during deserialization, a [`VersionReq`] by
+/// default, or a [`semver::Version`] for exact versions. The structured form
+/// defaults to [`DependencyDetail`]. The `Version` arm is serialized back
+/// to a string.
+///
+/// ```rust
+/// use serde_either::VersionOrStruct;
+///
+/// let short: VersionOrStruct = serde_json::from_str(r#""1.0""#).unwrap();
+/// let detailed: VersionOrStruct =
+///     serde_json::from_str(r#"{"version": "1.0", "features": ["derive"]}"#).unwrap();
+///
+/// assert_eq!(short.version(), detailed.version());
+/// assert!(serde_json::from_str::<VersionOrStruct>(r#""one""#).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum VersionOrStruct<S = DependencyDetail, V = VersionReq> {
+    Version(V),
+    Struct(S),
+}
+
+/// The structured form of a dependency:
+/// `{"version": "1.0", "features": ["derive"], "optional": true}`.
+///
+/// Every field is optional.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DependencyDetail {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<VersionReq>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub features: Vec<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub optional: bool,
+}
+
+impl VersionOrStruct<DependencyDetail, VersionReq> {
+    /// Returns the version requirement, whichever form it was written in.
+    pub fn version(&self) -> Option<&VersionReq> {
+        match self {
+            Self::Version(req) => Some(req),
+            Self::Struct(detail) => detail.version.as_ref(),
+        }
+    }
+}
+
+impl<S, V> VersionOrStruct<S, V> {
+    pub fn as_struct(&self) -> Option<&S> {
+        match self {
+            Self::Version(_) => None,
+            Self::Struct(s) => Some(s),
+        }
+    }
+}
+
+impl<'de, S, V> Deserialize<'de> for VersionOrStruct<S, V>
+where
+    S: Deserialize<'de>,
+    V: FromStr,
+    V::Err: Display,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match StringOrStruct::<S>::deserialize(deserializer)? {
+            StringOrStruct::String(s) => s
+                .parse()
+                .map(Self::Version)
+                .map_err(|e| D::Error::custom(format!("invalid version {:?}: {}", s, e))),
+            StringOrStruct::Struct(s) => Ok(Self::Struct(s)),
+        }
+    }
+}
+
+impl<S, V> Serialize for VersionOrStruct<S, V>
+where
+    S: Serialize,
+    V: Display,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        match self {
+            VersionOrStruct::Version(v) => serializer.collect_str(v),
+            VersionOrStruct::Struct(s) => s.serialize(serializer),
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use serde::de::{Deserialize, DeserializeSeed, Deserializer};
+use std::cell::RefCell;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// A non-fatal observation made while dispatching a value to an arm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Warning {
+    /// The value was written in its string form.
+    StringForm,
+    /// A number was turned into a string, see
+    /// [`DispatchPolicy::numbers_as_string`](crate::DispatchPolicy::numbers_as_string).
+    NumberAsString,
+    /// A sequence with a single element was unwrapped, see
+    /// [`DispatchPolicy::seq_of_one_as_struct`](crate::DispatchPolicy::seq_of_one_as_struct).
+    SeqOfOneAsStruct,
+    /// An empty sequence was given.
+    EmptyVec,
+    /// A toml datetime was rejected by the Struct arm and read as a string.
+    DatetimeAsString,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Warning::StringForm => "value uses the string form",
+            Warning::NumberAsString => "number was converted to a string",
+            Warning::SeqOfOneAsStruct => "single element sequence was unwrapped",
+            Warning::EmptyVec => "sequence is empty",
+            Warning::DatetimeAsString => "datetime was converted to a string",
+        })
+    }
+}
+
+thread_local! {
+    static COLLECTOR: RefCell<Option<Vec<Warning>>> = const { RefCell::new(None) };
+}
+
+/// Records a warning when warnings are being collected on this thread.
+pub(crate) fn warn(warning: Warning) {
+    COLLECTOR.with(|collector| {
+        if let Some(warnings) = collector.borrow_mut().as_mut() {
+            warnings.push(warning);
+        }
+    });
+}
+
+// collects the warnings raised while `f` runs, restoring any outer collection
+fn collect<T, E>(f: impl FnOnce() -> Result<T, E>) -> (Result<T, E>, Vec<Warning>) {
+    let outer = OuterCollection::start(Some(Vec::new()));
+    let res = f();
+    let warnings = COLLECTOR.with(|collector| collector.borrow_mut().take().unwrap_or_default());
+    drop(outer);
+    // warnings of a nested collection are also seen by the outer one, unless
+    // the value they were raised for is thrown away
+    if res.is_ok() {
+        for warning in &warnings {
+            warn(*warning);
+        }
+    }
+    (res, warnings)
+}
+
+fn is_collecting() -> bool {
+    COLLECTOR.with(|collector| collector.borrow().is_some())
+}
+
+/// Runs an attempt to deserialize a value, keeping the warnings it raises
+/// only when it succeeds, so that attempts given up on by untagged enums and
+/// fallbacks leave no trace.
+pub(crate) fn scoped<T, E>(f: impl FnOnce() -> Result<T, E>) -> Result<T, E> {
+    if is_collecting() {
+        collect(f).0
+    } else {
+        f()
+    }
+}
+
+/// Runs `f` without recording any of the warnings it raises.
+pub(crate) fn silenced<T>(f: impl FnOnce() -> T) -> T {
+    let outer = OuterCollection::start(None);
+    let res = f();
+    drop(outer);
+    res
+}
+
+// puts the outer collection back when dropped, even if `f` panics
+struct OuterCollection {
+    warnings: Option<Vec<Warning>>,
+}
+
+impl OuterCollection {
+    fn start(inner: Option<Vec<Warning>>) -> Self {
+        let warnings = COLLECTOR.with(|collector| collector.replace(inner));
+        OuterCollection { warnings }
+    }
+}
+
+impl Drop for OuterCollection {
+    fn drop(&mut self) {
+        let warnings = self.warnings.take();
+        COLLECTOR.with(|collector| *collector.borrow_mut() = warnings);
+    }
+}
+
+/// A deserialized value together with the warnings raised by the enums of
+/// this crate anywhere inside of it.
+///
+/// Only the warnings of values that end up deserialized are kept: attempts
+/// given up on, such as the variants an untagged enum tries before the one
+/// that matches, leave none.
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use serde_either::{StringOrStruct, Warned, Warning};
+/// use std::collections::HashMap;
+///
+/// #[derive(Deserialize)]
+/// struct Config {
+///     output: StringOrStruct<HashMap<String, String>>,
+/// }
+///
+/// let res: Warned<Config> = serde_json::from_str(r#"{"output": "dist"}"#).unwrap();
+///
+/// assert_eq!(res.warnings, vec![Warning::StringForm]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Warned<T> {
+    pub value: T,
+    pub warnings: Vec<Warning>,
+}
+
+impl<'de, T> Deserialize<'de> for Warned<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        WarningSeed::new(PhantomData::<T>).deserialize(deserializer)
+    }
+}
+
+/// Wraps another `DeserializeSeed`, such as the one returned by
+/// [`DispatchPolicy::seed`](crate::DispatchPolicy::seed), collecting the
+/// warnings raised while it runs into a [`Warned`].
+pub struct WarningSeed<S> {
+    seed: S,
+}
+
+impl<S> WarningSeed<S> {
+    pub fn new(seed: S) -> Self {
+        WarningSeed { seed }
+    }
+}
+
+impl<'de, S> DeserializeSeed<'de> for WarningSeed<S>
+where
+    S: DeserializeSeed<'de>,
+{
+    type Value = Warned<S::Value>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (res, warnings) = collect(|| self.seed.deserialize(deserializer));
+        res.map(|value| Warned { value, warnings })
+    }
+}
@@ -0,0 +1,93 @@
+//! serde_json's `arbitrary_precision` feature hands numbers over as a map with
+//! a single private key holding their text. Enabling the feature here would
+//! change every other test, so the encoding is written out by hand.
+
+use serde::de::DeserializeSeed;
+use serde_either::{DispatchPolicy, Kind, StringOrStruct, Warned, Warning, WarningSeed};
+use std::collections::HashMap;
+
+const BIG: &str = r#"{"$serde_json::private::Number": "123456789012345678901234567890.000000001"}"#;
+
+fn policy() -> DispatchPolicy {
+    DispatchPolicy::new().numbers_as_string(true)
+}
+
+#[test]
+fn number_as_string_keeps_every_digit() {
+    let res: StringOrStruct<HashMap<String, u8>> = policy()
+        .seed()
+        .deserialize(&mut serde_json::Deserializer::from_str(BIG))
+        .unwrap();
+
+    assert_eq!(
+        res,
+        StringOrStruct::String(String::from("123456789012345678901234567890.000000001"))
+    );
+}
+
+#[test]
+fn number_as_string_warns() {
+    let res: Warned<StringOrStruct<HashMap<String, u8>>> = WarningSeed::new(policy().seed())
+        .deserialize(&mut serde_json::Deserializer::from_str(BIG))
+        .unwrap();
+
+    assert_eq!(
+        res.warnings,
+        vec![Warning::NumberAsString, Warning::StringForm]
+    );
+}
+
+#[test]
+fn struct_arm_gets_the_number_as_is() {
+    let res: StringOrStruct<HashMap<String, String>> = serde_json::from_str(BIG).unwrap();
+
+    match res {
+        StringOrStruct::Struct(map) => assert_eq!(
+            map["$serde_json::private::Number"],
+            "123456789012345678901234567890.000000001"
+        ),
+        StringOrStruct::String(_) => panic!("expected the Struct arm"),
+    }
+}
+
+#[test]
+fn peek_kind() {
+    let mut deserializer = serde_json::Deserializer::from_str(BIG);
+    assert_eq!(policy().peek_kind(&mut deserializer).unwrap(), Kind::String);
+
+    let mut deserializer = serde_json::Deserializer::from_str(BIG);
+    assert_eq!(
+        serde_either::peek_kind(&mut deserializer).unwrap(),
+        Kind::Struct
+    );
+}
+
+#[test]
+fn other_maps_are_structs() {
+    let res: StringOrStruct<HashMap<String, String>> = policy()
+        .seed()
+        .deserialize(&mut serde_json::Deserializer::from_str(
+            r#"{"number": "1"}"#,
+        ))
+        .unwrap();
+
+    assert!(matches!(res, StringOrStruct::Struct(_)));
+}
+
+#[cfg(feature = "rust_decimal")]
+#[test]
+fn decimal() {
+    use rust_decimal::Decimal;
+    use serde_either::StringOrDecimal;
+    use std::str::FromStr;
+
+    let res: StringOrDecimal = serde_json::from_str(
+        r#"{"$serde_json::private::Number": "79228162514264337593543950335"}"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        *res,
+        Decimal::from_str("79228162514264337593543950335").unwrap()
+    );
+}
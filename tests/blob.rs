@@ -0,0 +1,94 @@
+mod common;
+
+use crate::common::SimpleStruct;
+use serde::Serialize;
+use serde_either::{BytesOrStruct, StringOrBytes};
+
+fn cbor<T: Serialize>(value: &T) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    ciborium::into_writer(value, &mut bytes).unwrap();
+    bytes
+}
+
+fn simple() -> SimpleStruct {
+    SimpleStruct {
+        number: 1,
+        text: String::from("a"),
+    }
+}
+
+mod bytes_or_struct {
+    use super::*;
+
+    #[test]
+    fn cbor_byte_strings_are_kept() {
+        let value = BytesOrStruct::<SimpleStruct>::Bytes(vec![0, 159, 146, 150]);
+
+        let res: BytesOrStruct<SimpleStruct> = ciborium::from_reader(&cbor(&value)[..]).unwrap();
+
+        assert_eq!(res, value);
+        assert_eq!(res.as_bytes(), Some(&[0, 159, 146, 150][..]));
+    }
+
+    #[test]
+    fn cbor_structs() {
+        let value = BytesOrStruct::Struct(simple());
+
+        let res: BytesOrStruct<SimpleStruct> = ciborium::from_reader(&cbor(&value)[..]).unwrap();
+
+        assert_eq!(res.as_struct(), Some(&simple()));
+    }
+
+    #[test]
+    fn bytes_are_serialized_as_byte_strings() {
+        let value = BytesOrStruct::<SimpleStruct>::Bytes(vec![1, 2]);
+
+        // a major type 2 header of length 2
+        assert_eq!(cbor(&value), vec![0x42, 1, 2]);
+    }
+
+    #[test]
+    fn rejects_text() {
+        let err = serde_json::from_str::<BytesOrStruct<SimpleStruct>>(r#""text""#).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "invalid type: string \"text\", expected bytes, a map or a sequence"
+        );
+    }
+}
+
+mod string_or_bytes {
+    use super::*;
+
+    #[test]
+    fn cbor_round_trip() {
+        for value in &[
+            StringOrBytes::String(String::from("text")),
+            StringOrBytes::Bytes(b"text".to_vec()),
+            StringOrBytes::Bytes(vec![0xff, 0xfe]),
+        ] {
+            let res: StringOrBytes = ciborium::from_reader(&cbor(value)[..]).unwrap();
+
+            assert_eq!(res, *value);
+        }
+    }
+
+    #[test]
+    fn json_strings() {
+        let res: StringOrBytes = serde_json::from_str(r#""text""#).unwrap();
+
+        assert_eq!(res.as_str(), Some("text"));
+        assert_eq!(res.into_bytes(), b"text".to_vec());
+    }
+
+    #[test]
+    fn rejects_other_types() {
+        let err = serde_json::from_str::<StringOrBytes>("[1, 2]").unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "invalid type: sequence, expected a string or bytes at line 1 column 1"
+        );
+    }
+}
@@ -0,0 +1,226 @@
+mod common;
+
+use crate::common::SimpleStruct;
+use serde::{Deserialize, Serialize};
+use serde_either::{CowStringOrStruct, StringOrStruct};
+use std::borrow::Cow;
+
+mod cow_string_or_struct {
+    use super::*;
+
+    #[derive(Deserialize, Debug)]
+    struct Document<'a> {
+        #[serde(borrow)]
+        fields: Vec<CowStringOrStruct<'a, SimpleStruct>>,
+    }
+
+    #[test]
+    fn strings_are_borrowed() {
+        let json = r#"{"fields": ["plain", "esc\"aped", {"number": 1, "text": "a"}]}"#;
+
+        let res: Document = serde_json::from_str(json).unwrap();
+
+        assert!(matches!(
+            res.fields[0],
+            CowStringOrStruct::String(Cow::Borrowed("plain"))
+        ));
+        assert!(matches!(
+            &res.fields[1],
+            CowStringOrStruct::String(Cow::Owned(s)) if s == "esc\"aped"
+        ));
+        assert_eq!(
+            res.fields[2].as_struct(),
+            Some(&SimpleStruct {
+                number: 1,
+                text: String::from("a")
+            })
+        );
+    }
+
+    #[test]
+    fn sequences_go_to_the_struct() {
+        let res: CowStringOrStruct<Vec<u8>> = serde_json::from_str("[1, 2]").unwrap();
+
+        assert_eq!(res, CowStringOrStruct::Struct(vec![1, 2]));
+    }
+
+    #[test]
+    fn into_owned() {
+        let res: CowStringOrStruct<SimpleStruct> = serde_json::from_str(r#""text""#).unwrap();
+
+        assert_eq!(
+            res.into_owned(),
+            StringOrStruct::String(String::from("text"))
+        );
+    }
+
+    #[test]
+    fn rejects_other_types() {
+        let err = serde_json::from_str::<CowStringOrStruct<SimpleStruct>>("1").unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "invalid type: integer `1`, expected String or Struct at line 1 column 1"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        for json in &[r#""text""#, r#"{"number":1,"text":"a"}"#] {
+            let res: CowStringOrStruct<SimpleStruct> = serde_json::from_str(json).unwrap();
+
+            assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Author<'a> {
+    name: &'a str,
+}
+
+mod string_or_struct_ref {
+    use super::*;
+    use serde_either::StringOrStructRef;
+
+    #[derive(Deserialize, Debug)]
+    struct Book<'a> {
+        #[serde(borrow)]
+        authors: Vec<StringOrStructRef<'a, Author<'a>>>,
+    }
+
+    #[test]
+    fn borrows_strings_and_structs() {
+        let input = String::from(r#"{"authors": [{"name": "John"}, "Michael"]}"#);
+
+        let res: Book = serde_json::from_str(&input).unwrap();
+
+        assert_eq!(res.authors[0].as_struct(), Some(&Author { name: "John" }));
+        assert_eq!(res.authors[1].as_str(), Some("Michael"));
+    }
+
+    #[test]
+    fn into_owned() {
+        let res: StringOrStructRef<SimpleStruct> = serde_json::from_str(r#""a""#).unwrap();
+
+        assert_eq!(res.into_owned(), StringOrStruct::String(String::from("a")));
+    }
+
+    #[test]
+    fn rejects_strings_that_cannot_be_borrowed() {
+        let err = serde_json::from_str::<StringOrStructRef<SimpleStruct>>(r#""a\"b""#).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "invalid type: string \"a\\\"b\", expected borrowed String or Struct at line 1 column 6"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        for json in &[r#""text""#, r#"{"name":"John"}"#] {
+            let res: StringOrStructRef<Author> = serde_json::from_str(json).unwrap();
+
+            assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+        }
+    }
+}
+
+mod string_or_struct_or_vec_ref {
+    use super::*;
+    use serde_either::{StringOrStructOrVec, StringOrStructOrVecRef};
+
+    type Field<'a> = StringOrStructOrVecRef<'a, Author<'a>, Vec<&'a str>>;
+
+    #[test]
+    fn borrows_every_arm() {
+        let input = String::from(r#"["a", {"name": "John"}, ["b", "c"]]"#);
+
+        let res: Vec<Field> = serde_json::from_str(&input).unwrap();
+
+        assert_eq!(res[0].as_str(), Some("a"));
+        assert_eq!(res[1].as_struct(), Some(&Author { name: "John" }));
+        assert_eq!(res[2].as_vec(), Some(&vec!["b", "c"]));
+    }
+
+    #[test]
+    fn into_owned() {
+        let res: StringOrStructOrVecRef<SimpleStruct, Vec<u8>> =
+            serde_json::from_str(r#""a""#).unwrap();
+
+        assert_eq!(
+            res.into_owned(),
+            StringOrStructOrVec::String(String::from("a"))
+        );
+    }
+
+    #[test]
+    fn rejects_other_types() {
+        let err = serde_json::from_str::<Field>("1").unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "invalid type: integer `1`, expected borrowed String, Struct or Vec at line 1 column 1"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        for json in &[r#""a""#, r#"{"name":"John"}"#, r#"["b","c"]"#] {
+            let res: Field = serde_json::from_str(json).unwrap();
+
+            assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+        }
+    }
+}
+
+mod single_or_vec_ref {
+    use super::*;
+    use serde_either::SingleOrVecRef;
+
+    #[test]
+    fn borrows_single_structs() {
+        let input = String::from(r#"{"name": "John"}"#);
+
+        let res: SingleOrVecRef<Author> = serde_json::from_str(&input).unwrap();
+
+        assert_eq!(res.into_vec(), vec![Author { name: "John" }]);
+    }
+
+    #[test]
+    fn borrows_vecs() {
+        let input = String::from(r#"["a", "b"]"#);
+
+        let res: SingleOrVecRef<&str> = serde_json::from_str(&input).unwrap();
+
+        assert_eq!(res, SingleOrVecRef::Vec(vec!["a", "b"]));
+    }
+
+    #[test]
+    fn scalars() {
+        let res: SingleOrVecRef<u8> = serde_json::from_str("1").unwrap();
+        assert_eq!(res, SingleOrVecRef::Single(1));
+
+        let res: SingleOrVecRef<Option<u8>> = serde_json::from_str("null").unwrap();
+        assert_eq!(res, SingleOrVecRef::Single(None));
+    }
+
+    #[test]
+    fn single_errors_come_from_the_element() {
+        let err = serde_json::from_str::<SingleOrVecRef<u8>>("true").unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "invalid type: boolean `true`, expected u8 at line 1 column 4"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        for json in &["1", "[1,2]"] {
+            let res: SingleOrVecRef<u8> = serde_json::from_str(json).unwrap();
+
+            assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+        }
+    }
+}
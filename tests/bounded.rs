@@ -0,0 +1,84 @@
+mod common;
+
+use crate::common::SimpleStruct;
+use serde::de::DeserializeSeed;
+use serde_either::{Bounded, DispatchPolicy, SingleOrVec, StringOrStruct, StringOrStructOrVec};
+
+type BoundedStringOrStruct = Bounded<StringOrStruct<SimpleStruct>, 10, 3>;
+type BoundedSingleOrVec = Bounded<SingleOrVec<u8>, 10, 3>;
+
+#[test]
+fn within_limits() {
+    let res: BoundedStringOrStruct = serde_json::from_str(r#""short""#).unwrap();
+
+    assert_eq!(
+        res.into_inner(),
+        StringOrStruct::String(String::from("short"))
+    );
+}
+
+#[test]
+fn string_too_long() {
+    let res: serde_json::Result<BoundedStringOrStruct> =
+        serde_json::from_str(r#""a very long string""#);
+
+    let message = res.unwrap_err().to_string();
+    assert!(
+        message.contains("exceeds the maximum length of 10"),
+        "{}",
+        message
+    );
+}
+
+#[test]
+fn nested_string_too_long() {
+    let res: serde_json::Result<BoundedStringOrStruct> =
+        serde_json::from_str(r#"{"number": 1, "text": "a very long string"}"#);
+
+    assert!(res.is_err());
+}
+
+#[test]
+fn seq_within_limits() {
+    let res: BoundedSingleOrVec = serde_json::from_str("[1, 2, 3]").unwrap();
+
+    assert_eq!(*res, SingleOrVec::Vec(vec![1, 2, 3]));
+}
+
+#[test]
+fn seq_too_long() {
+    let res: serde_json::Result<BoundedSingleOrVec> = serde_json::from_str("[1, 2, 3, 4]");
+
+    let message = res.unwrap_err().to_string();
+    assert!(
+        message.contains("sequence exceeds the maximum length of 3"),
+        "{}",
+        message
+    );
+}
+
+#[test]
+fn map_too_long() {
+    let res: serde_json::Result<Bounded<StringOrStruct<serde_json::Value>, 10, 1>> =
+        serde_json::from_str(r#"{"a": 1, "b": 2}"#);
+
+    assert!(res.is_err());
+}
+
+#[test]
+fn serializes_transparently() {
+    let value: BoundedStringOrStruct = Bounded(StringOrStruct::String(String::from("short")));
+
+    assert_eq!(serde_json::to_string(&value).unwrap(), r#""short""#);
+}
+
+#[test]
+fn runtime_limits() {
+    let policy = DispatchPolicy::new().max_string_len(4);
+
+    let res: serde_json::Result<StringOrStructOrVec<SimpleStruct, Vec<SimpleStruct>>> = policy
+        .seed()
+        .deserialize(&mut serde_json::Deserializer::from_str(r#""12345""#));
+
+    assert!(res.is_err());
+}
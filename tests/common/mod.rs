@@ -1,8 +1,10 @@
+#![allow(dead_code)]
+
 use serde::{Deserialize, Serialize};
 use serde_either::{StringOrStruct, StringOrStructOrVec};
+use std::error::Error;
 use std::fmt::{self, Display, Formatter};
 use std::str::FromStr;
-use std::{error::Error, ops::Deref};
 
 #[derive(Serialize, Deserialize, PartialEq, Debug)]
 pub struct SimpleStruct {
@@ -16,7 +18,7 @@ pub struct MyType {
     pub string_or_struct_with_vec_of_u8: Option<StringOrStruct<Vec<u8>>>,
     pub string_or_struct_or_vec: Option<StringOrStructOrVec<SimpleStruct, Vec<SimpleStruct>>>,
 }
-#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
 pub struct Person {
     pub first_name: String,
     pub last_name: String,
@@ -38,6 +40,12 @@ impl Display for PersonFromStrError {
 
 impl Error for PersonFromStrError {}
 
+impl Display for Person {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_fmt(format_args!("{} {}", &self.first_name, &self.last_name))
+    }
+}
+
 impl FromStr for Person {
     type Err = PersonFromStrError;
 
@@ -49,7 +57,7 @@ impl FromStr for Person {
         }
         Ok(Person {
             first_name: parts[0].into(),
-            last_name: parts.last().unwrap().deref().into(),
+            last_name: (*parts.last().unwrap()).into(),
         })
     }
 }
@@ -2,8 +2,8 @@ mod common;
 
 use crate::common::{MyType, Person, SimpleStruct};
 use eyre::eyre;
-use serde_either::{SingleOrVec, StringOrStruct, StringOrStructOrVec};
-use serde_json;
+use serde::de::{value, Deserialize, IntoDeserializer};
+use serde_either::{OneOrMany, SingleOrVec, StringOrStruct, StringOrStructOrVec};
 
 mod string_or_struct {
     use super::*;
@@ -114,6 +114,57 @@ mod string_or_struct {
         assert_eq!(value.unwrap(), "[1,5,8,12,32]");
     }
 
+    #[test]
+    fn char_value() {
+        let deserializer: value::CharDeserializer<value::Error> = 'x'.into_deserializer();
+
+        let res = StringOrStruct::<SimpleStruct>::deserialize(deserializer).unwrap();
+
+        assert_eq!(res, StringOrStruct::String(String::from("x")));
+    }
+
+    mod map_keys {
+        use super::*;
+        use std::collections::HashMap;
+
+        #[test]
+        fn string_keys() {
+            let string_value_json = r#"{
+              "Bob Smith": 1,
+              "Uncle Jones": 2
+            }"#;
+
+            let res: HashMap<StringOrStruct<Person>, u8> =
+                serde_json::from_str(string_value_json).unwrap();
+
+            assert_eq!(
+                res.get(&StringOrStruct::String(String::from("Bob Smith"))),
+                Some(&1)
+            );
+            assert_eq!(res.len(), 2);
+        }
+
+        #[test]
+        fn parsed_keys() {
+            let string_value_json = r#"{
+              "Bob Smith": 1
+            }"#;
+
+            let res: HashMap<StringOrStruct<Person>, u8> =
+                serde_json::from_str(string_value_json).unwrap();
+            let parsed: HashMap<StringOrStruct<Person>, u8> = res
+                .into_iter()
+                .map(|(key, value)| (key.into_parsed().unwrap(), value))
+                .collect();
+
+            let key = StringOrStruct::Struct(Person {
+                first_name: String::from("Bob"),
+                last_name: String::from("Smith"),
+            });
+            assert_eq!(parsed.get(&key), Some(&1));
+        }
+    }
+
     mod errors {
         use super::*;
 
@@ -228,6 +279,16 @@ mod string_or_struct_or_vec {
         );
     }
 
+    #[test]
+    fn char_value() {
+        let deserializer: value::CharDeserializer<value::Error> = 'x'.into_deserializer();
+
+        let res = StringOrStructOrVec::<SimpleStruct, Vec<SimpleStruct>>::deserialize(deserializer)
+            .unwrap();
+
+        assert_eq!(res, StringOrStructOrVec::String(String::from("x")));
+    }
+
     mod errors {
         use super::*;
 
@@ -317,3 +378,44 @@ mod single_or_vec {
         );
     }
 }
+
+mod one_or_many {
+    use super::*;
+
+    #[test]
+    fn one() {
+        let res: OneOrMany<u8> = serde_json::from_str("1").unwrap();
+
+        assert_eq!(res, OneOrMany::One(1));
+        assert_eq!(res.first(), &1);
+    }
+
+    #[test]
+    fn many() {
+        let res: OneOrMany<u8> = serde_json::from_str("[1, 2, 3]").unwrap();
+
+        assert_eq!(res, OneOrMany::Many(1, vec![2, 3]));
+        assert_eq!(res.len(), 3);
+        assert_eq!(res.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn single_element_array() {
+        let res: OneOrMany<u8> = serde_json::from_str("[1]").unwrap();
+
+        assert_eq!(res, OneOrMany::Many(1, vec![]));
+        assert_eq!(res.into_vec(), vec![1]);
+    }
+
+    mod errors {
+        use super::*;
+
+        #[test]
+        fn on_empty_array() {
+            let res: serde_json::Result<OneOrMany<u8>> = serde_json::from_str("[]");
+
+            let message = res.unwrap_err().to_string();
+            assert!(message.contains("at least one element"), "{}", message);
+        }
+    }
+}
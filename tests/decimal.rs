@@ -0,0 +1,107 @@
+#![cfg(feature = "rust_decimal")]
+
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use serde_either::StringOrDecimal;
+use std::str::FromStr;
+
+fn dec(s: &str) -> Decimal {
+    Decimal::from_str(s).unwrap()
+}
+
+#[test]
+fn quoted_string_keeps_scale() {
+    let res: StringOrDecimal = serde_json::from_str(r#""19.990""#).unwrap();
+
+    assert_eq!(res.to_string(), "19.990");
+}
+
+#[test]
+fn quoted_string_keeps_precision() {
+    let res: StringOrDecimal = serde_json::from_str(r#""12345678901234567890.12345678""#).unwrap();
+
+    assert_eq!(*res, dec("12345678901234567890.12345678"));
+}
+
+#[test]
+fn scientific_string() {
+    let res: StringOrDecimal = serde_json::from_str(r#""1.5e-3""#).unwrap();
+
+    assert_eq!(*res, dec("0.0015"));
+}
+
+#[test]
+fn integer() {
+    let res: StringOrDecimal = serde_json::from_str("-42").unwrap();
+
+    assert_eq!(*res, Decimal::from(-42));
+}
+
+#[test]
+fn float_uses_shortest_representation() {
+    let res: StringOrDecimal = serde_json::from_str("0.1").unwrap();
+
+    assert_eq!(res.to_string(), "0.1");
+}
+
+#[test]
+fn bare_numbers_go_through_f64() {
+    // without serde_json's arbitrary_precision, the last digit is lost
+    let res: StringOrDecimal = serde_json::from_str("0.300000000000000044").unwrap();
+    assert_eq!(res.to_string(), "0.30000000000000004");
+
+    let res: StringOrDecimal = serde_json::from_str(r#""0.300000000000000044""#).unwrap();
+    assert_eq!(res.to_string(), "0.300000000000000044");
+}
+
+#[test]
+fn arbitrary_precision_numbers_keep_every_digit() {
+    let res: StringOrDecimal =
+        serde_json::from_str(r#"{"$serde_json::private::Number": "0.300000000000000044"}"#)
+            .unwrap();
+
+    assert_eq!(res.to_string(), "0.300000000000000044");
+}
+
+#[test]
+fn invalid_string() {
+    let res = serde_json::from_str::<StringOrDecimal>(r#""twelve""#);
+
+    assert!(res.is_err());
+}
+
+#[test]
+fn rejects_structs() {
+    let res = serde_json::from_str::<StringOrDecimal>(r#"{"amount": 1}"#);
+
+    assert!(res.is_err());
+}
+
+#[test]
+fn serializes_as_string() {
+    let res = serde_json::to_string(&StringOrDecimal(dec("100.00"))).unwrap();
+
+    assert_eq!(res, r#""100.00""#);
+}
+
+#[test]
+fn in_struct() {
+    #[derive(Deserialize)]
+    struct Price {
+        amount: StringOrDecimal,
+        tax: StringOrDecimal,
+    }
+
+    let res: Price = serde_json::from_str(r#"{"amount": "9.95", "tax": 2}"#).unwrap();
+
+    assert_eq!(*res.amount + *res.tax, dec("11.95"));
+}
+
+#[test]
+fn amounts_written_both_ways() {
+    let res: Vec<StringOrDecimal> = serde_json::from_str(r#"["10.50", 10.5]"#).unwrap();
+
+    assert_eq!(*res[0], *res[1]);
+    assert_eq!(res[0].to_string(), "10.50");
+    assert_eq!(res[1].to_string(), "10.5");
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use crate::common::SimpleStruct;
+use serde::Deserialize;
+use serde_either::{SingleOrVec, StringOrStruct};
+
+serde_either::default_string_fn!(default_text, "none");
+
+#[derive(Deserialize, Debug)]
+struct WithDefaults {
+    #[serde(default = "serde_either::default_string")]
+    empty: StringOrStruct<SimpleStruct>,
+    #[serde(default = "default_text")]
+    literal: StringOrStruct<SimpleStruct>,
+    #[serde(default = "serde_either::defaults::empty_string_or_struct")]
+    empty_from_module: StringOrStruct<SimpleStruct>,
+    #[serde(default = "serde_either::defaults::empty_single_or_vec")]
+    list: SingleOrVec<SimpleStruct>,
+}
+
+#[test]
+fn missing_fields_use_defaults() {
+    let res: WithDefaults = serde_json::from_str("{}").unwrap();
+
+    assert_eq!(res.empty, StringOrStruct::String(String::new()));
+    assert_eq!(res.literal, StringOrStruct::String(String::from("none")));
+    assert_eq!(res.empty_from_module, StringOrStruct::String(String::new()));
+    assert_eq!(res.list, SingleOrVec::Vec(vec![]));
+}
+
+#[test]
+fn string_default_from_a_function() {
+    fn default_mode() -> StringOrStruct<SimpleStruct> {
+        serde_either::defaults::string_default("auto")
+    }
+
+    #[derive(Deserialize)]
+    struct Config {
+        #[serde(default = "default_mode")]
+        mode: StringOrStruct<SimpleStruct>,
+    }
+
+    let res: Config = serde_json::from_str("{}").unwrap();
+    assert_eq!(res.mode, StringOrStruct::String(String::from("auto")));
+}
+
+#[test]
+fn present_fields_are_deserialized() {
+    let res: WithDefaults =
+        serde_json::from_str(r#"{"empty": "a", "literal": {"number": 1, "text": "b"}}"#).unwrap();
+
+    assert_eq!(res.empty, StringOrStruct::String(String::from("a")));
+    assert_eq!(
+        res.literal,
+        StringOrStruct::Struct(SimpleStruct {
+            number: 1,
+            text: String::from("b")
+        })
+    );
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "humantime")]
+
+use serde::Deserialize;
+use serde_either::DurationOrNumber;
+use std::time::Duration;
+
+#[derive(Deserialize)]
+struct Config {
+    timeout: DurationOrNumber,
+    interval: DurationOrNumber<true>,
+}
+
+#[test]
+fn numbers() {
+    let res: Config = serde_yaml::from_str("timeout: 30\ninterval: 250\n").unwrap();
+
+    assert_eq!(
+        res.timeout,
+        DurationOrNumber::Number(Duration::from_secs(30))
+    );
+    assert_eq!(res.interval.duration(), Duration::from_millis(250));
+}
+
+#[test]
+fn fractional_numbers() {
+    let res: DurationOrNumber = serde_json::from_str("1.5").unwrap();
+    assert_eq!(res.duration(), Duration::from_millis(1500));
+
+    let res: DurationOrNumber<true> = serde_json::from_str("0.5").unwrap();
+    assert_eq!(res.duration(), Duration::from_micros(500));
+}
+
+#[test]
+fn strings() {
+    let res: Config = serde_yaml::from_str("timeout: 1h30m\ninterval: 2s\n").unwrap();
+
+    assert_eq!(
+        res.timeout,
+        DurationOrNumber::String(Duration::from_secs(5400))
+    );
+    assert_eq!(Duration::from(res.interval), Duration::from_secs(2));
+}
+
+#[test]
+fn rejects_invalid_durations() {
+    let err = serde_json::from_str::<DurationOrNumber>("-1").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid value: integer `-1`, expected a number of seconds or a duration string at line 1 column 2"
+    );
+
+    let err = serde_json::from_str::<DurationOrNumber>(r#""soon""#).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid duration \"soon\": expected number at 0 at line 1 column 6"
+    );
+
+    assert!(serde_json::from_str::<DurationOrNumber>("true").is_err());
+}
+
+#[test]
+fn round_trip() {
+    for json in &["30", "1.5", r#""1h 30m""#] {
+        let res: DurationOrNumber = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+    }
+
+    for json in &["250", "0.5"] {
+        let res: DurationOrNumber<true> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+    }
+}
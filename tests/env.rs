@@ -0,0 +1,75 @@
+use serde::Deserialize;
+use serde_either::EnvListOrMap;
+
+#[derive(Deserialize, Debug)]
+struct Service {
+    environment: EnvListOrMap,
+}
+
+fn pair(key: &str, value: Option<&str>) -> (String, Option<String>) {
+    (String::from(key), value.map(String::from))
+}
+
+#[test]
+fn lists() {
+    let res: Service = serde_yaml::from_str(
+        "environment:\n  - RACK_ENV=development\n  - URL=http://a?b=c\n  - SESSION_SECRET\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        res.environment.into_pairs(),
+        vec![
+            pair("RACK_ENV", Some("development")),
+            pair("URL", Some("http://a?b=c")),
+            pair("SESSION_SECRET", None),
+        ]
+    );
+}
+
+#[test]
+fn maps() {
+    let res: Service = serde_yaml::from_str(
+        "environment:\n  RACK_ENV: development\n  SHOW: true\n  WORKERS: 4\n  SESSION_SECRET:\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        res.environment.into_pairs(),
+        vec![
+            pair("RACK_ENV", Some("development")),
+            pair("SHOW", Some("true")),
+            pair("WORKERS", Some("4")),
+            pair("SESSION_SECRET", None),
+        ]
+    );
+}
+
+#[test]
+fn into_map() {
+    let res: EnvListOrMap = serde_json::from_str(r#"["A=1", "B=2", "A=3"]"#).unwrap();
+
+    let map = res.into_map();
+    assert_eq!(map.len(), 2);
+    assert_eq!(map["A"], Some(String::from("3")));
+}
+
+#[test]
+fn rejects_other_types() {
+    let err = serde_json::from_str::<EnvListOrMap>(r#""A=1""#).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid type: string \"A=1\", expected a list of KEY=VALUE strings or a map at line 1 column 5"
+    );
+
+    assert!(serde_json::from_str::<EnvListOrMap>(r#"{"A": [1]}"#).is_err());
+}
+
+#[test]
+fn round_trip_keeps_the_shape() {
+    for json in &[r#"["A=1","B"]"#, r#"{"A":"1","B":null}"#] {
+        let res: EnvListOrMap = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+    }
+}
@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+use serde_either::{HasId, IdOrObject};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Customer {
+    id: String,
+    email: String,
+}
+
+impl HasId for Customer {
+    type Id = String;
+
+    fn id(&self) -> &String {
+        &self.id
+    }
+}
+
+#[derive(Deserialize)]
+struct Charge {
+    customer: IdOrObject<String, Customer>,
+}
+
+#[test]
+fn ids() {
+    let res: Charge = serde_json::from_str(r#"{"customer": "cus_123"}"#).unwrap();
+
+    assert!(res.customer.id_only());
+    assert_eq!(res.customer.id(), "cus_123");
+    assert_eq!(res.customer.as_object(), None);
+}
+
+#[test]
+fn expanded_objects() {
+    let res: Charge =
+        serde_json::from_str(r#"{"customer": {"id": "cus_123", "email": "a@b.c"}}"#).unwrap();
+
+    assert!(res.customer.expanded());
+    assert_eq!(res.customer.id(), "cus_123");
+}
+
+#[test]
+fn numeric_ids_with_a_closure() {
+    #[derive(Deserialize)]
+    struct Order {
+        number: u64,
+    }
+
+    let res: Vec<IdOrObject<u64, Order>> = serde_json::from_str(r#"[7, {"number": 8}]"#).unwrap();
+
+    let ids: Vec<u64> = res
+        .iter()
+        .map(|order| *order.id_with(|o| &o.number))
+        .collect();
+    assert_eq!(ids, [7, 8]);
+}
+
+#[test]
+fn invalid_objects_are_errors() {
+    let res = serde_json::from_str::<IdOrObject<String, Customer>>(r#"{"id": 1}"#);
+
+    assert!(res.is_err());
+}
+
+#[test]
+fn round_trip() {
+    for json in &[r#""cus_123""#, r#"{"id":"cus_123","email":"a@b.c"}"#] {
+        let res: IdOrObject<String, Customer> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+    }
+}
@@ -0,0 +1,156 @@
+mod common;
+
+use crate::common::SimpleStruct;
+use serde::de::DeserializeSeed;
+use serde::Deserialize;
+use serde_either::{
+    DeserializeWithPolicy, DispatchPolicy, Kind, SingleOrVec, StringOrStruct, StringOrStructOrVec,
+    Tree,
+};
+
+fn streaming() -> DispatchPolicy {
+    DispatchPolicy::new().stream_structs(true)
+}
+
+// deserializes through serde_ignored, returning the paths of the ignored keys
+fn with_ignored<'a, T>(policy: &DispatchPolicy, json: &'a str) -> (T, Vec<String>)
+where
+    T: DeserializeWithPolicy<'a>,
+{
+    let mut ignored = Vec::new();
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    let mut callback = |path: serde_ignored::Path| ignored.push(path.to_string());
+    let deserializer = serde_ignored::Deserializer::new(&mut deserializer, &mut callback);
+    let value = policy.seed::<T>().deserialize(deserializer).unwrap();
+    (value, ignored)
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+enum Shape {
+    Circle { radius: u32 },
+    Square(u32),
+}
+
+#[test]
+fn buffering_hides_ignored_keys() {
+    let (value, ignored): (StringOrStruct<SimpleStruct>, _) = with_ignored(
+        &DispatchPolicy::new(),
+        r#"{"number": 1, "text": "a", "extra": true}"#,
+    );
+
+    assert!(matches!(value, StringOrStruct::Struct(_)));
+    assert!(ignored.is_empty());
+}
+
+#[test]
+fn string_or_struct() {
+    let (value, ignored): (StringOrStruct<SimpleStruct>, _) = with_ignored(
+        &streaming(),
+        r#"{"extra": [1, 2], "number": 1, "text": "a"}"#,
+    );
+
+    assert_eq!(
+        value,
+        StringOrStruct::Struct(SimpleStruct {
+            number: 1,
+            text: String::from("a")
+        })
+    );
+    assert_eq!(ignored, vec!["extra"]);
+}
+
+fn streamed<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: DeserializeWithPolicy<'de>,
+{
+    T::deserialize_with_policy(deserializer, &streaming())
+}
+
+#[test]
+fn nested_paths() {
+    #[derive(Deserialize)]
+    struct Config {
+        #[serde(deserialize_with = "streamed")]
+        output: StringOrStructOrVec<SimpleStruct, Vec<SimpleStruct>>,
+    }
+
+    let json = r#"{"output": {"number": 1, "text": "a", "extra": 1}, "verbose": true}"#;
+    let mut ignored = Vec::new();
+    let config: Config =
+        serde_ignored::deserialize(&mut serde_json::Deserializer::from_str(json), |path| {
+            ignored.push(path.to_string())
+        })
+        .unwrap();
+
+    assert_eq!(config.output.kind(), Kind::Struct);
+    assert_eq!(ignored, vec!["output.extra", "verbose"]);
+}
+
+#[test]
+fn strings_and_vecs_are_buffered() {
+    let policy = streaming();
+
+    let (value, _): (StringOrStructOrVec<SimpleStruct, Vec<SimpleStruct>>, _) =
+        with_ignored(&policy, r#""text""#);
+    assert_eq!(value, StringOrStructOrVec::String(String::from("text")));
+
+    let (value, _): (StringOrStructOrVec<SimpleStruct, Vec<SimpleStruct>>, _) =
+        with_ignored(&policy, r#"[{"number": 1, "text": "a"}]"#);
+    assert_eq!(value.kind(), Kind::Vec);
+}
+
+#[test]
+fn single_or_vec() {
+    let (value, ignored): (SingleOrVec<SimpleStruct>, _) = with_ignored(
+        &streaming(),
+        r#"{"number": 2, "unknown": null, "text": "b"}"#,
+    );
+
+    assert_eq!(value.len(), 1);
+    assert_eq!(ignored, vec!["unknown"]);
+}
+
+#[test]
+fn tree() {
+    let (value, ignored): (Tree<SimpleStruct>, _) =
+        with_ignored(&streaming(), r#"{"number": 3, "text": "c", "more": {}}"#);
+
+    assert_eq!(value.depth(), 0);
+    assert_eq!(ignored, vec!["more"]);
+}
+
+#[test]
+fn externally_tagged_enum() {
+    let policy = streaming();
+
+    let (value, _): (StringOrStruct<Shape>, _) =
+        with_ignored(&policy, r#"{"Circle": {"radius": 2}}"#);
+    assert_eq!(value, StringOrStruct::Struct(Shape::Circle { radius: 2 }));
+
+    let (value, _): (StringOrStruct<Shape>, _) = with_ignored(&policy, r#"{"Square": 3}"#);
+    assert_eq!(value, StringOrStruct::Struct(Shape::Square(3)));
+}
+
+#[test]
+fn denied_struct() {
+    let policy = streaming().deny(Kind::Struct);
+    let mut deserializer = serde_json::Deserializer::from_str(r#"{"number": 1, "text": "a"}"#);
+
+    let err = policy
+        .seed::<StringOrStruct<SimpleStruct>>()
+        .deserialize(&mut deserializer)
+        .unwrap_err();
+
+    assert!(err.to_string().contains("expected String"), "{}", err);
+}
+
+#[test]
+fn arbitrary_precision_numbers_are_buffered() {
+    let policy = streaming().numbers_as_string(true);
+
+    let (value, _): (StringOrStruct<SimpleStruct>, _) =
+        with_ignored(&policy, r#"{"$serde_json::private::Number": "1.50"}"#);
+
+    assert_eq!(value, StringOrStruct::String(String::from("1.50")));
+}
@@ -0,0 +1,70 @@
+use serde::Deserialize;
+use serde_either::IntOrString;
+
+#[derive(Deserialize)]
+struct Port {
+    port: IntOrString,
+    #[serde(rename = "targetPort")]
+    target_port: IntOrString,
+}
+
+#[test]
+fn kubernetes_service_port() {
+    let res: Port = serde_yaml::from_str("port: 80\ntargetPort: http\n").unwrap();
+
+    assert_eq!(res.port, IntOrString::Int(80));
+    assert_eq!(res.target_port, IntOrString::String(String::from("http")));
+}
+
+#[test]
+fn percentage() {
+    let res: IntOrString = serde_json::from_str(r#""25%""#).unwrap();
+
+    assert_eq!(res.as_percentage(), Some(25));
+    assert_eq!(res.as_int(), None);
+}
+
+#[test]
+fn not_percentages() {
+    assert_eq!(IntOrString::Int(25).as_percentage(), None);
+    assert_eq!(
+        IntOrString::String(String::from("25")).as_percentage(),
+        None
+    );
+    assert_eq!(
+        IntOrString::String(String::from("a%")).as_percentage(),
+        None
+    );
+}
+
+#[test]
+fn quoted_integer_stays_a_string() {
+    let res: IntOrString = serde_json::from_str(r#""80""#).unwrap();
+
+    assert_eq!(res, IntOrString::String(String::from("80")));
+}
+
+#[test]
+fn rejects_out_of_range_integers() {
+    let err = serde_json::from_str::<IntOrString>("4294967296").unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "invalid value: integer `4294967296`, expected a 32-bit integer or a string at line 1 column 10"
+    );
+}
+
+#[test]
+fn rejects_floats_and_structs() {
+    assert!(serde_json::from_str::<IntOrString>("1.5").is_err());
+    assert!(serde_json::from_str::<IntOrString>(r#"{"port": 80}"#).is_err());
+}
+
+#[test]
+fn round_trip() {
+    for json in &["-1", "80", r#""25%""#] {
+        let res: IntOrString = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+    }
+}
@@ -0,0 +1,78 @@
+#![cfg(feature = "intern")]
+
+mod common;
+
+use crate::common::SimpleStruct;
+use serde::de::DeserializeSeed;
+use serde_either::{InternedStringOrStruct, Interner};
+use std::sync::Arc;
+
+fn from_str_with(
+    interner: &Interner,
+    json: &str,
+) -> serde_json::Result<InternedStringOrStruct<SimpleStruct>> {
+    interner
+        .seed()
+        .deserialize(&mut serde_json::Deserializer::from_str(json))
+}
+
+#[test]
+fn equal_strings_share_allocation() {
+    let interner = Interner::new();
+
+    let first = from_str_with(&interner, r#""shared""#).unwrap();
+    let second = from_str_with(&interner, r#""shared""#).unwrap();
+
+    match (first, second) {
+        (InternedStringOrStruct::String(a), InternedStringOrStruct::String(b)) => {
+            assert!(Arc::ptr_eq(&a, &b))
+        }
+        _ => panic!("Wrong deserialize type"),
+    }
+    assert_eq!(interner.len(), 1);
+}
+
+#[test]
+fn shared_across_threads() {
+    let interner = Interner::new();
+
+    let values: Vec<_> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..4)
+            .map(|_| scope.spawn(|| from_str_with(&interner, r#""shared""#).unwrap()))
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+
+    for value in &values[1..] {
+        match (&values[0], value) {
+            (InternedStringOrStruct::String(a), InternedStringOrStruct::String(b)) => {
+                assert!(Arc::ptr_eq(a, b))
+            }
+            _ => panic!("Wrong deserialize type"),
+        }
+    }
+    assert_eq!(interner.len(), 1);
+}
+
+#[test]
+fn struct_value() {
+    let interner = Interner::new();
+
+    let res = from_str_with(&interner, r#"{"number": 1, "text": "a"}"#).unwrap();
+
+    assert_eq!(
+        res,
+        InternedStringOrStruct::Struct(SimpleStruct {
+            number: 1,
+            text: String::from("a")
+        })
+    );
+    assert!(interner.is_empty());
+}
+
+#[test]
+fn serialize() {
+    let value = InternedStringOrStruct::<SimpleStruct>::String(Arc::from("shared"));
+
+    assert_eq!(serde_json::to_string(&value).unwrap(), r#""shared""#);
+}
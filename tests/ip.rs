@@ -0,0 +1,57 @@
+#![cfg(feature = "ipnet")]
+
+use ipnet::IpNet;
+use serde_either::{IpNetParts, IpOrStruct};
+
+#[test]
+fn cidr_string() {
+    let res: IpOrStruct = serde_json::from_str(r#""10.0.0.0/8""#).unwrap();
+
+    assert_eq!(res, IpOrStruct::Ip("10.0.0.0/8".parse().unwrap()));
+}
+
+#[test]
+fn address_string() {
+    let res: IpOrStruct = serde_json::from_str(r#""::1""#).unwrap();
+
+    assert_eq!(res.to_ipnet().unwrap(), "::1/128".parse::<IpNet>().unwrap());
+}
+
+#[test]
+fn struct_value() {
+    let res: IpOrStruct = serde_json::from_str(r#"{"addr": "192.168.0.0", "prefix": 16}"#).unwrap();
+
+    assert_eq!(
+        res,
+        IpOrStruct::Struct(IpNetParts {
+            addr: "192.168.0.0".parse().unwrap(),
+            prefix: 16
+        })
+    );
+    assert_eq!(
+        res.to_ipnet().unwrap(),
+        "192.168.0.0/16".parse::<IpNet>().unwrap()
+    );
+}
+
+#[test]
+fn invalid_prefix_in_struct() {
+    let res: IpOrStruct = serde_json::from_str(r#"{"addr": "10.0.0.0", "prefix": 33}"#).unwrap();
+
+    assert!(res.to_ipnet().is_err());
+}
+
+#[test]
+fn invalid_string() {
+    let res: serde_json::Result<IpOrStruct> = serde_json::from_str(r#""10.0.0.0/abc""#);
+
+    let message = res.unwrap_err().to_string();
+    assert!(message.contains("invalid IP network"), "{}", message);
+}
+
+#[test]
+fn serialize() {
+    let value: IpOrStruct = IpOrStruct::Ip("10.0.0.0/8".parse().unwrap());
+
+    assert_eq!(serde_json::to_string(&value).unwrap(), r#""10.0.0.0/8""#);
+}
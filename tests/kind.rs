@@ -0,0 +1,167 @@
+mod common;
+
+use crate::common::SimpleStruct;
+use serde_either::{Kind, SingleOrVec, StringOrStruct, StringOrStructOrVec};
+use std::collections::BTreeMap;
+
+#[test]
+fn kind_of_values() {
+    let value: StringOrStructOrVec<SimpleStruct, Vec<SimpleStruct>> =
+        StringOrStructOrVec::Vec(vec![]);
+    assert_eq!(value.kind(), Kind::Vec);
+
+    let value = StringOrStruct::<SimpleStruct>::String(String::from("a"));
+    assert_eq!(value.kind(), Kind::String);
+
+    let value = SingleOrVec::Single(1);
+    assert_eq!(value.kind(), Kind::Struct);
+}
+
+#[test]
+fn display() {
+    assert_eq!(Kind::String.to_string(), "String");
+    assert_eq!(format!("{}", Kind::Struct), "Struct");
+}
+
+#[test]
+fn from_str() {
+    assert_eq!("Vec".parse::<Kind>().unwrap(), Kind::Vec);
+    assert!("Map".parse::<Kind>().is_err());
+}
+
+#[test]
+fn census_by_kind_name() {
+    let mut census = BTreeMap::new();
+    for json in &[r#""a""#, r#"{"number": 1, "text": "b"}"#, r#""c""#] {
+        let value: StringOrStruct<SimpleStruct> = serde_json::from_str(json).unwrap();
+        *census.entry(value.kind().to_string()).or_insert(0) += 1;
+    }
+
+    assert_eq!(
+        serde_json::to_string(&census).unwrap(),
+        r#"{"String":2,"Struct":1}"#
+    );
+}
+
+#[test]
+fn round_trip() {
+    let kinds = vec![Kind::String, Kind::Struct, Kind::Vec];
+
+    let json = serde_json::to_string(&kinds).unwrap();
+    let res: Vec<Kind> = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(json, r#"["String","Struct","Vec"]"#);
+    assert_eq!(res, kinds);
+}
+
+#[test]
+fn deserialize_unknown() {
+    let res: serde_json::Result<Kind> = serde_json::from_str(r#""Map""#);
+
+    let message = res.unwrap_err().to_string();
+    assert!(message.contains("unknown variant `Map`"), "{}", message);
+}
+
+mod peek_kind {
+    use super::*;
+    use serde_either::{peek_kind, DispatchPolicy};
+
+    fn peek(json: &str) -> serde_json::Result<Kind> {
+        let mut deserializer = serde_json::Deserializer::from_str(json);
+        let kind = peek_kind(&mut deserializer)?;
+        deserializer.end()?;
+        Ok(kind)
+    }
+
+    #[test]
+    fn kinds() {
+        assert_eq!(peek(r#""a""#).unwrap(), Kind::String);
+        assert_eq!(peek(r#"{"a": {"b": [1]}}"#).unwrap(), Kind::Struct);
+        assert_eq!(peek(r#"[{"a": 1}, 2]"#).unwrap(), Kind::Vec);
+    }
+
+    #[test]
+    fn errors() {
+        let message = peek("true").unwrap_err().to_string();
+
+        assert!(
+            message.contains("expected String, Struct or Vec"),
+            "{}",
+            message
+        );
+        assert!(peek("null").is_err());
+    }
+
+    #[test]
+    fn with_policy() {
+        let policy = DispatchPolicy::new()
+            .numbers_as_string(true)
+            .seq_of_one_as_struct(true)
+            .deny(Kind::Vec);
+
+        let kind = |json: &str| policy.peek_kind(&mut serde_json::Deserializer::from_str(json));
+
+        assert_eq!(kind("12").unwrap(), Kind::String);
+        assert_eq!(kind("[1]").unwrap(), Kind::Struct);
+        assert!(kind("[1, 2]").is_err());
+    }
+
+    #[test]
+    fn buffered_value() {
+        let value = serde_value::Value::Seq(vec![]);
+
+        let kind = peek_kind(serde_value::ValueDeserializer::<
+            serde_value::DeserializerError,
+        >::new(value));
+
+        assert_eq!(kind.unwrap(), Kind::Vec);
+    }
+
+    #[test]
+    fn unit_only_variants() {
+        use serde::de::value::{EnumAccessDeserializer, Error, StrDeserializer};
+
+        let deserializer = EnumAccessDeserializer::new(StrDeserializer::<Error>::new("Empty"));
+
+        assert_eq!(peek_kind(deserializer).unwrap(), Kind::Struct);
+    }
+
+    #[test]
+    fn limits() {
+        let kind = |policy: DispatchPolicy, json: &str| {
+            policy.peek_kind(&mut serde_json::Deserializer::from_str(json))
+        };
+
+        let message = kind(DispatchPolicy::new().max_string_len(3), r#""abcd""#)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            message.contains("exceeds the maximum length of 3"),
+            "{}",
+            message
+        );
+
+        let message = kind(DispatchPolicy::new().max_seq_len(2), "[1, 2, 3]")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            message.contains("exceeds the maximum length of 2"),
+            "{}",
+            message
+        );
+
+        let message = kind(DispatchPolicy::new().max_depth(2), "[[[1]]]")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            message.contains("exceeds the maximum nesting depth of 2"),
+            "{}",
+            message
+        );
+
+        assert_eq!(
+            kind(DispatchPolicy::new().max_depth(2), "[[1]]").unwrap(),
+            Kind::Vec
+        );
+    }
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use crate::common::SimpleStruct;
+use serde_either::{LenientVec, StringOrStruct};
+
+#[test]
+fn all_valid() {
+    let res: LenientVec<u8> = serde_json::from_str("[1, 2, 3]").unwrap();
+
+    assert!(res.is_clean());
+    assert_eq!(res.into_vec(), vec![1, 2, 3]);
+}
+
+#[test]
+fn skips_invalid_elements() {
+    let res: LenientVec<StringOrStruct<SimpleStruct>> = serde_json::from_str(
+        r#"["a", {"number": "nan", "text": "b"}, {"number": 1, "text": "c"}, 7]"#,
+    )
+    .unwrap();
+
+    let (items, errors) = res.into_parts();
+
+    assert_eq!(
+        items,
+        vec![
+            StringOrStruct::String(String::from("a")),
+            StringOrStruct::Struct(SimpleStruct {
+                number: 1,
+                text: String::from("c")
+            })
+        ]
+    );
+    assert_eq!(
+        errors.iter().map(|e| e.index).collect::<Vec<_>>(),
+        vec![1, 3]
+    );
+}
+
+#[test]
+fn errors_are_displayed_with_index() {
+    let res: LenientVec<u8> = serde_json::from_str(r#"[1, "two"]"#).unwrap();
+
+    let message = res.errors()[0].to_string();
+    assert!(message.starts_with("element 1:"), "{}", message);
+}
+
+#[test]
+fn rejects_non_sequences() {
+    let res: serde_json::Result<LenientVec<u8>> = serde_json::from_str("1");
+
+    assert!(res.is_err());
+}
+
+#[test]
+fn serializes_items() {
+    let res: LenientVec<u8> = serde_json::from_str(r#"[1, "two", 3]"#).unwrap();
+
+    assert_eq!(serde_json::to_string(&res).unwrap(), "[1,3]");
+}
+
+#[test]
+fn errors_keep_their_source() {
+    use std::error::Error;
+
+    let res: LenientVec<u8> = serde_json::from_str(r#"[1, "two"]"#).unwrap();
+
+    let element = &res.errors()[0];
+    let source = element.source().unwrap();
+    assert_eq!(source.to_string(), element.error.message());
+    assert!(
+        element.error.message().contains("expected u8"),
+        "{}",
+        element.error
+    );
+}
@@ -0,0 +1,58 @@
+use serde::Deserialize;
+use serde_either::LocalizedString;
+use std::collections::BTreeMap;
+
+#[derive(Deserialize)]
+struct Manifest {
+    name: LocalizedString,
+    description: LocalizedString,
+}
+
+#[test]
+fn plain_and_localized() {
+    let res: Manifest = serde_json::from_str(
+        r#"{"name": "Notes", "description": {"en": "Take notes", "pt-BR": "Faça anotações"}}"#,
+    )
+    .unwrap();
+
+    assert_eq!(res.name, LocalizedString::String(String::from("Notes")));
+    assert_eq!(res.name.get("fr"), Some("Notes"));
+    assert_eq!(res.description.get("pt-BR"), Some("Faça anotações"));
+    assert_eq!(res.description.get("pt"), None);
+    assert_eq!(res.description.get_or_fallback("fr"), Some("Take notes"));
+}
+
+#[test]
+fn languages_of_locales() {
+    let res: LocalizedString = serde_json::from_str(r#"{"de": "Öffnen"}"#).unwrap();
+
+    assert_eq!(res.get("de_CH"), Some("Öffnen"));
+    assert_eq!(res.fallback(), Some("Öffnen"));
+}
+
+#[test]
+fn empty_maps_have_no_fallback() {
+    let res = LocalizedString::Map(BTreeMap::new());
+
+    assert_eq!(res.get_or_fallback("en"), None);
+}
+
+#[test]
+fn rejects_other_types() {
+    let err = serde_json::from_str::<LocalizedString>(r#"{"en": 1}"#).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid type: integer `1`, expected a string at line 1 column 8"
+    );
+
+    assert!(serde_json::from_str::<LocalizedString>("[]").is_err());
+}
+
+#[test]
+fn round_trip() {
+    for json in &[r#""Notes""#, r#"{"de":"Notizen","en":"Notes"}"#] {
+        let res: LocalizedString = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+    }
+}
@@ -0,0 +1,117 @@
+#![cfg(feature = "mlua")]
+
+use mlua::{Lua, Table};
+use serde_either::{OneOrMany, SingleOrVec, StringOrStruct, StringOrStructOrVec};
+use std::collections::HashMap;
+
+type Fields = HashMap<String, i64>;
+
+fn eval<T: mlua::FromLua>(lua: &Lua, chunk: &str) -> mlua::Result<T> {
+    lua.load(chunk).eval()
+}
+
+#[test]
+fn string_or_struct_from_lua() {
+    let lua = Lua::new();
+
+    let res: StringOrStruct<Fields> = eval(&lua, r#""text""#).unwrap();
+    assert_eq!(res, StringOrStruct::String(String::from("text")));
+
+    let res: StringOrStruct<Fields> = eval(&lua, "{ number = 1 }").unwrap();
+    let expected: Fields = vec![(String::from("number"), 1)].into_iter().collect();
+    assert_eq!(res, StringOrStruct::Struct(expected));
+
+    assert!(eval::<StringOrStruct<Fields>>(&lua, "true").is_err());
+}
+
+#[test]
+fn string_or_struct_or_vec_from_lua() {
+    let lua = Lua::new();
+
+    let res: StringOrStructOrVec<Fields, Vec<Fields>> = eval(&lua, r#""text""#).unwrap();
+    assert_eq!(res, StringOrStructOrVec::String(String::from("text")));
+
+    let res: StringOrStructOrVec<Fields, Vec<Fields>> =
+        eval(&lua, "{ { number = 1 }, { number = 2 } }").unwrap();
+    assert!(matches!(res, StringOrStructOrVec::Vec(ref v) if v.len() == 2));
+
+    let res: StringOrStructOrVec<Fields, Vec<Fields>> = eval(&lua, "{ number = 1 }").unwrap();
+    assert!(matches!(res, StringOrStructOrVec::Struct(_)));
+}
+
+#[test]
+fn mixed_and_empty_tables_are_structs() {
+    let lua = Lua::new();
+
+    let res: SingleOrVec<Table> = eval(&lua, "{ 1, 2, extra = 3 }").unwrap();
+    assert!(matches!(res, SingleOrVec::Single(ref table) if table.raw_len() == 2));
+
+    let res: SingleOrVec<Table> = eval(&lua, "{}").unwrap();
+    assert!(matches!(res, SingleOrVec::Single(ref table) if table.is_empty()));
+}
+
+#[test]
+fn single_or_vec_from_lua() {
+    let lua = Lua::new();
+
+    let res: SingleOrVec<i64> = eval(&lua, "1").unwrap();
+    assert_eq!(res, SingleOrVec::Single(1));
+
+    let res: SingleOrVec<i64> = eval(&lua, "{ 1, 2, 3 }").unwrap();
+    assert_eq!(res, SingleOrVec::Vec(vec![1, 2, 3]));
+}
+
+#[test]
+fn one_or_many_from_lua() {
+    let lua = Lua::new();
+
+    let res: OneOrMany<String> = eval(&lua, r#""a""#).unwrap();
+    assert_eq!(res, OneOrMany::One(String::from("a")));
+
+    let res: OneOrMany<String> = eval(&lua, r#"{ "a", "b" }"#).unwrap();
+    assert_eq!(
+        res,
+        OneOrMany::Many(String::from("a"), vec![String::from("b")])
+    );
+}
+
+#[test]
+fn into_lua() {
+    let lua = Lua::new();
+    let globals = lua.globals();
+
+    let fields: Fields = vec![(String::from("number"), 1)].into_iter().collect();
+    globals
+        .set("config", StringOrStruct::<Fields>::Struct(fields))
+        .unwrap();
+    assert_eq!(eval::<i64>(&lua, "config.number").unwrap(), 1);
+
+    globals
+        .set(
+            "config",
+            StringOrStruct::<Fields>::String(String::from("text")),
+        )
+        .unwrap();
+    assert_eq!(eval::<String>(&lua, "config").unwrap(), "text");
+
+    globals
+        .set("config", SingleOrVec::Vec(vec![1, 2, 3]))
+        .unwrap();
+    let table: Table = eval(&lua, "config").unwrap();
+    assert_eq!(table.raw_len(), 3);
+
+    globals.set("config", OneOrMany::Many(1, vec![2])).unwrap();
+    assert_eq!(eval::<i64>(&lua, "config[2]").unwrap(), 2);
+}
+
+#[test]
+fn round_trip() {
+    let lua = Lua::new();
+
+    let value: StringOrStructOrVec<Fields, Vec<String>> =
+        StringOrStructOrVec::Vec(vec![String::from("a"), String::from("b")]);
+    let lua_value = mlua::IntoLua::into_lua(value.clone(), &lua).unwrap();
+    let res: StringOrStructOrVec<Fields, Vec<String>> =
+        mlua::FromLua::from_lua(lua_value, &lua).unwrap();
+    assert_eq!(res, value);
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use crate::common::Person;
+use serde_either::{match_either, OneOrMany, SingleOrVec, StringOrStruct, StringOrStructOrVec};
+
+fn person(first_name: &str, last_name: &str) -> Person {
+    Person {
+        first_name: String::from(first_name),
+        last_name: String::from(last_name),
+    }
+}
+
+#[test]
+fn string_or_struct() {
+    let values = vec![
+        StringOrStruct::String(String::from("Bob Smith")),
+        StringOrStruct::Struct(person("Uncle", "Jones")),
+    ];
+
+    let names: Vec<String> = values
+        .into_iter()
+        .map(|value| match_either!(value, s => s, p => p.to_string()))
+        .collect();
+
+    assert_eq!(names, vec!["Bob Smith", "Uncle Jones"]);
+}
+
+#[test]
+fn string_or_struct_by_reference() {
+    let value = StringOrStruct::Struct(person("Uncle", "Jones"));
+
+    let first_name = match_either!(&value, s => s.as_str(), p => p.first_name.as_str());
+
+    assert_eq!(first_name, "Uncle");
+    assert!(matches!(value, StringOrStruct::Struct(_)));
+}
+
+#[test]
+fn string_or_struct_or_vec() {
+    let value: StringOrStructOrVec<Person, Vec<Person>> =
+        StringOrStructOrVec::Vec(vec![person("Bob", "Smith"), person("Jane", "Doe")]);
+
+    let count = match_either!(&value, _ => 1, _ => 1, people => people.len());
+
+    assert_eq!(count, 2);
+}
+
+#[test]
+fn patterns() {
+    let value: StringOrStructOrVec<Person, Vec<Person>> =
+        StringOrStructOrVec::Struct(person("Bob", "Smith"));
+
+    let first_name = match_either!(
+        value,
+        s => s,
+        Person { first_name, .. } => first_name,
+        _ => String::new(),
+    );
+
+    assert_eq!(first_name, "Bob");
+}
+
+#[test]
+fn single_or_vec() {
+    let value = SingleOrVec::Vec(vec![1, 2, 3]);
+
+    let total: i32 = match_either!(value, n => n, v => v.into_iter().sum());
+
+    assert_eq!(total, 6);
+}
+
+#[test]
+fn one_or_many() {
+    let value = OneOrMany::Many(1, vec![2, 3]);
+
+    let last = match_either!(&value, n => *n, (_, rest) => *rest.last().unwrap());
+
+    assert_eq!(last, 3);
+}
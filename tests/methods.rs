@@ -0,0 +1,482 @@
+mod common;
+
+use crate::common::Person;
+use serde::Deserialize;
+use serde_either::{
+    AsStruct, AsVec, BoolOrStruct, FlattenExt, NormalizedEq, OneOrMany, SingleOrVec,
+    SingleOrVecOrNull, StringOrStruct, StringOrStructOrNull, StringOrStructOrVec,
+    StringOrStructOrVecOrMap, StringOrStructOrVecOrNull, StringOrVec,
+};
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashMap};
+use std::hash::Hash;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+mod string_or_struct {
+    use super::*;
+
+    mod to_canonical_string {
+        use super::*;
+
+        #[test]
+        fn string_value() {
+            let value = StringOrStruct::<Person>::String(String::from("Bob Smith"));
+
+            assert_eq!(value.to_canonical_string(), "Bob Smith");
+        }
+
+        #[test]
+        fn struct_value() {
+            let value = StringOrStruct::Struct(Person {
+                first_name: String::from("Bob"),
+                last_name: String::from("Smith"),
+            });
+
+            assert_eq!(value.to_canonical_string(), "Bob Smith");
+        }
+
+        #[test]
+        fn round_trips_through_from_str() {
+            let value = StringOrStruct::Struct(Person {
+                first_name: String::from("Uncle"),
+                last_name: String::from("Jones"),
+            });
+
+            let parsed: Person = value.to_canonical_string().parse().unwrap();
+
+            assert_eq!(StringOrStruct::Struct(parsed), value);
+        }
+    }
+
+    mod into_parsed {
+        use super::*;
+
+        #[test]
+        fn string_value() {
+            let value = StringOrStruct::<Person>::String(String::from("Bob Smith"));
+
+            assert_eq!(
+                value.into_parsed().unwrap(),
+                StringOrStruct::Struct(Person {
+                    first_name: String::from("Bob"),
+                    last_name: String::from("Smith"),
+                })
+            );
+        }
+
+        #[test]
+        fn unparseable_string() {
+            let value = StringOrStruct::<Person>::String(String::from("Bob"));
+
+            assert!(value.into_parsed().is_err());
+        }
+    }
+}
+
+mod single_or_vec {
+    use super::*;
+
+    #[test]
+    fn len() {
+        assert_eq!(SingleOrVec::Single(1).len(), 1);
+        assert_eq!(SingleOrVec::Vec(vec![1, 2]).len(), 2);
+        assert!(SingleOrVec::<u8>::Vec(vec![]).is_empty());
+    }
+
+    mod insert {
+        use super::*;
+
+        #[test]
+        fn promotes_single() {
+            let mut value = SingleOrVec::Single(1);
+
+            value.insert(0, 0);
+
+            assert_eq!(value, SingleOrVec::Vec(vec![0, 1]));
+        }
+
+        #[test]
+        fn into_empty_vec_demotes() {
+            let mut value = SingleOrVec::Vec(vec![]);
+
+            value.insert(0, 1);
+
+            assert_eq!(value, SingleOrVec::Single(1));
+        }
+
+        #[test]
+        #[should_panic]
+        fn out_of_bounds() {
+            let mut value = SingleOrVec::Single(1);
+
+            value.insert(2, 0);
+        }
+
+        #[test]
+        fn out_of_bounds_keeps_the_value() {
+            let mut value = SingleOrVec::Vec(vec![1, 2]);
+
+            assert!(catch_unwind(AssertUnwindSafe(|| value.insert(3, 0))).is_err());
+            assert_eq!(value, SingleOrVec::Vec(vec![1, 2]));
+        }
+    }
+
+    mod remove {
+        use super::*;
+
+        #[test]
+        fn demotes_to_single() {
+            let mut value = SingleOrVec::Vec(vec![1, 2]);
+
+            assert_eq!(value.remove(0), 1);
+            assert_eq!(value, SingleOrVec::Single(2));
+        }
+
+        #[test]
+        fn single_becomes_empty_vec() {
+            let mut value = SingleOrVec::Single(1);
+
+            assert_eq!(value.remove(0), 1);
+            assert_eq!(value, SingleOrVec::Vec(vec![]));
+        }
+
+        #[test]
+        fn out_of_bounds_keeps_the_value() {
+            let mut value = SingleOrVec::Single(1);
+
+            assert!(catch_unwind(AssertUnwindSafe(|| value.remove(1))).is_err());
+            assert_eq!(value, SingleOrVec::Single(1));
+        }
+    }
+
+    mod drain {
+        use super::*;
+
+        #[test]
+        fn range() {
+            let mut value = SingleOrVec::Vec(vec![1, 2, 3, 4]);
+
+            assert_eq!(value.drain(1..), vec![2, 3, 4]);
+            assert_eq!(value, SingleOrVec::Single(1));
+        }
+
+        #[test]
+        fn everything() {
+            let mut value = SingleOrVec::Single(1);
+
+            assert_eq!(value.drain(..), vec![1]);
+            assert_eq!(value, SingleOrVec::Vec(vec![]));
+        }
+
+        #[test]
+        fn out_of_bounds_keeps_the_value() {
+            let mut value = SingleOrVec::Vec(vec![1, 2]);
+
+            assert!(catch_unwind(AssertUnwindSafe(|| value.drain(1..3))).is_err());
+            assert_eq!(value, SingleOrVec::Vec(vec![1, 2]));
+        }
+    }
+
+    #[test]
+    fn as_cow_borrows_both_arms() {
+        let single = SingleOrVec::Single(1);
+        assert!(matches!(single.as_cow(), Cow::Borrowed(&[1])));
+
+        let vec = SingleOrVec::Vec(vec![1, 2]);
+        assert!(matches!(vec.as_cow(), Cow::Borrowed(&[1, 2])));
+
+        let empty = SingleOrVec::<i32>::Vec(vec![]);
+        assert!(empty.as_cow().is_empty());
+    }
+
+    #[test]
+    fn as_cow_into_owned() {
+        let single = SingleOrVec::Single(String::from("a"));
+        let owned: Vec<String> = single.as_cow().into_owned();
+
+        assert_eq!(owned, vec![String::from("a")]);
+        assert_eq!(single, SingleOrVec::Single(String::from("a")));
+    }
+}
+
+mod flatten_either {
+    use super::*;
+
+    #[test]
+    fn keeps_order_and_collects_strings() {
+        let values: Vec<StringOrStructOrVec<Person, Vec<Person>>> = serde_json::from_str(
+            r#"[
+                {"first_name": "Bob", "last_name": "Smith"},
+                "Anonymous",
+                [{"first_name": "Uncle", "last_name": "Jones"}, {"first_name": "Jane", "last_name": "Doe"}],
+                "Unknown"
+            ]"#,
+        )
+        .unwrap();
+
+        let (people, strings) = values.flatten_either();
+
+        let names: Vec<String> = people.iter().map(ToString::to_string).collect();
+        assert_eq!(names, vec!["Bob Smith", "Uncle Jones", "Jane Doe"]);
+        assert_eq!(strings, vec!["Anonymous", "Unknown"]);
+    }
+
+    #[test]
+    fn empty() {
+        let values: Vec<StringOrStructOrVec<Person, Vec<Person>>> = Vec::new();
+
+        let (people, strings) = values.flatten_either();
+
+        assert!(people.is_empty());
+        assert!(strings.is_empty());
+    }
+}
+
+mod eq_normalized {
+    use super::*;
+
+    fn bob() -> Person {
+        Person {
+            first_name: String::from("Bob"),
+            last_name: String::from("Smith"),
+        }
+    }
+
+    #[test]
+    fn string_equals_parsed_struct() {
+        let text = StringOrStruct::String(String::from("Bob Smith"));
+        let parsed = StringOrStruct::Struct(bob());
+
+        assert!(text.eq_normalized(&parsed));
+        assert!(parsed.eq_normalized(&text));
+        assert_ne!(text, parsed);
+    }
+
+    #[test]
+    fn string_differs_from_other_struct() {
+        let text = StringOrStruct::String(String::from("Uncle Jones"));
+
+        assert!(!text.eq_normalized(&StringOrStruct::Struct(bob())));
+    }
+
+    #[test]
+    fn unparsable_string() {
+        let text = StringOrStruct::String(String::from("Bob"));
+
+        assert!(!text.eq_normalized(&StringOrStruct::Struct(bob())));
+        assert!(text.eq_normalized(&StringOrStruct::String(String::from("Bob"))));
+    }
+
+    #[test]
+    fn single_equals_vec_of_one() {
+        let single = SingleOrVec::Single(1);
+
+        assert!(single.eq_normalized(&SingleOrVec::Vec(vec![1])));
+        assert!(!single.eq_normalized(&SingleOrVec::Vec(vec![1, 1])));
+        assert!(!single.eq_normalized(&SingleOrVec::Vec(vec![])));
+    }
+
+    #[test]
+    fn one_equals_many_without_rest() {
+        assert!(OneOrMany::One(1).eq_normalized(&OneOrMany::Many(1, vec![])));
+        assert!(!OneOrMany::One(1).eq_normalized(&OneOrMany::Many(1, vec![2])));
+    }
+
+    #[test]
+    fn wrapper() {
+        let from_json: SingleOrVec<Person> =
+            serde_json::from_str(r#"[{"first_name": "Bob", "last_name": "Smith"}]"#).unwrap();
+
+        assert_eq!(
+            NormalizedEq(from_json),
+            NormalizedEq(SingleOrVec::Single(bob()))
+        );
+        assert_eq!(
+            NormalizedEq(StringOrStruct::String(String::from("Bob Smith"))),
+            NormalizedEq(StringOrStruct::Struct(bob()))
+        );
+    }
+}
+
+mod traits {
+    use super::*;
+
+    fn assert_eq<T: Eq>() {}
+
+    fn assert_hash<T: Hash>() {}
+
+    #[test]
+    fn eq() {
+        assert_eq::<StringOrStruct<Person>>();
+        assert_eq::<StringOrStructOrVec<Person, Vec<Person>>>();
+        assert_eq::<SingleOrVec<Person>>();
+        assert_eq::<OneOrMany<Person>>();
+    }
+
+    #[test]
+    fn hash() {
+        assert_hash::<StringOrStruct<Person>>();
+        assert_hash::<StringOrStructOrVec<Person, Vec<Person>>>();
+        assert_hash::<SingleOrVec<Person>>();
+        assert_hash::<OneOrMany<Person>>();
+
+        let fragments: Vec<StringOrStruct<Person>> =
+            serde_json::from_str(r#"["a", "b", "a"]"#).unwrap();
+        let mut counts = HashMap::new();
+        for fragment in fragments {
+            *counts.entry(fragment).or_insert(0) += 1;
+        }
+
+        assert_eq!(counts[&StringOrStruct::String(String::from("a"))], 2);
+    }
+
+    #[test]
+    fn ord() {
+        let fragments: BTreeSet<StringOrStructOrVec<Person, Vec<Person>>> = serde_json::from_str(
+            r#"[[], {"first_name": "Bob", "last_name": "Smith"}, "b", "a", "b"]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            fragments.into_iter().collect::<Vec<_>>(),
+            [
+                StringOrStructOrVec::String(String::from("a")),
+                StringOrStructOrVec::String(String::from("b")),
+                StringOrStructOrVec::Struct(Person {
+                    first_name: String::from("Bob"),
+                    last_name: String::from("Smith"),
+                }),
+                StringOrStructOrVec::Vec(vec![]),
+            ]
+        );
+    }
+
+    #[derive(Deserialize, Default)]
+    struct Config {
+        #[serde(default)]
+        author: StringOrStruct<Vec<String>>,
+        #[serde(default)]
+        reviewers: SingleOrVec<Person>,
+        #[serde(default)]
+        owners: StringOrStructOrVec<Vec<String>, Vec<Person>>,
+    }
+
+    #[test]
+    fn default() {
+        let res: Config = serde_json::from_str("{}").unwrap();
+
+        assert_eq!(res.author, StringOrStruct::Struct(vec![]));
+        assert!(res.reviewers.is_empty());
+        assert_eq!(res.owners, StringOrStructOrVec::Struct(vec![]));
+        assert!(Config::default().reviewers.is_empty());
+    }
+}
+
+mod from {
+    use super::*;
+
+    fn bob() -> Person {
+        Person {
+            first_name: String::from("Bob"),
+            last_name: String::from("Smith"),
+        }
+    }
+
+    #[test]
+    fn string_or_struct() {
+        assert_eq!(
+            StringOrStruct::<Person>::from("Bob Smith"),
+            StringOrStruct::String(String::from("Bob Smith"))
+        );
+        assert_eq!(
+            StringOrStruct::from(AsStruct(bob())),
+            StringOrStruct::Struct(bob())
+        );
+
+        let text: StringOrStruct<String> = String::from("a").into();
+        let parsed: StringOrStruct<String> = AsStruct(String::from("a")).into();
+        assert_ne!(text, parsed);
+    }
+
+    #[test]
+    fn string_or_struct_or_vec() {
+        type People = StringOrStructOrVec<Person, Vec<Person>>;
+
+        assert_eq!(
+            People::from(String::from("Bob Smith")),
+            StringOrStructOrVec::String(String::from("Bob Smith"))
+        );
+        assert_eq!(
+            People::from(AsStruct(bob())),
+            StringOrStructOrVec::Struct(bob())
+        );
+        assert_eq!(
+            People::from(AsVec(vec![bob()])),
+            StringOrStructOrVec::Vec(vec![bob()])
+        );
+        assert_eq!(
+            People::from(StringOrStruct::Struct(bob())),
+            StringOrStructOrVec::Struct(bob())
+        );
+    }
+
+    #[test]
+    fn single_or_vec() {
+        assert_eq!(SingleOrVec::from(vec![1]), SingleOrVec::Vec(vec![1]));
+        assert_eq!(
+            SingleOrVec::from(OneOrMany::Many(1, vec![2])),
+            SingleOrVec::Vec(vec![1, 2])
+        );
+        assert_eq!(SingleOrVec::from(OneOrMany::One(1)), SingleOrVec::Single(1));
+    }
+
+    #[test]
+    fn null_variants() {
+        assert_eq!(
+            StringOrStructOrNull::<Person>::from("Bob Smith"),
+            StringOrStructOrNull::String(String::from("Bob Smith"))
+        );
+        assert_eq!(
+            StringOrStructOrNull::from(AsStruct(bob())),
+            StringOrStructOrNull::Struct(bob())
+        );
+        assert_eq!(
+            StringOrStructOrVecOrNull::<Person, _>::from(AsVec(vec![bob()])),
+            StringOrStructOrVecOrNull::Vec(vec![bob()])
+        );
+        assert_eq!(
+            SingleOrVecOrNull::from(vec![1]),
+            SingleOrVecOrNull::Vec(vec![1])
+        );
+    }
+
+    #[test]
+    fn toggle_and_sequence_variants() {
+        assert_eq!(
+            BoolOrStruct::<Person>::from(false),
+            BoolOrStruct::Bool(false)
+        );
+        assert_eq!(
+            BoolOrStruct::from(AsStruct(bob())),
+            BoolOrStruct::Struct(bob())
+        );
+        assert_eq!(
+            StringOrVec::<Vec<String>>::from("a"),
+            StringOrVec::String(String::from("a"))
+        );
+        assert_eq!(
+            StringOrVec::from(AsVec(vec![String::from("a")])),
+            StringOrVec::Vec(vec![String::from("a")])
+        );
+
+        type Jobs = StringOrStructOrVecOrMap<Person, Vec<Person>, HashMap<String, Person>>;
+        assert_eq!(
+            Jobs::from(String::from("a")),
+            StringOrStructOrVecOrMap::String(String::from("a"))
+        );
+        assert_eq!(
+            Jobs::from(AsStruct(bob())),
+            StringOrStructOrVecOrMap::Struct(bob())
+        );
+    }
+}
@@ -0,0 +1,20 @@
+#![cfg(feature = "napi")]
+
+// there is no Node runtime to run conversions against, so this only checks,
+// at compile time, that the enums can cross the boundary wherever their arms
+// can
+
+use napi::bindgen_prelude::{FromNapiValue, Object, ToNapiValue};
+use serde_either::{OneOrMany, SingleOrVec, StringOrStruct, StringOrStructOrVec};
+
+fn assert_impl<T: FromNapiValue + ToNapiValue>() {}
+
+const _: fn() = || {
+    assert_impl::<StringOrStruct<Object>>();
+    assert_impl::<StringOrStruct<u32>>();
+    assert_impl::<StringOrStructOrVec<Object, Vec<Object>>>();
+    assert_impl::<StringOrStructOrVec<bool, Vec<String>>>();
+    assert_impl::<SingleOrVec<String>>();
+    assert_impl::<SingleOrVec<StringOrStruct<Object>>>();
+    assert_impl::<OneOrMany<f64>>();
+};
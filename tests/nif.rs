@@ -0,0 +1,24 @@
+#![cfg(feature = "rustler")]
+
+// NIFs only run inside the BEAM, so this only checks, at compile time, that
+// the enums can cross the boundary wherever their arms can
+
+use rustler::{Decoder, Encoder, Term};
+use serde_either::{OneOrMany, SingleOrVec, StringOrStruct, StringOrStructOrVec};
+use std::collections::HashMap;
+
+fn assert_decoder<'a, T: Decoder<'a>>() {}
+
+fn assert_encoder<T: Encoder>() {}
+
+const _: fn() = || {
+    assert_decoder::<StringOrStruct<HashMap<String, i64>>>();
+    assert_encoder::<StringOrStruct<HashMap<String, i64>>>();
+    assert_decoder::<StringOrStruct<Term<'static>>>();
+    assert_decoder::<StringOrStructOrVec<HashMap<String, String>, Vec<String>>>();
+    assert_encoder::<StringOrStructOrVec<HashMap<String, String>, Vec<String>>>();
+    assert_decoder::<SingleOrVec<i64>>();
+    assert_encoder::<SingleOrVec<i64>>();
+    assert_decoder::<OneOrMany<String>>();
+    assert_encoder::<OneOrMany<String>>();
+};
@@ -0,0 +1,299 @@
+mod common;
+
+use crate::common::SimpleStruct;
+use serde::{Deserialize, Serialize};
+use serde_either::{
+    SingleOrVec, SingleOrVecOrNull, StringOrStruct, StringOrStructOrNull, StringOrStructOrVec,
+    StringOrStructOrVecOrNull, UnitOrStruct,
+};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Patch {
+    #[serde(
+        default,
+        deserialize_with = "StringOrStructOrNull::deserialize_some",
+        skip_serializing_if = "Option::is_none"
+    )]
+    field: Option<StringOrStructOrNull<SimpleStruct>>,
+}
+
+#[test]
+fn absent_field() {
+    let res: Patch = serde_json::from_str("{}").unwrap();
+
+    assert_eq!(res.field, None);
+    assert_eq!(serde_json::to_string(&res).unwrap(), "{}");
+}
+
+#[test]
+fn explicit_null() {
+    let res: Patch = serde_json::from_str(r#"{"field": null}"#).unwrap();
+
+    assert_eq!(res.field, Some(StringOrStructOrNull::Null));
+    assert_eq!(serde_json::to_string(&res).unwrap(), r#"{"field":null}"#);
+}
+
+#[test]
+fn yaml_null() {
+    let res: Patch = serde_yaml::from_str("field: ~").unwrap();
+
+    assert_eq!(res.field, Some(StringOrStructOrNull::Null));
+}
+
+#[test]
+fn strings_and_structs() {
+    let res: StringOrStructOrNull<SimpleStruct> = serde_json::from_str(r#""a""#).unwrap();
+    assert_eq!(res, StringOrStructOrNull::String(String::from("a")));
+
+    let res: StringOrStructOrNull<SimpleStruct> =
+        serde_json::from_str(r#"{"number": 1, "text": "a"}"#).unwrap();
+    assert_eq!(
+        res.into_option(),
+        Some(StringOrStruct::Struct(SimpleStruct {
+            number: 1,
+            text: String::from("a")
+        }))
+    );
+}
+
+#[test]
+fn rejects_other_types() {
+    let err = serde_json::from_str::<StringOrStructOrNull<SimpleStruct>>("true").unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "invalid type: boolean `true`, expected String or Struct"
+    );
+}
+
+#[test]
+fn round_trip() {
+    for json in &["null", r#""a""#, r#"{"number":1,"text":"a"}"#] {
+        let res: StringOrStructOrNull<SimpleStruct> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+    }
+}
+
+mod unit_or_struct {
+    use super::*;
+
+    #[derive(Deserialize, Debug)]
+    struct Plugins {
+        lint: UnitOrStruct<SimpleStruct>,
+        format: UnitOrStruct<SimpleStruct>,
+    }
+
+    #[test]
+    fn null_and_structs() {
+        let res: Plugins =
+            serde_yaml::from_str("lint: null\nformat:\n  number: 1\n  text: a\n").unwrap();
+
+        assert_eq!(res.lint, UnitOrStruct::Unit);
+        assert_eq!(
+            res.format.as_struct(),
+            Some(&SimpleStruct {
+                number: 1,
+                text: String::from("a")
+            })
+        );
+    }
+
+    #[test]
+    fn empty_maps() {
+        let res: UnitOrStruct<BTreeMap<String, u8>> = serde_json::from_str("{}").unwrap();
+        assert_eq!(res, UnitOrStruct::Struct(BTreeMap::new()));
+
+        let res: UnitOrStruct<BTreeMap<String, u8>, true> = serde_json::from_str("{}").unwrap();
+        assert_eq!(res, UnitOrStruct::Unit);
+    }
+
+    #[test]
+    fn unwrap_or_default() {
+        let res: UnitOrStruct<BTreeMap<String, u8>> = serde_json::from_str("null").unwrap();
+
+        assert!(res.unwrap_or_default().is_empty());
+    }
+
+    #[test]
+    fn rejects_other_types() {
+        let err = serde_json::from_str::<UnitOrStruct<SimpleStruct>>("true").unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "invalid type: boolean `true`, expected null or a map"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        for json in &["null", r#"{"number":1,"text":"a"}"#] {
+            let res: UnitOrStruct<SimpleStruct> = serde_json::from_str(json).unwrap();
+
+            assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+        }
+    }
+}
+
+mod single_or_vec_or_null {
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    struct Patch {
+        #[serde(
+            default,
+            deserialize_with = "SingleOrVecOrNull::deserialize_some",
+            skip_serializing_if = "Option::is_none"
+        )]
+        tags: Option<SingleOrVecOrNull<String>>,
+    }
+
+    #[test]
+    fn null_is_not_empty() {
+        let null: Patch = serde_json::from_str(r#"{"tags": null}"#).unwrap();
+        let empty: Patch = serde_json::from_str(r#"{"tags": []}"#).unwrap();
+        let absent: Patch = serde_json::from_str("{}").unwrap();
+
+        assert_eq!(null.tags, Some(SingleOrVecOrNull::Null));
+        assert_eq!(empty.tags, Some(SingleOrVecOrNull::Vec(vec![])));
+        assert_eq!(absent.tags, None);
+    }
+
+    #[test]
+    fn singles_and_vecs() {
+        let res: SingleOrVecOrNull<SimpleStruct> =
+            serde_json::from_str(r#"{"number": 1, "text": "a"}"#).unwrap();
+        assert_eq!(
+            res.into_option(),
+            Some(SingleOrVec::Single(SimpleStruct {
+                number: 1,
+                text: String::from("a")
+            }))
+        );
+
+        let res: SingleOrVecOrNull<u8> = serde_yaml::from_str("[1, 2]").unwrap();
+        assert_eq!(res.into_vec(), vec![1, 2]);
+
+        let res: SingleOrVecOrNull<u8> = serde_yaml::from_str("~").unwrap();
+        assert!(res.into_vec().is_empty());
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Task {
+        #[serde(default, serialize_with = "SingleOrVecOrNull::serialize_null_as_empty")]
+        tags: SingleOrVecOrNull<String>,
+        #[serde(default, skip_serializing_if = "SingleOrVecOrNull::is_null")]
+        owners: SingleOrVecOrNull<String>,
+        #[serde(default)]
+        labels: SingleOrVecOrNull<String>,
+    }
+
+    #[test]
+    fn null_as_empty() {
+        let res: Task = serde_json::from_str(r#"{"tags": null}"#).unwrap();
+
+        for field in &[&res.tags, &res.owners, &res.labels] {
+            assert!(field.is_null());
+            assert!(field.is_empty());
+            assert_eq!(field.iter().next(), None);
+        }
+        assert_eq!(
+            serde_json::to_string(&res).unwrap(),
+            r#"{"tags":[],"labels":null}"#
+        );
+    }
+
+    #[test]
+    fn elements() {
+        let res: SingleOrVecOrNull<u8> = serde_yaml::from_str("1").unwrap();
+        assert_eq!(res.as_slice(), [1]);
+
+        let res: SingleOrVecOrNull<u8> = serde_yaml::from_str("[1, 2]").unwrap();
+        assert_eq!(res.len(), 2);
+        assert_eq!((&res).into_iter().sum::<u8>(), 3);
+
+        let res = Task {
+            tags: SingleOrVecOrNull::Single(String::from("a")),
+            owners: SingleOrVecOrNull::Vec(vec![]),
+            labels: SingleOrVecOrNull::Null,
+        };
+        assert_eq!(
+            serde_json::to_string(&res).unwrap(),
+            r#"{"tags":"a","owners":[],"labels":null}"#
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_elements() {
+        let res = serde_json::from_str::<SingleOrVecOrNull<u8>>(r#"["a"]"#);
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn round_trip() {
+        for json in &[
+            r#"{"tags":null}"#,
+            r#"{"tags":[]}"#,
+            r#"{"tags":"a"}"#,
+            "{}",
+        ] {
+            let res: Patch = serde_json::from_str(json).unwrap();
+
+            assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+        }
+    }
+}
+
+mod string_or_struct_or_vec_or_null {
+    use super::*;
+
+    type Schema = StringOrStructOrVecOrNull<SimpleStruct, Vec<SimpleStruct>>;
+
+    #[test]
+    fn every_arm() {
+        let res: Vec<Schema> =
+            serde_json::from_str(r##"["#/components/Pet", {"number": 1, "text": "a"}, [], null]"##)
+                .unwrap();
+
+        assert_eq!(
+            res[0],
+            StringOrStructOrVecOrNull::String(String::from("#/components/Pet"))
+        );
+        assert!(matches!(res[1], StringOrStructOrVecOrNull::Struct(_)));
+        assert_eq!(res[2], StringOrStructOrVecOrNull::Vec(vec![]));
+        assert!(res[3].is_null());
+    }
+
+    #[test]
+    fn into_option() {
+        let res: Schema = serde_yaml::from_str("text").unwrap();
+        assert_eq!(
+            res.into_option(),
+            Some(StringOrStructOrVec::String(String::from("text")))
+        );
+
+        let res: Schema = serde_yaml::from_str("null").unwrap();
+        assert_eq!(res.into_option(), None);
+    }
+
+    #[test]
+    fn rejects_other_types() {
+        let err = serde_json::from_str::<Schema>("true").unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "invalid type: boolean `true`, expected String, Struct or Vec"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        for json in &[r#""a""#, r#"{"number":1,"text":"a"}"#, "[]", "null"] {
+            let res: Schema = serde_json::from_str(json).unwrap();
+
+            assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+        }
+    }
+}
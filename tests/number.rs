@@ -0,0 +1,281 @@
+mod common;
+
+use crate::common::SimpleStruct;
+use serde::Deserialize;
+use serde_either::{Number, NumberOrStruct, NumericStringOrNumber, StringOrNumber};
+
+#[test]
+fn quoted_number_stays_a_string() {
+    let res: StringOrNumber = serde_json::from_str(r#""42""#).unwrap();
+
+    assert_eq!(res, StringOrNumber::String(String::from("42")));
+    assert_eq!(res.as_str(), Some("42"));
+}
+
+#[test]
+fn integers() {
+    let res: StringOrNumber = serde_json::from_str("42").unwrap();
+    assert_eq!(res, StringOrNumber::Number(Number::U64(42)));
+
+    let res: StringOrNumber = serde_json::from_str("-42").unwrap();
+    assert_eq!(res, StringOrNumber::Number(Number::I64(-42)));
+}
+
+#[test]
+fn non_negative_integers_are_unsigned_in_every_format() {
+    // yaml hands positive integers over as i64
+    let res: StringOrNumber = serde_yaml::from_str("42").unwrap();
+    assert_eq!(res, StringOrNumber::Number(Number::U64(42)));
+
+    let res: StringOrNumber = toml::from_str::<Wrapper>("value = 42").unwrap().value;
+    assert_eq!(res, StringOrNumber::Number(Number::U64(42)));
+}
+
+#[test]
+fn floats() {
+    let res: StringOrNumber = serde_json::from_str("1.5").unwrap();
+
+    assert_eq!(res, StringOrNumber::Number(Number::F64(1.5)));
+    assert_eq!(res.as_number().map(Number::as_f64), Some(1.5));
+}
+
+#[test]
+fn arbitrary_precision() {
+    let res: StringOrNumber =
+        serde_json::from_str(r#"{"$serde_json::private::Number": "18446744073709551615"}"#)
+            .unwrap();
+
+    assert_eq!(res, StringOrNumber::Number(Number::U64(u64::MAX)));
+}
+
+#[test]
+fn arbitrary_precision_integers_beyond_64_bits_are_errors() {
+    for text in &["18446744073709551616", "-9223372036854775809"] {
+        let json = format!(r#"{{"$serde_json::private::Number": "{}"}}"#, text);
+
+        let err = serde_json::from_str::<StringOrNumber>(&json).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with(&format!("{} does not fit in 64 bits", text)),
+            "{}",
+            err
+        );
+    }
+
+    // floats are expected to be approximated
+    let res: StringOrNumber =
+        serde_json::from_str(r#"{"$serde_json::private::Number": "1e20"}"#).unwrap();
+    assert_eq!(res, StringOrNumber::Number(Number::F64(1e20)));
+}
+
+#[test]
+fn rejects_other_types() {
+    let err = serde_json::from_str::<StringOrNumber>("true").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid type: boolean `true`, expected a string or a number at line 1 column 4"
+    );
+
+    assert!(serde_json::from_str::<StringOrNumber>(r#"{"a": 1}"#).is_err());
+    assert!(serde_json::from_str::<StringOrNumber>("[1]").is_err());
+}
+
+#[test]
+fn number_conversions() {
+    assert_eq!(Number::U64(1).as_i64(), Some(1));
+    assert_eq!(Number::U64(u64::MAX).as_i64(), None);
+    assert_eq!(Number::I64(-1).as_u64(), None);
+    assert_eq!(Number::F64(1.0).as_u64(), None);
+}
+
+#[test]
+fn lenient_reads() {
+    let values: Vec<StringOrNumber> =
+        serde_json::from_str(r#"[42, -42, 42.0, 42.5, "42", " -42 ", "42.0", "4.25e1", "n/a"]"#)
+            .unwrap();
+
+    let ints: Vec<Option<i64>> = values.iter().map(StringOrNumber::as_i64).collect();
+    assert_eq!(
+        ints,
+        vec![
+            Some(42),
+            Some(-42),
+            Some(42),
+            None,
+            Some(42),
+            Some(-42),
+            Some(42),
+            None,
+            None
+        ]
+    );
+
+    let floats: Vec<Option<f64>> = values.iter().map(StringOrNumber::as_f64).collect();
+    assert_eq!(
+        floats,
+        vec![
+            Some(42.0),
+            Some(-42.0),
+            Some(42.0),
+            Some(42.5),
+            Some(42.0),
+            Some(-42.0),
+            Some(42.0),
+            Some(42.5),
+            None
+        ]
+    );
+
+    // the original representation is kept
+    assert_eq!(values[2].as_number(), Some(&Number::F64(42.0)));
+    assert_eq!(values[4].as_str(), Some("42"));
+}
+
+#[test]
+fn lenient_reads_check_ranges() {
+    let negative = StringOrNumber::Number(Number::I64(-1));
+    assert_eq!(negative.as_u64(), None);
+
+    let big = StringOrNumber::String(u64::MAX.to_string());
+    assert_eq!(big.as_u64(), Some(u64::MAX));
+    assert_eq!(big.as_i64(), None);
+
+    let not_finite = StringOrNumber::String(String::from("NaN"));
+    assert_eq!(not_finite.as_f64(), None);
+    assert_eq!(not_finite.to_number(), None);
+}
+
+#[test]
+fn negative_zero_is_unsigned() {
+    let res = StringOrNumber::String(String::from("-0"));
+
+    assert_eq!(res.to_number(), Some(Number::U64(0)));
+    assert_eq!(res.as_u64(), Some(0));
+}
+
+#[test]
+fn round_trip() {
+    for json in &[r#""42""#, "42", "-42", "1.5"] {
+        let res: StringOrNumber = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+        assert_eq!(res.to_string(), json.trim_matches('"'));
+    }
+}
+
+#[derive(Deserialize)]
+struct Wrapper {
+    value: StringOrNumber,
+}
+
+mod number_or_struct {
+    use super::*;
+
+    #[test]
+    fn numbers() {
+        let res: NumberOrStruct<SimpleStruct> = serde_json::from_str("3").unwrap();
+        assert_eq!(res, NumberOrStruct::Number(Number::U64(3)));
+
+        let res: NumberOrStruct<SimpleStruct> = serde_json::from_str("0.5").unwrap();
+        assert_eq!(res.as_number(), Some(&Number::F64(0.5)));
+    }
+
+    #[test]
+    fn structs() {
+        let res: NumberOrStruct<SimpleStruct> =
+            serde_json::from_str(r#"{"number": 3, "text": "exp"}"#).unwrap();
+
+        assert_eq!(
+            res,
+            NumberOrStruct::Struct(SimpleStruct {
+                number: 3,
+                text: String::from("exp")
+            })
+        );
+    }
+
+    #[test]
+    fn arbitrary_precision() {
+        let res: NumberOrStruct<SimpleStruct> =
+            serde_json::from_str(r#"{"$serde_json::private::Number": "-3"}"#).unwrap();
+
+        assert_eq!(res, NumberOrStruct::Number(Number::I64(-3)));
+    }
+
+    #[test]
+    fn rejects_other_types() {
+        let err = serde_json::from_str::<NumberOrStruct<SimpleStruct>>(r#""3""#).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid type: string \"3\", expected a number or a map"
+        );
+
+        assert!(serde_json::from_str::<NumberOrStruct<SimpleStruct>>("true").is_err());
+        assert!(serde_json::from_str::<NumberOrStruct<Vec<u8>>>("[3]").is_err());
+    }
+
+    #[test]
+    fn round_trip() {
+        for json in &["3", r#"{"number":3,"text":"exp"}"#] {
+            let res: NumberOrStruct<SimpleStruct> = serde_json::from_str(json).unwrap();
+
+            assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+        }
+    }
+}
+
+mod numeric_string_or_number {
+    use super::*;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn strings_and_numbers_read_alike() {
+        let res: Vec<NumericStringOrNumber> =
+            serde_json::from_str(r#"["1234", 1234, 1234.0, " 1234 "]"#).unwrap();
+
+        for value in &res {
+            assert_eq!(value.as_i64(), Some(1234));
+            assert_eq!(value.as_u64(), Some(1234));
+            assert_eq!(value.as_f64(), Some(1234.0));
+        }
+        assert!(res[0].is_string());
+        assert_eq!(res[2], NumericStringOrNumber::Number(Number::F64(1234.0)));
+    }
+
+    #[test]
+    fn fractional_and_negative_numbers() {
+        let res: NumericStringOrNumber = serde_json::from_str(r#""-1.5""#).unwrap();
+
+        assert_eq!(res.as_i64(), None);
+        assert_eq!(res.as_f64(), Some(-1.5));
+        assert_eq!(res.to_number(), Some(Number::F64(-1.5)));
+    }
+
+    #[test]
+    fn rejects_non_numeric_strings() {
+        let err = serde_json::from_str::<NumericStringOrNumber>(r#""n/a""#).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid value: string \"n/a\", expected a number or a numeric string"
+        );
+
+        assert!(serde_json::from_str::<NumericStringOrNumber>("true").is_err());
+        assert!(serde_json::from_str::<NumericStringOrNumber>(r#""inf""#).is_err());
+    }
+
+    #[test]
+    fn try_from_string_or_number() {
+        let res = NumericStringOrNumber::try_from(StringOrNumber::String(String::from("a")));
+
+        assert_eq!(res, Err(String::from("a")));
+    }
+
+    #[test]
+    fn round_trip() {
+        for json in &[r#""1234""#, r#""1234.50""#, "1234", "1234.5"] {
+            let res: NumericStringOrNumber = serde_json::from_str(json).unwrap();
+
+            assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+        }
+    }
+}
@@ -0,0 +1,172 @@
+mod common;
+
+use crate::common::{Person, SimpleStruct};
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde_either::{AnyOf, OneOf, StringOrStruct};
+use std::fmt;
+
+type Shapes = OneOf<(bool, i64, String, Vec<SimpleStruct>, SimpleStruct)>;
+
+#[test]
+fn dispatches_on_kind() {
+    let res: Shapes = serde_json::from_str("true").unwrap();
+    assert_eq!(res.index(), 0);
+
+    let res: Shapes = serde_json::from_str("-3").unwrap();
+    assert_eq!(res.index(), 1);
+
+    let res: Shapes = serde_json::from_str(r#""text""#).unwrap();
+    assert_eq!(res.index(), 2);
+
+    let res: Shapes = serde_json::from_str(r#"[{"number": 1, "text": "a"}]"#).unwrap();
+    assert_eq!(res.index(), 3);
+
+    let res: Shapes = serde_json::from_str(r#"{"number": 1, "text": "a"}"#).unwrap();
+    assert_eq!(
+        res.into_options().4,
+        Some(SimpleStruct {
+            number: 1,
+            text: String::from("a")
+        })
+    );
+}
+
+#[test]
+fn first_matching_struct_wins_when_it_deserializes() {
+    let res: OneOf<(SimpleStruct, Person)> =
+        serde_json::from_str(r#"{"first_name": "Bob", "last_name": "Smith"}"#).unwrap();
+
+    assert_eq!(res.index(), 1);
+}
+
+#[test]
+fn any_shaped_alternatives_are_tried() {
+    let res: OneOf<(u8, StringOrStruct<SimpleStruct>)> = serde_json::from_str(r#""text""#).unwrap();
+
+    assert_eq!(
+        res.into_options().1,
+        Some(StringOrStruct::String(String::from("text")))
+    );
+}
+
+#[test]
+fn options_accept_null() {
+    let res: OneOf<(String, Option<u8>)> = serde_json::from_str("null").unwrap();
+
+    assert_eq!(res.into_options(), (None, Some(None)));
+}
+
+#[test]
+fn no_matching_alternative() {
+    let res: serde_json::Result<OneOf<(bool, String)>> = serde_json::from_str("[1]");
+
+    let message = res.unwrap_err().to_string();
+    assert!(message.contains("one of 2 alternatives"), "{}", message);
+}
+
+#[test]
+fn no_matching_alternative_keeps_the_last_error() {
+    let res: serde_json::Result<OneOf<(bool, SimpleStruct)>> =
+        serde_json::from_str(r#"{"number": 1}"#);
+
+    let message = res.unwrap_err().to_string();
+    assert!(message.contains("one of 2 alternatives"), "{}", message);
+    assert!(message.contains("missing field `text`"), "{}", message);
+}
+
+#[test]
+fn serialize() {
+    let value: OneOf<(bool, String)> =
+        OneOf::from_options((None, Some(String::from("a")))).unwrap();
+
+    assert_eq!(serde_json::to_string(&value).unwrap(), r#""a""#);
+}
+
+#[test]
+fn from_options_requires_exactly_one_value() {
+    assert!(OneOf::<(bool, String)>::from_options((None, None)).is_none());
+    assert!(OneOf::<(bool, String)>::from_options((Some(true), Some(String::new()))).is_none());
+    assert_eq!(
+        OneOf::<(bool, String)>::from_options((Some(true), None)).map(|value| value.index()),
+        Some(0)
+    );
+}
+
+// asks for an integer, but also parses strings
+#[derive(Debug, PartialEq)]
+struct Port(u16);
+
+impl<'de> Deserialize<'de> for Port {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct PortVisitor;
+
+        impl<'de> Visitor<'de> for PortVisitor {
+            type Value = Port;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a port")
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Port, E> {
+                Ok(Port(v as u16))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Port, E> {
+                v.parse().map(Port).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_u16(PortVisitor)
+    }
+}
+
+mod any_of {
+    use super::*;
+
+    #[test]
+    fn first_success_wins() {
+        let res: AnyOf<(SimpleStruct, Person)> =
+            serde_json::from_str(r#"{"first_name": "Bob", "last_name": "Smith"}"#).unwrap();
+        assert_eq!(res.index(), 1);
+
+        let res: AnyOf<(u8, u16, String)> = serde_json::from_str("256").unwrap();
+        assert_eq!(res.into_options(), (None, Some(256), None));
+    }
+
+    #[test]
+    fn alternatives_are_not_skipped_by_shape() {
+        let res: AnyOf<(Port, String)> = serde_json::from_str(r#""8080""#).unwrap();
+        assert_eq!(res.into_options(), (Some(Port(8080)), None));
+
+        // OneOf only hands integers to Port
+        let res: OneOf<(Port, String)> = serde_json::from_str(r#""8080""#).unwrap();
+        assert_eq!(res.into_options(), (None, Some(String::from("8080"))));
+    }
+
+    #[test]
+    fn no_alternative_succeeds() {
+        let res: serde_json::Result<AnyOf<(bool, String)>> = serde_json::from_str("[1]");
+
+        let message = res.unwrap_err().to_string();
+        assert!(message.contains("any of 2 alternatives"), "{}", message);
+        assert!(message.contains("expected a string"), "{}", message);
+    }
+
+    #[test]
+    fn serialize() {
+        let value: AnyOf<(bool, String)> = AnyOf::from_options((Some(true), None)).unwrap();
+
+        assert_eq!(serde_json::to_string(&value).unwrap(), "true");
+    }
+
+    #[test]
+    fn from_options_requires_exactly_one_value() {
+        assert!(AnyOf::<(bool, String)>::from_options((None, None)).is_none());
+        assert!(AnyOf::<(bool, String)>::from_options((Some(true), Some(String::new()))).is_none());
+        assert_eq!(
+            AnyOf::<(bool, String)>::from_options((None, Some(String::new())))
+                .map(|value| value.index()),
+            Some(1)
+        );
+    }
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "sea-orm")]
+
+mod common;
+
+use crate::common::SimpleStruct;
+use sea_orm::sea_query::{ColumnType, Nullable, ValueType};
+use sea_orm::{ActiveValue, IntoActiveValue, ProxyRow, QueryResult, TryGetable, Value};
+use serde_either::{SingleOrVec, StringOrStruct, StringOrStructOrVec};
+use std::collections::BTreeMap;
+
+fn row(column: &str, value: Value) -> QueryResult {
+    let mut values = BTreeMap::new();
+    values.insert(column.to_owned(), value);
+    QueryResult::from(ProxyRow { values })
+}
+
+fn simple() -> SimpleStruct {
+    SimpleStruct {
+        number: 1,
+        text: String::from("a"),
+    }
+}
+
+#[test]
+fn into_value() {
+    let value: Value = StringOrStruct::Struct(simple()).into();
+
+    assert_eq!(
+        value,
+        Value::Json(Some(Box::new(
+            serde_json::json!({"number": 1, "text": "a"})
+        )))
+    );
+}
+
+#[test]
+fn try_get() {
+    let res = row(
+        "data",
+        Value::Json(Some(Box::new(serde_json::json!("text")))),
+    );
+
+    let value =
+        StringOrStructOrVec::<SimpleStruct, Vec<SimpleStruct>>::try_get(&res, "", "data").unwrap();
+
+    assert_eq!(value, StringOrStructOrVec::String(String::from("text")));
+}
+
+#[test]
+fn try_get_invalid() {
+    let res = row("data", Value::Json(Some(Box::new(serde_json::json!(true)))));
+
+    assert!(StringOrStruct::<SimpleStruct>::try_get(&res, "", "data").is_err());
+}
+
+#[test]
+fn value_type() {
+    let json = Value::Json(Some(Box::new(serde_json::json!([1, 2]))));
+
+    assert_eq!(
+        <SingleOrVec<u32> as ValueType>::try_from(json).unwrap(),
+        SingleOrVec::Vec(vec![1, 2])
+    );
+    assert!(<SingleOrVec<u32> as ValueType>::try_from(Value::Json(None)).is_err());
+    assert_eq!(SingleOrVec::<u32>::column_type(), ColumnType::Json);
+    assert_eq!(SingleOrVec::<u32>::null(), Value::Json(None));
+}
+
+#[test]
+fn active_value() {
+    let value = StringOrStruct::Struct(simple()).into_active_value();
+
+    assert_eq!(value, ActiveValue::Set(StringOrStruct::Struct(simple())));
+}
@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+use serde_either::PathOrStruct;
+use std::path::PathBuf;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Entry {
+    path: String,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct Config {
+    entry: PathOrStruct<Entry>,
+}
+
+#[test]
+fn path_string() {
+    let res: Config = serde_yaml::from_str("entry: src/main.ts").unwrap();
+
+    assert_eq!(res.entry, PathOrStruct::Path(PathBuf::from("src/main.ts")));
+}
+
+#[test]
+fn structured() {
+    let res: Config = serde_yaml::from_str("entry: { path: src/main.ts, name: app }").unwrap();
+
+    assert_eq!(
+        res.entry.as_struct(),
+        Some(&Entry {
+            path: String::from("src/main.ts"),
+            name: String::from("app"),
+        })
+    );
+}
+
+#[test]
+fn rejects_other_types() {
+    let err = serde_json::from_str::<PathOrStruct<Entry>>("1").unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "invalid type: integer `1`, expected String or Struct"
+    );
+}
+
+#[test]
+fn round_trip() {
+    for json in &[r#""src/main.ts""#, r#"{"path":"src/main.ts","name":"app"}"#] {
+        let res: PathOrStruct<Entry> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+    }
+}
+
+#[cfg(feature = "camino")]
+#[test]
+fn utf8_paths() {
+    let res: serde_either::Utf8PathOrStruct<Entry> =
+        serde_json::from_str(r#""src/main.ts""#).unwrap();
+
+    assert_eq!(res.as_path().and_then(|path| path.extension()), Some("ts"));
+}
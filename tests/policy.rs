@@ -0,0 +1,207 @@
+mod common;
+
+use crate::common::{Person, SimpleStruct};
+use serde::de::DeserializeSeed;
+use serde_either::{DispatchPolicy, Kind, SingleOrVec, StringOrStruct, StringOrStructOrVec};
+
+fn from_str_with<'a, T>(policy: &DispatchPolicy, json: &'a str) -> serde_json::Result<T>
+where
+    T: serde_either::DeserializeWithPolicy<'a>,
+{
+    policy
+        .seed::<T>()
+        .deserialize(&mut serde_json::Deserializer::from_str(json))
+}
+
+mod default_policy {
+    use super::*;
+
+    #[test]
+    fn behaves_like_deserialize() {
+        let policy = DispatchPolicy::default();
+
+        let res: StringOrStructOrVec<SimpleStruct, Vec<SimpleStruct>> =
+            from_str_with(&policy, r#"[{"number": 1, "text": "a"}]"#).unwrap();
+
+        assert_eq!(
+            res,
+            StringOrStructOrVec::Vec(vec![SimpleStruct {
+                number: 1,
+                text: String::from("a")
+            }])
+        );
+    }
+
+    #[test]
+    fn rejects_numbers() {
+        let policy = DispatchPolicy::default();
+
+        let res: serde_json::Result<StringOrStruct<SimpleStruct>> = from_str_with(&policy, "18");
+
+        assert!(res.is_err());
+    }
+}
+
+mod deny {
+    use super::*;
+
+    #[test]
+    fn string() {
+        let policy = DispatchPolicy::new().deny(Kind::String);
+
+        let res: serde_json::Result<StringOrStructOrVec<SimpleStruct, Vec<SimpleStruct>>> =
+            from_str_with(&policy, r#""some string""#);
+
+        let message = res.unwrap_err().to_string();
+        assert!(message.contains("expected Struct or Vec"), "{}", message);
+    }
+
+    #[test]
+    fn vec_on_single_or_vec() {
+        let policy = DispatchPolicy::new().deny(Kind::Vec);
+
+        let res: serde_json::Result<SingleOrVec<u8>> = from_str_with(&policy, "[1, 2]");
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn string_on_single_or_vec() {
+        // strings land in the Single arm, which is of the Struct kind
+        let policy = DispatchPolicy::new().deny(Kind::String);
+        let res: SingleOrVec<String> = from_str_with(&policy, r#""a""#).unwrap();
+        assert_eq!(res, SingleOrVec::Single(String::from("a")));
+
+        let policy = DispatchPolicy::new().deny(Kind::Struct);
+        let res: serde_json::Result<SingleOrVec<String>> = from_str_with(&policy, r#""a""#);
+        let message = res.unwrap_err().to_string();
+        assert!(message.contains("expected Vec"), "{}", message);
+    }
+
+    #[test]
+    fn seq_on_string_or_struct() {
+        // without a Vec arm, sequences land in the Struct arm
+        let policy = DispatchPolicy::new().deny(Kind::Vec);
+        let res: StringOrStruct<Vec<u32>> = from_str_with(&policy, "[1, 2]").unwrap();
+        assert_eq!(res, StringOrStruct::Struct(vec![1, 2]));
+
+        let policy = DispatchPolicy::new().deny(Kind::Struct);
+        let res: serde_json::Result<StringOrStruct<Vec<u32>>> = from_str_with(&policy, "[1, 2]");
+        let message = res.unwrap_err().to_string();
+        assert!(message.contains("expected String"), "{}", message);
+    }
+
+    #[test]
+    fn allow_reverts_deny() {
+        let policy = DispatchPolicy::new().deny(Kind::String).allow(Kind::String);
+
+        let res: StringOrStruct<SimpleStruct> = from_str_with(&policy, r#""some string""#).unwrap();
+
+        assert_eq!(res, StringOrStruct::String(String::from("some string")));
+    }
+}
+
+mod numbers_as_string {
+    use super::*;
+
+    #[test]
+    fn integer() {
+        let policy = DispatchPolicy::new().numbers_as_string(true);
+
+        let res: StringOrStruct<SimpleStruct> = from_str_with(&policy, "-42").unwrap();
+
+        assert_eq!(res, StringOrStruct::String(String::from("-42")));
+    }
+
+    #[test]
+    fn float() {
+        let policy = DispatchPolicy::new().numbers_as_string(true);
+
+        let res: StringOrStructOrVec<SimpleStruct, Vec<SimpleStruct>> =
+            from_str_with(&policy, "1.5").unwrap();
+
+        assert_eq!(res, StringOrStructOrVec::String(String::from("1.5")));
+    }
+}
+
+mod seq_of_one_as_struct {
+    use super::*;
+
+    #[test]
+    fn string_or_struct_or_vec() {
+        let policy = DispatchPolicy::new().seq_of_one_as_struct(true);
+
+        let res: StringOrStructOrVec<Person, Vec<Person>> =
+            from_str_with(&policy, r#"[{"first_name": "Bob", "last_name": "Smith"}]"#).unwrap();
+
+        assert_eq!(
+            res,
+            StringOrStructOrVec::Struct(Person {
+                first_name: String::from("Bob"),
+                last_name: String::from("Smith")
+            })
+        );
+    }
+
+    #[test]
+    fn longer_seqs_stay_vec() {
+        let policy = DispatchPolicy::new().seq_of_one_as_struct(true);
+
+        let res: SingleOrVec<u8> = from_str_with(&policy, "[1, 2]").unwrap();
+
+        assert_eq!(res, SingleOrVec::Vec(vec![1, 2]));
+    }
+
+    #[test]
+    fn single_or_vec() {
+        let policy = DispatchPolicy::new().seq_of_one_as_struct(true);
+
+        let res: SingleOrVec<u8> = from_str_with(&policy, "[7]").unwrap();
+
+        assert_eq!(res, SingleOrVec::Single(7));
+    }
+}
+
+mod max_depth {
+    use super::*;
+    use serde::Deserialize;
+    use serde_value::{Value, ValueDeserializer};
+
+    fn nested_seqs(depth: usize) -> Value {
+        (0..depth).fold(Value::U8(1), |value, _| Value::Seq(vec![value]))
+    }
+
+    #[test]
+    fn within_limit() {
+        let policy = DispatchPolicy::new().max_depth(3);
+
+        let res: SingleOrVec<Vec<Vec<u8>>> = from_str_with(&policy, "[[[1]]]").unwrap();
+
+        assert_eq!(res, SingleOrVec::Vec(vec![vec![vec![1]]]));
+    }
+
+    #[test]
+    fn exceeding_limit() {
+        let policy = DispatchPolicy::new().max_depth(2);
+
+        let res: serde_json::Result<SingleOrVec<Vec<Vec<u8>>>> = from_str_with(&policy, "[[[1]]]");
+
+        let message = res.unwrap_err().to_string();
+        assert!(
+            message.contains("exceeds the maximum nesting depth of 2"),
+            "{}",
+            message
+        );
+    }
+
+    #[test]
+    fn default_limit() {
+        let deserializer = ValueDeserializer::<serde_value::DeserializerError>::new(nested_seqs(
+            serde_either::DEFAULT_MAX_DEPTH + 1,
+        ));
+
+        let res = StringOrStructOrVec::<Value, Value>::deserialize(deserializer);
+
+        assert!(res.is_err());
+    }
+}
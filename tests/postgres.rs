@@ -0,0 +1,75 @@
+#![cfg(feature = "postgres")]
+
+mod common;
+
+use crate::common::SimpleStruct;
+use bytes::BytesMut;
+use postgres_types::{FromSql, ToSql, Type};
+use serde_either::{OneOrMany, SingleOrVec, StringOrStruct, StringOrStructOrVec};
+
+fn to_sql<T: ToSql>(value: &T, ty: &Type) -> BytesMut {
+    let mut out = BytesMut::new();
+    value.to_sql_checked(ty, &mut out).unwrap();
+    out
+}
+
+#[test]
+fn json_round_trip() {
+    let value: StringOrStruct<SimpleStruct> = StringOrStruct::Struct(SimpleStruct {
+        number: 1,
+        text: String::from("a"),
+    });
+
+    let raw = to_sql(&value, &Type::JSON);
+    assert_eq!(&raw[..], br#"{"number":1,"text":"a"}"#);
+
+    let res = StringOrStruct::<SimpleStruct>::from_sql(&Type::JSON, &raw).unwrap();
+    assert_eq!(res, value);
+}
+
+#[test]
+fn jsonb_round_trip() {
+    let value: StringOrStructOrVec<SimpleStruct, Vec<SimpleStruct>> =
+        StringOrStructOrVec::String(String::from("text"));
+
+    let raw = to_sql(&value, &Type::JSONB);
+    // jsonb is prefixed with the version of its binary format
+    assert_eq!(&raw[..], b"\x01\"text\"");
+
+    let res = StringOrStructOrVec::<SimpleStruct, Vec<SimpleStruct>>::from_sql(&Type::JSONB, &raw)
+        .unwrap();
+    assert_eq!(res, value);
+}
+
+#[test]
+fn reads_any_form() {
+    let res = SingleOrVec::<u32>::from_sql(&Type::JSON, b"[1, 2]").unwrap();
+    assert_eq!(res, SingleOrVec::Vec(vec![1, 2]));
+
+    let res = OneOrMany::<u32>::from_sql(&Type::JSONB, b"\x013").unwrap();
+    assert_eq!(res, OneOrMany::One(3));
+}
+
+#[test]
+fn accepts_json_types_only() {
+    assert!(<StringOrStruct<SimpleStruct> as ToSql>::accepts(
+        &Type::JSON
+    ));
+    assert!(<StringOrStruct<SimpleStruct> as FromSql>::accepts(
+        &Type::JSONB
+    ));
+    assert!(!<StringOrStruct<SimpleStruct> as ToSql>::accepts(
+        &Type::TEXT
+    ));
+
+    let mut out = BytesMut::new();
+    let res = OneOrMany::One(1).to_sql_checked(&Type::TEXT, &mut out);
+    assert!(res.is_err());
+}
+
+#[test]
+fn invalid_json() {
+    let res = StringOrStruct::<SimpleStruct>::from_sql(&Type::JSON, b"42");
+
+    assert!(res.is_err());
+}
@@ -0,0 +1,143 @@
+#![cfg(feature = "prost-types")]
+
+mod common;
+
+use crate::common::SimpleStruct;
+use prost_types::value::Kind;
+use prost_types::{ListValue, Struct, Value};
+use serde_either::{OneOrMany, SingleOrVec, StringOrStruct, StringOrStructOrVec};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+fn string(s: &str) -> Value {
+    Value {
+        kind: Some(Kind::StringValue(s.to_owned())),
+    }
+}
+
+fn number(n: f64) -> Value {
+    Value {
+        kind: Some(Kind::NumberValue(n)),
+    }
+}
+
+fn simple(number_value: f64, text: &str) -> Struct {
+    let mut fields = BTreeMap::new();
+    fields.insert(String::from("number"), number(number_value));
+    fields.insert(String::from("text"), string(text));
+    Struct { fields }
+}
+
+#[test]
+fn string_value() {
+    let res = StringOrStruct::<SimpleStruct>::try_from(string("a")).unwrap();
+
+    assert_eq!(res, StringOrStruct::String(String::from("a")));
+}
+
+#[test]
+fn struct_value_with_integral_numbers() {
+    let value = Value {
+        kind: Some(Kind::StructValue(simple(1.0, "a"))),
+    };
+
+    let res = StringOrStruct::<SimpleStruct>::try_from(value).unwrap();
+
+    assert_eq!(
+        res,
+        StringOrStruct::Struct(SimpleStruct {
+            number: 1,
+            text: String::from("a")
+        })
+    );
+}
+
+#[test]
+fn fractional_numbers_are_not_integers() {
+    let res = StringOrStruct::<SimpleStruct>::try_from(simple(1.5, "a"));
+
+    assert!(res.is_err());
+}
+
+#[test]
+fn list_value() {
+    let list = ListValue {
+        values: vec![
+            Value {
+                kind: Some(Kind::StructValue(simple(1.0, "a"))),
+            },
+            Value {
+                kind: Some(Kind::StructValue(simple(-2.0, "b"))),
+            },
+        ],
+    };
+
+    let res = StringOrStructOrVec::<SimpleStruct, Vec<SimpleStruct>>::try_from(list).unwrap();
+
+    match res {
+        StringOrStructOrVec::Vec(items) => {
+            assert_eq!(items.len(), 2);
+            assert_eq!(items[1].number, -2);
+        }
+        _ => panic!("expected the Vec arm"),
+    }
+}
+
+#[test]
+fn single_and_many() {
+    let res = SingleOrVec::<f64>::try_from(number(0.5)).unwrap();
+    assert_eq!(res, SingleOrVec::Single(0.5));
+
+    let empty = ListValue { values: vec![] };
+    assert!(OneOrMany::<f64>::try_from(empty).is_err());
+}
+
+#[test]
+fn null_value_is_rejected() {
+    let res = StringOrStruct::<SimpleStruct>::try_from(Value { kind: None });
+
+    assert!(res.is_err());
+}
+
+#[test]
+fn to_prost() {
+    let value = StringOrStructOrVec::<SimpleStruct, Vec<SimpleStruct>>::Vec(vec![SimpleStruct {
+        number: 3,
+        text: String::from("c"),
+    }]);
+
+    let res = Value::try_from(value).unwrap();
+
+    assert_eq!(
+        res,
+        Value {
+            kind: Some(Kind::ListValue(ListValue {
+                values: vec![Value {
+                    kind: Some(Kind::StructValue(simple(3.0, "c")))
+                }]
+            }))
+        }
+    );
+}
+
+#[test]
+fn round_trip() {
+    let value = StringOrStruct::<SimpleStruct>::String(String::from("text"));
+
+    let res = StringOrStruct::<SimpleStruct>::try_from(Value::try_from(value).unwrap()).unwrap();
+
+    assert_eq!(res, StringOrStruct::String(String::from("text")));
+}
+
+#[test]
+fn non_string_keys() {
+    let mut map = BTreeMap::new();
+    map.insert(1, String::from("a"));
+
+    let res: Result<Value, serde_either::Error> = Value::try_from(SingleOrVec::Single(map));
+
+    assert_eq!(
+        res.unwrap_err().message(),
+        "protobuf Struct keys must be strings"
+    );
+}
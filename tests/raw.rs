@@ -0,0 +1,227 @@
+mod common;
+
+use crate::common::SimpleStruct;
+use serde::{Deserialize, Serialize};
+use serde_either::{SingleOrVec, StringOrStructOrOther, StructOrRaw, UnknownOr, ValueOr};
+use serde_value::Value;
+
+#[test]
+fn known_struct() {
+    let res: StructOrRaw<SimpleStruct> =
+        serde_json::from_str(r#"{"number": 1, "text": "a"}"#).unwrap();
+
+    assert_eq!(
+        res,
+        StructOrRaw::Struct(SimpleStruct {
+            number: 1,
+            text: String::from("a")
+        })
+    );
+    assert!(res.as_raw().is_none());
+}
+
+#[test]
+fn unknown_struct_is_kept() {
+    let res: StructOrRaw<SimpleStruct> =
+        serde_json::from_str(r#"{"kind": "other", "payload": [1, 2]}"#).unwrap();
+
+    let raw = res.as_raw().unwrap();
+    match raw {
+        Value::Map(map) => {
+            assert_eq!(
+                map.get(&Value::String(String::from("kind"))),
+                Some(&Value::String(String::from("other")))
+            );
+        }
+        _ => panic!("expected a map"),
+    }
+    assert!(res.as_struct().is_none());
+}
+
+#[test]
+fn raw_round_trip() {
+    let json = r#"{"kind":"other","payload":[1,2]}"#;
+
+    let res: StructOrRaw<SimpleStruct> = serde_json::from_str(json).unwrap();
+
+    assert_eq!(serde_json::to_string(&res).unwrap(), json);
+}
+
+#[test]
+fn rejects_non_maps() {
+    let res = serde_json::from_str::<StructOrRaw<SimpleStruct>>(r#""text""#);
+
+    assert!(res.unwrap_err().to_string().contains("expected a map"));
+}
+
+#[test]
+fn mixed_list() {
+    let res: SingleOrVec<StructOrRaw<SimpleStruct>> =
+        serde_json::from_str(r#"[{"number": 1, "text": "a"}, {"unknown": true}]"#).unwrap();
+
+    match res {
+        SingleOrVec::Vec(items) => {
+            assert!(items[0].as_struct().is_some());
+            assert!(items[1].as_raw().is_some());
+        }
+        SingleOrVec::Single(_) => panic!("expected the Vec arm"),
+    }
+}
+
+mod value_or {
+    use super::*;
+
+    #[test]
+    fn parsed() {
+        let res: ValueOr<SimpleStruct> =
+            serde_json::from_str(r#"{"number": 1, "text": "a"}"#).unwrap();
+
+        assert_eq!(
+            res.as_parsed(),
+            Some(&SimpleStruct {
+                number: 1,
+                text: String::from("a")
+            })
+        );
+        assert!(res.as_other().is_none());
+    }
+
+    #[test]
+    fn any_shape_is_kept() {
+        let res: ValueOr<SimpleStruct> = serde_json::from_str("[1, 2]").unwrap();
+        assert_eq!(
+            res,
+            ValueOr::Other(Value::Seq(vec![Value::U64(1), Value::U64(2)]))
+        );
+
+        let res: ValueOr<SimpleStruct> = serde_json::from_str(r#""text""#).unwrap();
+        assert_eq!(res, ValueOr::Other(Value::String(String::from("text"))));
+    }
+
+    #[test]
+    fn json_values() {
+        let res: ValueOr<u8, serde_json::Value> = serde_json::from_str("256").unwrap();
+
+        assert_eq!(res, ValueOr::Other(serde_json::json!(256)));
+    }
+
+    #[test]
+    fn fails_when_the_fallback_fails() {
+        let res = serde_json::from_str::<ValueOr<u8, String>>("256");
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn round_trip() {
+        let json = r#"[{"number":1,"text":"a"},{"version":2},null]"#;
+
+        let res: Vec<ValueOr<SimpleStruct>> = serde_json::from_str(json).unwrap();
+
+        assert!(res[0].as_parsed().is_some());
+        assert!(res[1].as_other().is_some());
+        assert_eq!(serde_json::to_string(&res).unwrap(), json);
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn json_value_or() {
+        let res: serde_either::JsonValueOr<u8> = serde_json::from_str(r#"{"a": 1}"#).unwrap();
+
+        assert_eq!(res.as_other(), Some(&serde_json::json!({"a": 1})));
+    }
+}
+
+mod string_or_struct_or_other {
+    use super::*;
+
+    type Field = StringOrStructOrOther<SimpleStruct, serde_json::Value>;
+
+    #[test]
+    fn strings_and_structs() {
+        let res: Field = serde_json::from_str(r#""a""#).unwrap();
+        assert_eq!(res, StringOrStructOrOther::String(String::from("a")));
+
+        let res: Field = serde_json::from_str(r#"{"number": 1, "text": "a"}"#).unwrap();
+        assert_eq!(
+            res,
+            StringOrStructOrOther::Struct(SimpleStruct {
+                number: 1,
+                text: String::from("a")
+            })
+        );
+    }
+
+    #[test]
+    fn everything_else_is_other() {
+        for json in &["1", "true", "null", "[1, 2]"] {
+            let res: Field = serde_json::from_str(json).unwrap();
+
+            let expected: serde_json::Value = serde_json::from_str(json).unwrap();
+            assert_eq!(res, StringOrStructOrOther::Other(expected));
+        }
+    }
+
+    #[test]
+    fn invalid_structs_are_errors() {
+        let res = serde_json::from_str::<Field>(r#"{"number": "one"}"#);
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn round_trip() {
+        for json in &[r#""a""#, r#"{"number":1,"text":"a"}"#, "[1,2]"] {
+            let res: Field = serde_json::from_str(json).unwrap();
+
+            assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+        }
+    }
+}
+
+mod unknown_or {
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    #[serde(rename_all = "snake_case")]
+    enum Status {
+        Active,
+        Suspended,
+    }
+
+    #[test]
+    fn known_variants() {
+        let res: UnknownOr<Status> = serde_json::from_str(r#""suspended""#).unwrap();
+
+        assert_eq!(res, UnknownOr::Known(Status::Suspended));
+        assert!(!res.is_unknown());
+    }
+
+    #[test]
+    fn unknown_strings_are_kept() {
+        let res: Vec<UnknownOr<Status>> =
+            serde_json::from_str(r#"["active", "archived"]"#).unwrap();
+
+        assert_eq!(res[0].as_known(), Some(&Status::Active));
+        assert_eq!(res[1].as_unknown(), Some("archived"));
+    }
+
+    #[test]
+    fn rejects_other_types() {
+        let err = serde_json::from_str::<UnknownOr<Status>>("1").unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "invalid type: integer `1`, expected string or map"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let json = r#"["active","archived"]"#;
+
+        let res: Vec<UnknownOr<Status>> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_string(&res).unwrap(), json);
+    }
+}
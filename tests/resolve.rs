@@ -0,0 +1,80 @@
+#![cfg(feature = "async")]
+
+mod common;
+
+use crate::common::Person;
+use serde_either::{Resolve, StringOrStruct};
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut context = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+struct Directory;
+
+impl Resolve<Person> for Directory {
+    type Error = String;
+
+    async fn resolve(&self, reference: &str) -> Result<Person, String> {
+        reference
+            .parse()
+            .map_err(|_| format!("unknown person {}", reference))
+    }
+}
+
+#[test]
+fn string_value_is_resolved() {
+    let value = StringOrStruct::String(String::from("Bob Smith"));
+
+    let res = block_on(value.resolve_async(&Directory));
+
+    assert_eq!(
+        res.unwrap(),
+        Person {
+            first_name: String::from("Bob"),
+            last_name: String::from("Smith")
+        }
+    );
+}
+
+#[test]
+fn struct_value_is_kept() {
+    let person = Person {
+        first_name: String::from("Uncle"),
+        last_name: String::from("Jones"),
+    };
+    let value = StringOrStruct::Struct(person);
+
+    let res = block_on(value.resolve_async(&Directory));
+
+    assert_eq!(res.unwrap().first_name, "Uncle");
+}
+
+#[test]
+fn resolution_errors() {
+    let value = StringOrStruct::String(String::from("Bob"));
+
+    let res = block_on(value.resolve_async(&Directory));
+
+    assert_eq!(res.unwrap_err(), "unknown person Bob");
+}
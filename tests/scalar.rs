@@ -0,0 +1,68 @@
+mod common;
+
+use crate::common::SimpleStruct;
+use serde_either::{Number, Scalar, ScalarOrStruct};
+
+type Either = ScalarOrStruct<SimpleStruct>;
+
+#[test]
+fn every_scalar() {
+    let res: Vec<Scalar> = serde_json::from_str(r#"["a", true, 1, -1, 1.5]"#).unwrap();
+
+    assert_eq!(
+        res,
+        vec![
+            Scalar::String(String::from("a")),
+            Scalar::Bool(true),
+            Scalar::Number(Number::U64(1)),
+            Scalar::Number(Number::I64(-1)),
+            Scalar::Number(Number::F64(1.5)),
+        ]
+    );
+    assert_eq!(
+        res.iter().map(Scalar::to_string).collect::<Vec<_>>(),
+        ["a", "true", "1", "-1", "1.5"]
+    );
+}
+
+#[test]
+fn scalars_and_structs() {
+    let res: Either = serde_yaml::from_str("yes").unwrap();
+    assert_eq!(res.as_scalar().and_then(Scalar::as_str), Some("yes"));
+
+    let res: Either = serde_yaml::from_str("false").unwrap();
+    assert_eq!(res.as_scalar().and_then(Scalar::as_bool), Some(false));
+
+    let res: Either = serde_json::from_str(r#"{"number": 1, "text": "a"}"#).unwrap();
+    assert_eq!(
+        res.as_struct(),
+        Some(&SimpleStruct {
+            number: 1,
+            text: String::from("a")
+        })
+    );
+}
+
+#[test]
+fn rejects_other_types() {
+    let err = serde_json::from_str::<Either>("[]").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid type: sequence, expected a scalar or a map"
+    );
+
+    let err = serde_json::from_str::<Scalar>("null").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid type: null, expected a string, a boolean or a number at line 1 column 4"
+    );
+}
+
+#[test]
+fn round_trip() {
+    for json in &[r#""a""#, "true", "-1", "1.5", r#"{"number":1,"text":"a"}"#] {
+        let res: Either = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+    }
+}
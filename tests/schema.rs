@@ -0,0 +1,88 @@
+#![cfg(feature = "jsonschema")]
+
+mod common;
+
+use crate::common::SimpleStruct;
+use jsonschema::Validator;
+use serde::de::DeserializeSeed;
+use serde_either::{DispatchPolicy, SchemaSeed, SingleOrVec, StringOrStruct, StringOrStructOrVec};
+
+fn validator() -> Validator {
+    let schema = serde_json::json!({
+        "type": "object",
+        "properties": {
+            "number": {"type": "integer", "minimum": 0},
+            "text": {"type": "string", "minLength": 1},
+        },
+        "required": ["number", "text"],
+    });
+    jsonschema::validator_for(&schema).unwrap()
+}
+
+fn from_str<'a, T>(seed: SchemaSeed<'_, T>, json: &'a str) -> serde_json::Result<T>
+where
+    T: serde_either::DeserializeWithPolicy<'a>,
+{
+    seed.deserialize(&mut serde_json::Deserializer::from_str(json))
+}
+
+#[test]
+fn valid_struct() {
+    let validator = validator();
+
+    let res: StringOrStruct<SimpleStruct> =
+        from_str(SchemaSeed::new(&validator), r#"{"number": 1, "text": "a"}"#).unwrap();
+
+    assert!(matches!(res, StringOrStruct::Struct(_)));
+}
+
+#[test]
+fn reports_every_violation() {
+    let validator = validator();
+
+    let err = from_str::<StringOrStruct<SimpleStruct>>(
+        SchemaSeed::new(&validator),
+        r#"{"number": -1, "text": ""}"#,
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert!(
+        err.contains("/number: -1 is less than the minimum of 0"),
+        "{}",
+        err
+    );
+    assert!(err.contains("/text: "), "{}", err);
+}
+
+#[test]
+fn missing_field() {
+    let validator = validator();
+
+    let err =
+        from_str::<StringOrStruct<SimpleStruct>>(SchemaSeed::new(&validator), r#"{"number": 1}"#)
+            .unwrap_err()
+            .to_string();
+
+    assert!(err.contains("\"text\" is a required property"), "{}", err);
+}
+
+#[test]
+fn strings_are_not_validated() {
+    let validator = validator();
+
+    let res: StringOrStructOrVec<SimpleStruct, Vec<SimpleStruct>> =
+        from_str(SchemaSeed::new(&validator), r#""text""#).unwrap();
+
+    assert_eq!(res, StringOrStructOrVec::String(String::from("text")));
+}
+
+#[test]
+fn with_policy() {
+    let validator = validator();
+    let seed = SchemaSeed::new(&validator).policy(DispatchPolicy::new().seq_of_one_as_struct(true));
+
+    let res: SingleOrVec<SimpleStruct> = from_str(seed, r#"[{"number": 1, "text": "a"}]"#).unwrap();
+
+    assert_eq!(res.len(), 1);
+}
@@ -1,8 +1,7 @@
 mod common;
 
 use crate::common::SimpleStruct;
-use serde_either::{SingleOrVec, StringOrStruct, StringOrStructOrVec};
-use serde_json;
+use serde_either::{OneOrMany, SingleOrVec, StringOrStruct, StringOrStructOrVec};
 
 mod string_or_struct {
     use super::*;
@@ -143,3 +142,28 @@ mod single_or_vec {
         );
     }
 }
+
+mod one_or_many {
+    use super::*;
+
+    #[test]
+    fn one_value() {
+        let value = OneOrMany::One(1);
+
+        assert_eq!(serde_json::to_string(&value).unwrap(), "1");
+    }
+
+    #[test]
+    fn many_value() {
+        let value = OneOrMany::Many(1, vec![2]);
+
+        assert_eq!(serde_json::to_string(&value).unwrap(), "[1,2]");
+    }
+
+    #[test]
+    fn single_element_array_round_trips() {
+        let value = OneOrMany::Many(1, vec![]);
+
+        assert_eq!(serde_json::to_string(&value).unwrap(), "[1]");
+    }
+}
@@ -0,0 +1,73 @@
+use serde::Deserialize;
+use serde_either::CommaSeparatedOrVec;
+
+#[derive(Deserialize, Debug)]
+struct Config {
+    hosts: CommaSeparatedOrVec<String>,
+    ports: CommaSeparatedOrVec<u16>,
+}
+
+#[test]
+fn strings_are_split() {
+    let res: Config =
+        serde_json::from_str(r#"{"hosts": "a.example, b.example", "ports": "80,443"}"#).unwrap();
+
+    assert_eq!(
+        *res.hosts,
+        vec![String::from("a.example"), String::from("b.example")]
+    );
+    assert_eq!(*res.ports, vec![80, 443]);
+}
+
+#[test]
+fn sequences_are_deserialized() {
+    let res: Config =
+        serde_yaml::from_str("hosts: [a.example, 'b,example']\nports: [80, 443]\n").unwrap();
+
+    assert_eq!(
+        res.hosts.into_inner(),
+        vec![String::from("a.example"), String::from("b,example")]
+    );
+    assert_eq!(*res.ports, vec![80, 443]);
+}
+
+#[test]
+fn empty_string_is_empty() {
+    let res: CommaSeparatedOrVec<u16> = serde_json::from_str(r#""""#).unwrap();
+
+    assert!(res.is_empty());
+}
+
+#[test]
+fn custom_separator() {
+    let res: CommaSeparatedOrVec<String, ':'> = serde_json::from_str(r#""/bin:/usr/bin""#).unwrap();
+
+    assert_eq!(*res, vec![String::from("/bin"), String::from("/usr/bin")]);
+}
+
+#[test]
+fn invalid_piece() {
+    let err = serde_json::from_str::<CommaSeparatedOrVec<u16>>(r#""80,http""#).unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "invalid element \"http\": invalid digit found in string at line 1 column 9"
+    );
+}
+
+#[test]
+fn rejects_other_types() {
+    let err = serde_json::from_str::<CommaSeparatedOrVec<u16>>("80").unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "invalid type: integer `80`, expected a sequence or a string separated by ',' at line 1 column 2"
+    );
+}
+
+#[test]
+fn serialized_as_a_sequence() {
+    let res: CommaSeparatedOrVec<u16> = serde_json::from_str(r#""80,443""#).unwrap();
+
+    assert_eq!(serde_json::to_string(&res).unwrap(), "[80,443]");
+}
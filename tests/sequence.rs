@@ -0,0 +1,570 @@
+mod common;
+
+use crate::common::SimpleStruct;
+use serde::{Deserialize, Serialize};
+use serde_either::{
+    KeyedMapOrVec, MapOrString, MapOrVec, SingleOrMap, SingleOrSet, StringOrStrings,
+    StringOrStructOrVecOrMap, StringOrVec, StructOrVec, TupleOrStruct,
+};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+type Either = StructOrVec<SimpleStruct, Vec<SimpleStruct>>;
+
+fn simple(number: i32) -> SimpleStruct {
+    SimpleStruct {
+        number,
+        text: String::from("a"),
+    }
+}
+
+mod struct_or_vec {
+    use super::*;
+
+    #[test]
+    fn structs() {
+        let res: Either = serde_json::from_str(r#"{"number": 1, "text": "a"}"#).unwrap();
+
+        assert_eq!(res, StructOrVec::Struct(simple(1)));
+        assert_eq!(res.as_vec(), None);
+    }
+
+    #[test]
+    fn vecs() {
+        let res: Either =
+            serde_yaml::from_str("- number: 1\n  text: a\n- number: 2\n  text: a\n").unwrap();
+
+        assert_eq!(res, StructOrVec::Vec(vec![simple(1), simple(2)]));
+        assert_eq!(res.as_struct(), None);
+    }
+
+    #[test]
+    fn rejects_strings() {
+        let err = serde_json::from_str::<Either>(r#""text""#).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "invalid type: string \"text\", expected a map or a sequence"
+        );
+    }
+
+    #[test]
+    fn rejects_numbers() {
+        assert!(serde_json::from_str::<Either>("1").is_err());
+    }
+
+    #[test]
+    fn round_trip() {
+        for json in &[r#"{"number":1,"text":"a"}"#, r#"[{"number":1,"text":"a"}]"#] {
+            let res: Either = serde_json::from_str(json).unwrap();
+
+            assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+        }
+    }
+}
+
+mod string_or_vec {
+    use super::*;
+
+    type Tags = StringOrVec<Vec<String>>;
+
+    #[test]
+    fn strings() {
+        let res: Tags = serde_yaml::from_str("a").unwrap();
+
+        assert_eq!(res, StringOrVec::String(String::from("a")));
+        assert_eq!(res.as_vec(), None);
+    }
+
+    #[test]
+    fn vecs() {
+        let res: Tags = serde_json::from_str(r#"["a", "b"]"#).unwrap();
+
+        assert_eq!(
+            res,
+            StringOrVec::Vec(vec![String::from("a"), String::from("b")])
+        );
+        assert_eq!(res.as_str(), None);
+    }
+
+    #[test]
+    fn rejects_maps() {
+        let err = serde_json::from_str::<Tags>(r#"{"a": "b"}"#).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "invalid type: map, expected a string or a sequence"
+        );
+    }
+
+    #[test]
+    fn utf8_bytes_are_strings() {
+        use serde_value::{DeserializerError, Value, ValueDeserializer};
+
+        let bytes = Value::Bytes(b"a".to_vec());
+        let res = Tags::deserialize(ValueDeserializer::<DeserializerError>::new(bytes)).unwrap();
+        assert_eq!(res, StringOrVec::String(String::from("a")));
+
+        let invalid = Value::Bytes(vec![0xff]);
+        assert!(Tags::deserialize(ValueDeserializer::<DeserializerError>::new(invalid)).is_err());
+    }
+
+    #[test]
+    fn rejects_other_scalars() {
+        assert!(serde_json::from_str::<Tags>("1").is_err());
+        assert!(serde_json::from_str::<Tags>("null").is_err());
+    }
+
+    #[test]
+    fn round_trip() {
+        for json in &[r#""a""#, r#"["a","b"]"#] {
+            let res: Tags = serde_json::from_str(json).unwrap();
+
+            assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+        }
+    }
+}
+
+mod map_or_vec {
+    use super::*;
+
+    #[derive(Deserialize, Debug)]
+    struct Service {
+        environment: MapOrVec<BTreeMap<String, String>, Vec<String>>,
+    }
+
+    #[test]
+    fn maps() {
+        let res: Service =
+            serde_yaml::from_str("environment:\n  RACK_ENV: development\n  SHOW: 'true'\n")
+                .unwrap();
+
+        let map = res.environment.as_map().unwrap();
+        assert_eq!(map["RACK_ENV"], "development");
+        assert_eq!(map["SHOW"], "true");
+    }
+
+    #[test]
+    fn vecs() {
+        let res: Service =
+            serde_yaml::from_str("environment:\n  - RACK_ENV=development\n  - SHOW=true\n")
+                .unwrap();
+
+        assert_eq!(
+            res.environment,
+            MapOrVec::Vec(vec![
+                String::from("RACK_ENV=development"),
+                String::from("SHOW=true")
+            ])
+        );
+    }
+
+    #[test]
+    fn rejects_scalars() {
+        let err = serde_json::from_str::<MapOrVec<BTreeMap<String, String>, Vec<String>>>(
+            r#""RACK_ENV=development""#,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "invalid type: string \"RACK_ENV=development\", expected a map or a sequence"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        for json in &[r#"{"A":"1"}"#, r#"["A=1"]"#] {
+            let res: MapOrVec<BTreeMap<String, String>, Vec<String>> =
+                serde_json::from_str(json).unwrap();
+
+            assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+        }
+    }
+}
+
+mod keyed_map_or_vec {
+    use super::*;
+    use serde::Serialize;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Service {
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        name: String,
+        image: String,
+    }
+
+    fn service(name: &str, image: &str) -> Service {
+        Service {
+            name: String::from(name),
+            image: String::from(image),
+        }
+    }
+
+    #[test]
+    fn keyed_maps_keep_their_order() {
+        let res: KeyedMapOrVec<Service> =
+            serde_yaml::from_str("web:\n  image: nginx\ndb:\n  image: postgres\n").unwrap();
+
+        assert_eq!(res.len(), 2);
+        assert_eq!(
+            res.into_vec(|s, name| s.name = name),
+            vec![service("web", "nginx"), service("db", "postgres")]
+        );
+    }
+
+    #[test]
+    fn vecs_are_left_as_they_are() {
+        let res: KeyedMapOrVec<Service> = serde_json::from_str(
+            r#"[{"name": "web", "image": "nginx"}, {"name": "db", "image": "postgres"}]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            res.into_vec(|_, _| panic!("not a map")),
+            vec![service("web", "nginx"), service("db", "postgres")]
+        );
+    }
+
+    #[test]
+    fn rejects_scalars() {
+        let err = serde_json::from_str::<KeyedMapOrVec<Service>>(r#""web""#).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "invalid type: string \"web\", expected a map or a sequence at line 1 column 5"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        for json in &[
+            r#"{"web":{"image":"nginx"},"db":{"image":"postgres"}}"#,
+            r#"[{"name":"web","image":"nginx"}]"#,
+        ] {
+            let res: KeyedMapOrVec<Service> = serde_json::from_str(json).unwrap();
+
+            assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+        }
+    }
+}
+
+mod single_or_set {
+    use super::*;
+
+    #[test]
+    fn single() {
+        let res: SingleOrSet<String> = serde_json::from_str(r#""admin""#).unwrap();
+
+        assert_eq!(res.as_single().map(String::as_str), Some("admin"));
+        assert_eq!(
+            res.into_set(),
+            vec![String::from("admin")]
+                .into_iter()
+                .collect::<HashSet<_>>()
+        );
+    }
+
+    #[test]
+    fn sets_are_deduplicated() {
+        let res: SingleOrSet<u32> = serde_json::from_str("[2, 1, 2]").unwrap();
+
+        assert_eq!(res.as_set().map(HashSet::len), Some(2));
+    }
+
+    #[test]
+    fn btree_sets_are_sorted() {
+        let json = "[3,1,2,1]";
+
+        let res: SingleOrSet<u32, BTreeSet<u32>> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_string(&res).unwrap(), "[1,2,3]");
+    }
+
+    #[test]
+    fn structs() {
+        let res: SingleOrSet<SimpleStruct, Vec<SimpleStruct>> =
+            serde_json::from_str(r#"{"number": 1, "text": "a"}"#).unwrap();
+
+        assert_eq!(
+            res,
+            SingleOrSet::Single(SimpleStruct {
+                number: 1,
+                text: String::from("a")
+            })
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let res: SingleOrSet<String, BTreeSet<String>> = serde_json::from_str(r#""a""#).unwrap();
+
+        assert_eq!(serde_json::to_string(&res).unwrap(), r#""a""#);
+    }
+}
+
+mod single_or_map {
+    use super::*;
+
+    type Output = SingleOrMap<String, BTreeMap<String, String>>;
+
+    #[test]
+    fn single() {
+        let res: Output = serde_yaml::from_str("dist").unwrap();
+
+        assert_eq!(res, SingleOrMap::Single(String::from("dist")));
+    }
+
+    #[test]
+    fn maps() {
+        let res: Output = serde_yaml::from_str("js: dist/js\ncss: dist/css\n").unwrap();
+
+        let map = res.as_map().unwrap();
+        assert_eq!(map["js"], "dist/js");
+        assert_eq!(map["css"], "dist/css");
+    }
+
+    #[test]
+    fn structs_are_maps() {
+        let res: SingleOrMap<String, SimpleStruct> =
+            serde_json::from_str(r#"{"number": 1, "text": "a"}"#).unwrap();
+
+        assert!(res.as_map().is_some());
+    }
+
+    #[test]
+    fn rejects_invalid_singles() {
+        let err = serde_json::from_str::<Output>("[1]").unwrap_err();
+
+        assert_eq!(err.to_string(), "invalid type: sequence, expected a string");
+    }
+
+    #[test]
+    fn round_trip() {
+        for json in &[r#""dist""#, r#"{"css":"dist/css","js":"dist/js"}"#] {
+            let res: Output = serde_json::from_str(json).unwrap();
+
+            assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+        }
+    }
+}
+
+mod string_or_strings {
+    use super::*;
+
+    #[test]
+    fn single_string() {
+        let res: StringOrStrings = serde_yaml::from_str("a").unwrap();
+
+        assert_eq!(res.iter().collect::<Vec<_>>(), ["a"]);
+        assert_eq!(res.into_vec(), vec![String::from("a")]);
+    }
+
+    #[test]
+    fn strings() {
+        let res: StringOrStrings = serde_yaml::from_str("[a, b]").unwrap();
+
+        assert_eq!(res.into_vec(), vec![String::from("a"), String::from("b")]);
+    }
+
+    #[test]
+    fn rejects_non_string_elements() {
+        let err = serde_json::from_str::<StringOrStrings>(r#"["a", "b", {"c": 1}]"#).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "invalid type: map, expected a string as element 2 at line 1 column 11"
+        );
+    }
+
+    #[test]
+    fn rejects_other_types() {
+        let err = serde_json::from_str::<StringOrStrings>("1").unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "invalid type: integer `1`, expected a string or a sequence of strings at line 1 column 1"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        for json in &[r#""a""#, r#"["a"]"#, "[]"] {
+            let res: StringOrStrings = serde_json::from_str(json).unwrap();
+
+            assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+        }
+    }
+}
+
+mod map_or_string {
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct Request {
+        headers: MapOrString,
+    }
+
+    #[test]
+    fn strings() {
+        let res: Request = serde_yaml::from_str("headers: 'X-Id: 1'").unwrap();
+
+        assert_eq!(res.headers, MapOrString::String(String::from("X-Id: 1")));
+    }
+
+    #[test]
+    fn maps() {
+        let res: Request =
+            serde_yaml::from_str("headers:\n  X-Id: '1'\n  Accept: text/plain\n").unwrap();
+
+        let map = res.headers.as_map().unwrap();
+        assert_eq!(map["X-Id"], "1");
+        assert_eq!(map["Accept"], "text/plain");
+    }
+
+    #[test]
+    fn rejects_other_types() {
+        let err = serde_json::from_str::<MapOrString>(r#"["a"]"#).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "invalid type: sequence, expected a map or a string at line 1 column 1"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        for json in &[r#""X-Id: 1""#, r#"{"Accept":"text/plain","X-Id":"1"}"#] {
+            let res: MapOrString = serde_json::from_str(json).unwrap();
+
+            assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+        }
+    }
+
+    #[cfg(feature = "indexmap")]
+    #[test]
+    fn index_maps_keep_the_order() {
+        let json = r#"{"X-Id":"1","Accept":"text/plain"}"#;
+
+        let res: serde_either::IndexMapOrString = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_string(&res).unwrap(), json);
+    }
+}
+
+mod tuple_or_struct {
+    use super::*;
+
+    type Pair = TupleOrStruct<(i32, String), SimpleStruct>;
+
+    impl From<(i32, String)> for SimpleStruct {
+        fn from((number, text): (i32, String)) -> Self {
+            SimpleStruct { number, text }
+        }
+    }
+
+    #[test]
+    fn positional_and_named() {
+        let res: Vec<Pair> =
+            serde_json::from_str(r#"[[1, "a"], {"number": 1, "text": "a"}]"#).unwrap();
+
+        assert_eq!(res[0].as_tuple(), Some(&(1, String::from("a"))));
+        assert_eq!(res[1].as_struct(), Some(&simple(1)));
+        assert_eq!(
+            res.into_iter().map(Pair::into_struct).collect::<Vec<_>>(),
+            vec![simple(1), simple(1)]
+        );
+    }
+
+    #[test]
+    fn rejects_other_types() {
+        let err = serde_json::from_str::<Pair>(r#""a""#).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"invalid type: string "a", expected a map or a sequence"#
+        );
+
+        assert!(serde_json::from_str::<Pair>("[1]").is_err());
+    }
+
+    #[test]
+    fn round_trip() {
+        for json in &[r#"[1,"a"]"#, r#"{"number":1,"text":"a"}"#] {
+            let res: Pair = serde_json::from_str(json).unwrap();
+
+            assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+        }
+    }
+}
+
+mod string_or_struct_or_vec_or_map {
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    #[serde(deny_unknown_fields)]
+    struct Job {
+        script: String,
+        #[serde(default)]
+        retry: u8,
+    }
+
+    type Jobs = StringOrStructOrVecOrMap<Job, Vec<Job>, BTreeMap<String, Job>>;
+
+    fn job() -> Job {
+        Job {
+            script: String::from("make"),
+            retry: 0,
+        }
+    }
+
+    #[test]
+    fn every_arm() {
+        let res: Jobs = serde_yaml::from_str("make").unwrap();
+        assert_eq!(res.as_str(), Some("make"));
+
+        let res: Jobs = serde_yaml::from_str("script: make").unwrap();
+        assert_eq!(res.as_struct(), Some(&job()));
+
+        let res: Jobs = serde_yaml::from_str("- script: make").unwrap();
+        assert_eq!(res.as_vec(), Some(&vec![job()]));
+
+        let res: Jobs = serde_yaml::from_str("build:\n  script: make\n").unwrap();
+        assert_eq!(
+            res.as_map(),
+            Some(&BTreeMap::from([(String::from("build"), job())]))
+        );
+    }
+
+    #[test]
+    fn reports_struct_errors() {
+        let err = serde_json::from_str::<Jobs>(r#"{"script": "make", "retries": 1}"#).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "unknown field `retries`, expected `script` or `retry`"
+        );
+    }
+
+    #[test]
+    fn rejects_other_types() {
+        let err = serde_json::from_str::<Jobs>("true").unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "invalid type: boolean `true`, expected String, Struct or Vec"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        for json in &[
+            r#""make""#,
+            r#"{"script":"make","retry":0}"#,
+            r#"[{"script":"make","retry":1}]"#,
+            r#"{"build":{"script":"make","retry":0}}"#,
+        ] {
+            let res: Jobs = serde_json::from_str(json).unwrap();
+
+            assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+        }
+    }
+}
@@ -0,0 +1,53 @@
+#![cfg(feature = "smallvec")]
+
+mod common;
+
+use crate::common::SimpleStruct;
+use serde_either::SingleOrSmallVec;
+
+#[test]
+fn single() {
+    let res: SingleOrSmallVec<SimpleStruct, 2> =
+        serde_json::from_str(r#"{"number": 1, "text": "a"}"#).unwrap();
+
+    assert_eq!(
+        res,
+        SingleOrSmallVec::Single(SimpleStruct {
+            number: 1,
+            text: String::from("a")
+        })
+    );
+    assert_eq!(res.len(), 1);
+}
+
+#[test]
+fn short_sequences_are_inline() {
+    let res: SingleOrSmallVec<u32, 3> = serde_json::from_str("[1, 2, 3]").unwrap();
+
+    assert_eq!(res.as_slice(), [1, 2, 3]);
+    assert!(!res.spilled());
+}
+
+#[test]
+fn long_sequences_spill() {
+    let res: SingleOrSmallVec<u32, 2> = serde_json::from_str("[1, 2, 3]").unwrap();
+
+    assert_eq!(res.len(), 3);
+    assert!(res.spilled());
+}
+
+#[test]
+fn into_smallvec() {
+    let res: SingleOrSmallVec<u32, 2> = serde_json::from_str("1").unwrap();
+
+    assert_eq!(res.into_smallvec().as_slice(), [1]);
+}
+
+#[test]
+fn round_trip() {
+    for json in &["1", "[1,2,3]", "[]"] {
+        let res: SingleOrSmallVec<u32, 2> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+    }
+}
@@ -0,0 +1,83 @@
+use serde::Deserialize;
+use serde_either::{HostPort, StringOrSocketAddr};
+use std::net::{SocketAddr, ToSocketAddrs};
+
+#[derive(Deserialize)]
+struct Service {
+    listen: StringOrSocketAddr,
+}
+
+fn host_port(host: &str, port: u16) -> HostPort {
+    HostPort {
+        host: String::from(host),
+        port,
+    }
+}
+
+#[test]
+fn strings() {
+    let res: Service = serde_yaml::from_str("listen: db.internal:5432").unwrap();
+    assert_eq!(
+        res.listen,
+        StringOrSocketAddr::String(host_port("db.internal", 5432))
+    );
+    assert_eq!(res.listen.to_socket_addr(), None);
+
+    let res: StringOrSocketAddr = serde_json::from_str(r#""[::1]:8080""#).unwrap();
+    assert_eq!(res.host(), "::1");
+    assert_eq!(
+        res.to_socket_addr(),
+        Some("[::1]:8080".parse::<SocketAddr>().unwrap())
+    );
+}
+
+#[test]
+fn structs() {
+    let res: Service =
+        serde_yaml::from_str("listen:\n  host: 0.0.0.0\n  port: 80\n  tls: false\n").unwrap();
+
+    assert_eq!(
+        res.listen,
+        StringOrSocketAddr::Struct(host_port("0.0.0.0", 80))
+    );
+    assert_eq!(res.listen.port(), 80);
+}
+
+#[test]
+fn resolves_names() {
+    let res: StringOrSocketAddr = serde_json::from_str(r#""localhost:80""#).unwrap();
+
+    let addrs: Vec<SocketAddr> = res.to_socket_addrs().unwrap().collect();
+    assert!(addrs.iter().all(|addr| addr.port() == 80));
+}
+
+#[test]
+fn rejects_invalid_addresses() {
+    let err = serde_json::from_str::<StringOrSocketAddr>(r#""localhost""#).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid address \"localhost\": expected host:port"
+    );
+
+    let err = serde_json::from_str::<StringOrSocketAddr>(r#""localhost:http""#).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid port in \"localhost:http\": invalid digit found in string"
+    );
+
+    let err = serde_json::from_str::<StringOrSocketAddr>(r#"{"host": "a"}"#).unwrap_err();
+    assert_eq!(err.to_string(), "missing field `port`");
+}
+
+#[test]
+fn round_trip() {
+    for json in &[
+        r#""db.internal:5432""#,
+        r#""[::1]:8080""#,
+        r#"{"host":"0.0.0.0","port":80}"#,
+    ] {
+        let res: StringOrSocketAddr = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+    }
+}
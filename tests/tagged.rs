@@ -0,0 +1,255 @@
+use serde::{Deserialize, Serialize};
+use serde_either::{
+    LenientVec, OneOf, SingleOrVec, StringOrStruct, StringOrStructOrVec, TaggedOrUntagged, Tree,
+};
+use std::net::IpAddr;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(untagged)]
+enum Untagged {
+    Point { x: i64, y: i64 },
+    Range { start: u64, end: u64 },
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(tag = "type")]
+enum Internal {
+    Circle { radius: f64 },
+    Square { side: u32 },
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(tag = "t", content = "c")]
+enum Adjacent {
+    Label(String),
+    Size(u8),
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Host {
+    addr: IpAddr,
+}
+
+fn cbor<T: Serialize>(value: &T) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    ciborium::into_writer(value, &mut bytes).unwrap();
+    bytes
+}
+
+#[test]
+fn untagged_inner_enum() {
+    let res: StringOrStruct<Untagged> = serde_json::from_str(r#"{"start": 1, "end": 2}"#).unwrap();
+
+    assert_eq!(
+        res,
+        StringOrStruct::Struct(Untagged::Range { start: 1, end: 2 })
+    );
+}
+
+#[test]
+fn internally_tagged_inner_enum() {
+    let res: StringOrStructOrVec<Internal, Vec<Internal>> = serde_json::from_str(
+        r#"[{"type": "Circle", "radius": 1.5}, {"type": "Square", "side": 3}]"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        res,
+        StringOrStructOrVec::Vec(vec![
+            Internal::Circle { radius: 1.5 },
+            Internal::Square { side: 3 }
+        ])
+    );
+}
+
+#[test]
+fn adjacently_tagged_inner_enum() {
+    let res: SingleOrVec<Adjacent> = serde_json::from_str(r#"{"t": "Size", "c": 200}"#).unwrap();
+
+    assert_eq!(res, SingleOrVec::Single(Adjacent::Size(200)));
+}
+
+#[test]
+fn internally_tagged_inner_enum_yaml() {
+    let res: StringOrStruct<Internal> = serde_yaml::from_str("type: Square\nside: 4").unwrap();
+
+    assert_eq!(res, StringOrStruct::Struct(Internal::Square { side: 4 }));
+}
+
+#[test]
+fn untagged_inner_enum_yaml() {
+    let res: StringOrStruct<Untagged> = serde_yaml::from_str("x: -1\ny: 2").unwrap();
+
+    assert_eq!(res, StringOrStruct::Struct(Untagged::Point { x: -1, y: 2 }));
+}
+
+#[test]
+fn tagged_inner_enum_cbor() {
+    let bytes = cbor(&Internal::Circle { radius: 2.0 });
+    let res: StringOrStruct<Internal> = ciborium::from_reader(&bytes[..]).unwrap();
+
+    assert_eq!(
+        res,
+        StringOrStruct::Struct(Internal::Circle { radius: 2.0 })
+    );
+}
+
+#[test]
+fn binary_representation_is_kept() {
+    // IpAddr serializes as an enum of octets when the format is not human readable
+    let host = Host {
+        addr: "10.0.0.1".parse().unwrap(),
+    };
+    let res: StringOrStruct<Host> = ciborium::from_reader(&cbor(&host)[..]).unwrap();
+
+    assert_eq!(res, StringOrStruct::Struct(host));
+}
+
+#[test]
+fn binary_representation_is_kept_in_vec() {
+    let hosts = vec![
+        Host {
+            addr: "::1".parse().unwrap(),
+        },
+        Host {
+            addr: "127.0.0.1".parse().unwrap(),
+        },
+    ];
+    let res: SingleOrVec<Host> = ciborium::from_reader(&cbor(&hosts)[..]).unwrap();
+
+    assert_eq!(res, SingleOrVec::Vec(hosts));
+}
+
+#[test]
+fn binary_representation_is_kept_in_lenient_vec() {
+    let addrs: Vec<IpAddr> = vec!["10.0.0.1".parse().unwrap(), "::1".parse().unwrap()];
+    let res: LenientVec<IpAddr> = ciborium::from_reader(&cbor(&addrs)[..]).unwrap();
+
+    assert!(res.is_clean(), "{:?}", res.errors());
+    assert_eq!(res.into_vec(), addrs);
+}
+
+#[test]
+fn binary_representation_is_kept_in_tree() {
+    let hosts = vec![vec![Host {
+        addr: "192.168.1.1".parse().unwrap(),
+    }]];
+    let res: Tree<Host> = ciborium::from_reader(&cbor(&hosts)[..]).unwrap();
+
+    assert_eq!(res.leaves().count(), 1);
+}
+
+#[test]
+fn binary_representation_is_kept_in_one_of() {
+    let host = Host {
+        addr: "10.1.2.3".parse().unwrap(),
+    };
+    let res: OneOf<(String, Host)> = ciborium::from_reader(&cbor(&host)[..]).unwrap();
+
+    assert_eq!(res.index(), 1);
+}
+
+mod tagged_or_untagged {
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    enum Shape {
+        Circle { radius: u32 },
+        Square { side: u32 },
+        Label(String),
+        Pair(u8, u8),
+        Empty,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Canvas {
+        #[serde(serialize_with = "TaggedOrUntagged::serialize_untagged")]
+        shape: TaggedOrUntagged<Shape>,
+    }
+
+    #[test]
+    fn both_forms() {
+        let res: Vec<TaggedOrUntagged<Shape>> = serde_json::from_str(
+            r#"[{"Circle": {"radius": 1}}, {"radius": 1}, "a", [1, 2], {"Empty": null}, null]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            res[0],
+            TaggedOrUntagged::Tagged(Shape::Circle { radius: 1 })
+        );
+        assert_eq!(
+            res[1],
+            TaggedOrUntagged::Untagged(Shape::Circle { radius: 1 })
+        );
+        assert_eq!(
+            res[2],
+            TaggedOrUntagged::Untagged(Shape::Label(String::from("a")))
+        );
+        assert_eq!(res[3].as_inner(), &Shape::Pair(1, 2));
+        assert_eq!(res[4], TaggedOrUntagged::Tagged(Shape::Empty));
+        assert_eq!(res[5], TaggedOrUntagged::Untagged(Shape::Empty));
+    }
+
+    #[test]
+    fn unit_variants_are_tagged() {
+        let res: TaggedOrUntagged<Shape> = serde_json::from_str(r#""Empty""#).unwrap();
+
+        assert_eq!(res, TaggedOrUntagged::Tagged(Shape::Empty));
+    }
+
+    #[test]
+    fn serialize_untagged() {
+        let res: Canvas = serde_json::from_str(r#"{"shape": {"Square": {"side": 2}}}"#).unwrap();
+
+        assert!(res.shape.is_tagged());
+        assert_eq!(
+            serde_json::to_string(&res).unwrap(),
+            r#"{"shape":{"side":2}}"#
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_tagged_forms() {
+        let err = serde_json::from_str::<TaggedOrUntagged<Shape>>(r#"{"Square": {"size": 2}}"#)
+            .unwrap_err();
+
+        assert_eq!(err.to_string(), "missing field `side`");
+    }
+
+    #[test]
+    fn rejects_other_types() {
+        let err = serde_json::from_str::<TaggedOrUntagged<Shape>>(r#"{"size": 2}"#).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "data did not match any variant, tagged or untagged"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        for json in &[
+            r#"{"Circle":{"radius":1}}"#,
+            r#"{"radius":1}"#,
+            r#"{"Pair":[1,2]}"#,
+            "[1,2]",
+            r#""a""#,
+            "null",
+        ] {
+            let res: TaggedOrUntagged<Shape> = serde_json::from_str(json).unwrap();
+
+            assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+        }
+    }
+
+    #[test]
+    fn binary_formats() {
+        let res: TaggedOrUntagged<Shape> =
+            ciborium::from_reader(&cbor(&Shape::Pair(1, 2))[..]).unwrap();
+        assert!(res.is_tagged());
+
+        let res: TaggedOrUntagged<Shape> = ciborium::from_reader(&cbor(&(1, 2))[..]).unwrap();
+        assert_eq!(res, TaggedOrUntagged::Untagged(Shape::Pair(1, 2)));
+    }
+}
@@ -0,0 +1,122 @@
+#![cfg(any(feature = "chrono", feature = "time"))]
+
+use serde_either::TimestampOrString;
+
+#[cfg(feature = "chrono")]
+mod chrono_timestamps {
+    use super::*;
+    use chrono::{DateTime, TimeZone, Utc};
+    use serde::Serialize;
+    use serde_either::Timestamp;
+
+    type Field = TimestampOrString<DateTime<Utc>>;
+
+    #[test]
+    fn rfc3339_strings() {
+        let res: Field = serde_json::from_str(r#""2024-05-01T14:00:00+02:00""#).unwrap();
+
+        assert_eq!(
+            res,
+            TimestampOrString::String(Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn epoch_numbers() {
+        let res: Field = serde_json::from_str("1714564800").unwrap();
+        assert_eq!(
+            res.into_timestamp(),
+            Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap()
+        );
+
+        let res: Field = serde_json::from_str("-1.5").unwrap();
+        assert_eq!(res.timestamp().unix_nanos(), -1_500_000_000);
+    }
+
+    #[test]
+    fn rejects_invalid_timestamps() {
+        let err = serde_json::from_str::<Field>(r#""yesterday""#).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("invalid timestamp \"yesterday\": "));
+
+        let err = serde_json::from_str::<Field>("1e300").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid value: floating point `1e+300`, expected a timestamp in range at line 1 column 5"
+        );
+
+        assert!(serde_json::from_str::<Field>("true").is_err());
+    }
+
+    #[test]
+    fn round_trip() {
+        for json in &[r#""2024-05-01T12:00:00Z""#, "1714564800", "1714564800.5"] {
+            let res: Field = serde_json::from_str(json).unwrap();
+
+            assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+        }
+    }
+
+    #[test]
+    fn configured_serialization() {
+        #[derive(Serialize)]
+        struct Event {
+            #[serde(serialize_with = "TimestampOrString::serialize_as_string")]
+            created: Field,
+            #[serde(serialize_with = "TimestampOrString::serialize_as_number")]
+            updated: Field,
+        }
+
+        let event = Event {
+            created: serde_json::from_str("1714564800").unwrap(),
+            updated: serde_json::from_str(r#""2024-05-01T12:00:00Z""#).unwrap(),
+        };
+
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"created":"2024-05-01T12:00:00Z","updated":1714564800}"#
+        );
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_timestamps {
+    use super::*;
+    use time::OffsetDateTime;
+
+    type Field = TimestampOrString<OffsetDateTime>;
+
+    #[test]
+    fn strings_and_numbers() {
+        let text: Field = serde_json::from_str(r#""2024-05-01T12:00:00Z""#).unwrap();
+        let epoch: Field = serde_json::from_str("1714564800").unwrap();
+
+        assert_eq!(text.timestamp(), epoch.timestamp());
+        assert_eq!(epoch.timestamp().unix_timestamp(), 1_714_564_800);
+    }
+
+    #[test]
+    fn round_trip() {
+        for json in &[r#""2024-05-01T12:00:00Z""#, "1714564800", "0.25"] {
+            let res: Field = serde_json::from_str(json).unwrap();
+
+            assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+        }
+    }
+
+    #[test]
+    fn out_of_range_strings_are_errors() {
+        // year -1199, which RFC 3339 cannot write
+        let res: Field = serde_json::from_str("-100000000000").unwrap();
+
+        assert_eq!(serde_json::to_string(&res).unwrap(), "-100000000000");
+
+        let err = Field::serialize_as_string(&res, serde_json::value::Serializer).unwrap_err();
+        assert!(
+            err.to_string().starts_with("invalid timestamp: "),
+            "{}",
+            err
+        );
+    }
+}
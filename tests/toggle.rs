@@ -0,0 +1,176 @@
+mod common;
+
+use crate::common::SimpleStruct;
+use serde::Deserialize;
+use serde_either::{BoolOrStruct, CountOrBool, StringOrBool};
+
+#[derive(Deserialize, Debug)]
+struct Config {
+    minify: BoolOrStruct<SimpleStruct>,
+}
+
+#[test]
+fn booleans() {
+    let res: BoolOrStruct<SimpleStruct> = serde_json::from_str("true").unwrap();
+    assert_eq!(res, BoolOrStruct::Bool(true));
+    assert!(res.is_enabled());
+
+    let res: BoolOrStruct<SimpleStruct> = serde_json::from_str("false").unwrap();
+    assert_eq!(res.as_bool(), Some(false));
+    assert!(!res.is_enabled());
+}
+
+#[test]
+fn structs() {
+    let res: Config = serde_yaml::from_str("minify:\n  number: 1\n  text: a\n").unwrap();
+
+    assert_eq!(
+        res.minify,
+        BoolOrStruct::Struct(SimpleStruct {
+            number: 1,
+            text: String::from("a")
+        })
+    );
+    assert!(res.minify.is_enabled());
+    assert_eq!(res.minify.as_bool(), None);
+}
+
+#[test]
+fn rejects_other_types() {
+    let err = serde_json::from_str::<BoolOrStruct<SimpleStruct>>(r#""yes""#).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid type: string \"yes\", expected a boolean or a map"
+    );
+
+    assert!(serde_json::from_str::<BoolOrStruct<SimpleStruct>>("1").is_err());
+    assert!(serde_json::from_str::<BoolOrStruct<Vec<u8>>>("[1]").is_err());
+}
+
+#[test]
+fn invalid_struct() {
+    assert!(serde_json::from_str::<BoolOrStruct<SimpleStruct>>(r#"{"number": "a"}"#).is_err());
+}
+
+#[test]
+fn round_trip() {
+    for json in &["true", r#"{"number":1,"text":"a"}"#] {
+        let res: BoolOrStruct<SimpleStruct> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+    }
+}
+
+mod string_or_bool {
+    use super::*;
+
+    #[derive(Deserialize, Debug)]
+    struct Settings {
+        color: StringOrBool,
+        pager: StringOrBool,
+    }
+
+    #[test]
+    fn yaml_settings() {
+        let res: Settings = serde_yaml::from_str("color: auto\npager: false\n").unwrap();
+
+        assert_eq!(res.color, StringOrBool::String(String::from("auto")));
+        assert_eq!(res.pager, StringOrBool::Bool(false));
+    }
+
+    #[test]
+    fn cache_setting() {
+        #[derive(Deserialize)]
+        struct Config {
+            cache: StringOrBool,
+        }
+
+        let res: Config = serde_json::from_str(r#"{"cache": true}"#).unwrap();
+        assert_eq!(res.cache.as_bool(), Some(true));
+
+        let res: Config = serde_json::from_str(r#"{"cache": "aggressive"}"#).unwrap();
+        assert_eq!(res.cache.as_str(), Some("aggressive"));
+    }
+
+    #[test]
+    fn quoted_boolean_stays_a_string() {
+        let res: StringOrBool = serde_json::from_str(r#""true""#).unwrap();
+
+        assert_eq!(res.as_str(), Some("true"));
+        assert_eq!(res.as_bool(), None);
+    }
+
+    #[test]
+    fn rejects_other_types() {
+        let err = serde_json::from_str::<StringOrBool>("1").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid type: integer `1`, expected a string or a boolean at line 1 column 1"
+        );
+
+        assert!(serde_json::from_str::<StringOrBool>(r#"{"a": true}"#).is_err());
+    }
+
+    #[test]
+    fn round_trip() {
+        for json in &["true", r#""auto""#] {
+            let res: StringOrBool = serde_json::from_str(json).unwrap();
+
+            assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+            assert_eq!(res.to_string(), json.trim_matches('"'));
+        }
+    }
+}
+
+mod count_or_bool {
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct Job {
+        retries: CountOrBool,
+    }
+
+    #[test]
+    fn yaml_jobs() {
+        let res: Job = serde_yaml::from_str("retries: false").unwrap();
+        assert_eq!(res.retries, CountOrBool::Bool(false));
+        assert!(!res.retries.is_enabled());
+
+        let res: Job = serde_yaml::from_str("retries: 5").unwrap();
+        assert_eq!(res.retries, CountOrBool::Count(5));
+        assert_eq!(res.retries.as_count(), Some(5));
+    }
+
+    #[test]
+    fn to_count() {
+        assert_eq!(CountOrBool::Bool(false).to_count(3), 0);
+        assert_eq!(CountOrBool::Bool(true).to_count(3), 3);
+        assert_eq!(CountOrBool::Count(0).to_count(3), 0);
+        assert!(!CountOrBool::Count(0).is_enabled());
+    }
+
+    #[test]
+    fn rejects_negative_counts() {
+        let err = serde_json::from_str::<CountOrBool>("-1").unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "invalid value: integer `-1`, expected a non-negative integer or a boolean at line 1 column 2"
+        );
+    }
+
+    #[test]
+    fn rejects_other_types() {
+        assert!(serde_json::from_str::<CountOrBool>("1.5").is_err());
+        assert!(serde_json::from_str::<CountOrBool>(r#""5""#).is_err());
+    }
+
+    #[test]
+    fn round_trip() {
+        for json in &["false", "5"] {
+            let res: CountOrBool = serde_json::from_str(json).unwrap();
+
+            assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+        }
+    }
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use crate::common::SimpleStruct;
+use serde::Deserialize;
+use serde_either::{DispatchPolicy, Kind, StringOrStruct, StringOrStructOrVec, Warned, Warning};
+use toml::value::Datetime;
+
+#[derive(Deserialize, Debug)]
+struct Dates {
+    datetime: StringOrStruct<Datetime>,
+    value: StringOrStruct<toml::Value>,
+    other: StringOrStructOrVec<SimpleStruct, Vec<Datetime>>,
+}
+
+#[test]
+fn datetimes_reach_the_struct_arm() {
+    let res: Dates = toml::from_str(
+        r#"
+        datetime = 1979-05-27T07:32:00Z
+        value = 1979-05-27
+        other = [07:32:00]
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        res.datetime,
+        StringOrStruct::Struct("1979-05-27T07:32:00Z".parse().unwrap())
+    );
+    assert_eq!(
+        res.value,
+        StringOrStruct::Struct(toml::Value::Datetime("1979-05-27".parse().unwrap()))
+    );
+    assert_eq!(
+        res.other,
+        StringOrStructOrVec::Vec(vec!["07:32:00".parse().unwrap()])
+    );
+}
+
+#[test]
+fn datetimes_fall_back_to_the_string_arm() {
+    #[derive(Deserialize, Debug)]
+    struct Config {
+        at: StringOrStruct<SimpleStruct>,
+    }
+
+    let res: Warned<Config> = toml::from_str("at = 1979-05-27").unwrap();
+
+    assert_eq!(
+        res.value.at,
+        StringOrStruct::String(String::from("1979-05-27"))
+    );
+    assert_eq!(
+        res.warnings,
+        vec![Warning::DatetimeAsString, Warning::StringForm]
+    );
+}
+
+#[test]
+fn datetimes_use_the_string_arm_when_struct_is_denied() {
+    let policy = DispatchPolicy::new().deny(Kind::Struct);
+    let deserializer = toml::de::ValueDeserializer::new("1979-05-27");
+
+    let res = serde::de::DeserializeSeed::deserialize(
+        policy.seed::<StringOrStruct<Datetime>>(),
+        deserializer,
+    )
+    .unwrap();
+
+    assert_eq!(res, StringOrStruct::String(String::from("1979-05-27")));
+}
+
+#[test]
+fn peek_datetimes_as_string_when_struct_is_denied() {
+    let policy = DispatchPolicy::new().deny(Kind::Struct);
+    let deserializer = toml::de::ValueDeserializer::new("1979-05-27");
+
+    assert_eq!(policy.peek_kind(deserializer).unwrap(), Kind::String);
+}
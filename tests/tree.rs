@@ -0,0 +1,83 @@
+mod common;
+
+use crate::common::SimpleStruct;
+use serde_either::{StringOrStructOrVec, Tree};
+
+fn leaf(text: &str) -> Tree<SimpleStruct> {
+    Tree(StringOrStructOrVec::String(String::from(text)))
+}
+
+#[test]
+fn string_leaf() {
+    let res: Tree<SimpleStruct> = serde_json::from_str(r#""a""#).unwrap();
+
+    assert_eq!(res, leaf("a"));
+    assert_eq!(res.depth(), 0);
+}
+
+#[test]
+fn nested_vecs() {
+    let res: Tree<SimpleStruct> =
+        serde_json::from_str(r#"["a", {"number": 1, "text": "b"}, ["c", ["d"]]]"#).unwrap();
+
+    assert_eq!(
+        res,
+        Tree(StringOrStructOrVec::Vec(vec![
+            leaf("a"),
+            Tree(StringOrStructOrVec::Struct(SimpleStruct {
+                number: 1,
+                text: String::from("b")
+            })),
+            Tree(StringOrStructOrVec::Vec(vec![
+                leaf("c"),
+                Tree(StringOrStructOrVec::Vec(vec![leaf("d")]))
+            ])),
+        ]))
+    );
+    assert_eq!(res.depth(), 3);
+}
+
+#[test]
+fn leaves_are_depth_first() {
+    let res: Tree<SimpleStruct> = serde_json::from_str(r#"[["a", "b"], "c", [["d"]]]"#).unwrap();
+
+    let leaves: Vec<_> = res.leaves().collect();
+
+    assert_eq!(leaves, vec![&leaf("a"), &leaf("b"), &leaf("c"), &leaf("d")]);
+}
+
+#[test]
+fn invalid_leaf() {
+    let res: serde_json::Result<Tree<SimpleStruct>> = serde_json::from_str(r#"["a", [true]]"#);
+
+    assert!(res.is_err());
+}
+
+#[test]
+fn round_trip() {
+    let json = r#"["a",{"number":1,"text":"b"},["c"]]"#;
+
+    let res: Tree<SimpleStruct> = serde_json::from_str(json).unwrap();
+
+    assert_eq!(serde_json::to_string(&res).unwrap(), json);
+}
+
+#[test]
+fn boxed_recursion_in_struct_arm() {
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    struct Node {
+        name: String,
+        child: Option<serde_either::StringOrStruct<Box<Node>>>,
+    }
+
+    let res: Node =
+        serde_json::from_str(r#"{"name": "a", "child": {"name": "b", "child": "c"}}"#).unwrap();
+
+    assert_eq!(
+        res.child.unwrap(),
+        serde_either::StringOrStruct::Struct(Box::new(Node {
+            name: String::from("b"),
+            child: Some(serde_either::StringOrStruct::String(String::from("c")))
+        }))
+    );
+}
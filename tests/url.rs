@@ -0,0 +1,54 @@
+#![cfg(feature = "url")]
+
+use serde::{Deserialize, Serialize};
+use serde_either::UrlOrStruct;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Repository {
+    #[serde(rename = "type")]
+    kind: String,
+    url: String,
+}
+
+#[test]
+fn url_string() {
+    let res: UrlOrStruct<Repository> = serde_json::from_str(r#""https://github.com/a/b""#).unwrap();
+
+    assert_eq!(
+        res,
+        UrlOrStruct::Url("https://github.com/a/b".parse().unwrap())
+    );
+}
+
+#[test]
+fn structured() {
+    let res: UrlOrStruct<Repository> =
+        serde_json::from_str(r#"{"type": "git", "url": "git+https://github.com/a/b"}"#).unwrap();
+
+    assert_eq!(
+        res.as_struct(),
+        Some(&Repository {
+            kind: String::from("git"),
+            url: String::from("git+https://github.com/a/b"),
+        })
+    );
+}
+
+#[test]
+fn rejects_invalid_urls() {
+    let err = serde_json::from_str::<UrlOrStruct<Repository>>(r#""github.com/a/b""#).unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "invalid URL \"github.com/a/b\": relative URL without a base"
+    );
+}
+
+#[test]
+fn round_trip() {
+    for json in &[r#""https://github.com/a/b""#, r#"{"type":"git","url":"x"}"#] {
+        let res: UrlOrStruct<Repository> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+    }
+}
@@ -0,0 +1,63 @@
+#![cfg(feature = "uuid")]
+
+use serde_either::UuidStringOrBytes;
+use uuid::Uuid;
+
+const TEXT: &str = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+
+fn uuid() -> Uuid {
+    Uuid::parse_str(TEXT).unwrap()
+}
+
+#[test]
+fn strings() {
+    let res: UuidStringOrBytes = serde_json::from_str(&format!("{:?}", TEXT)).unwrap();
+    assert_eq!(*res, uuid());
+
+    let res: UuidStringOrBytes =
+        serde_json::from_str(r#""67E5504410B1426F9247BB680E5FE0C8""#).unwrap();
+    assert_eq!(res.into_inner(), uuid());
+}
+
+#[test]
+fn byte_sequences() {
+    let json = serde_json::to_string(uuid().as_bytes()).unwrap();
+
+    let res: UuidStringOrBytes = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(*res, uuid());
+}
+
+#[test]
+fn binary_round_trip() {
+    let mut cbor = Vec::new();
+    ciborium::into_writer(&UuidStringOrBytes(uuid()), &mut cbor).unwrap();
+
+    // a 16-byte string: one header byte and the bytes themselves
+    assert_eq!(cbor.len(), 17);
+    let res: UuidStringOrBytes = ciborium::from_reader(&cbor[..]).unwrap();
+    assert_eq!(*res, uuid());
+}
+
+#[test]
+fn human_readable_round_trip() {
+    let json = format!("{:?}", TEXT);
+
+    let res: UuidStringOrBytes = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(serde_json::to_string(&res).unwrap(), json);
+}
+
+#[test]
+fn rejects_invalid_uuids() {
+    let err = serde_json::from_str::<UuidStringOrBytes>("[1, 2]").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid length 2, expected a UUID string or 16 bytes at line 1 column 6"
+    );
+
+    let err = serde_json::from_str::<UuidStringOrBytes>(r#""not-a-uuid""#).unwrap_err();
+    assert!(err.to_string().starts_with("invalid UUID \"not-a-uuid\": "));
+
+    assert!(serde_json::from_str::<UuidStringOrBytes>("1").is_err());
+}
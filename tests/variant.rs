@@ -0,0 +1,63 @@
+mod common;
+
+use crate::common::SimpleStruct;
+use serde::{Deserialize, Serialize};
+use serde_either::VariantNameOrStruct;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum Mode {
+    Fast,
+    Safe,
+}
+
+type Setting = VariantNameOrStruct<Mode, SimpleStruct>;
+
+#[derive(Deserialize)]
+struct Config {
+    mode: Setting,
+}
+
+#[test]
+fn names_and_structs() {
+    let res: Config = serde_yaml::from_str("mode: safe").unwrap();
+    assert_eq!(res.mode, VariantNameOrStruct::Variant(Mode::Safe));
+
+    let res: Config = serde_yaml::from_str("mode:\n  number: 1\n  text: a\n").unwrap();
+    assert_eq!(
+        res.mode.as_struct(),
+        Some(&SimpleStruct {
+            number: 1,
+            text: String::from("a")
+        })
+    );
+}
+
+#[test]
+fn rejects_unknown_names() {
+    let err = serde_json::from_str::<Setting>(r#""slow""#).unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "unknown variant `slow`, expected `fast` or `safe`"
+    );
+}
+
+#[test]
+fn rejects_other_types() {
+    let err = serde_json::from_str::<Setting>("[]").unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "invalid type: sequence, expected a variant name or a map"
+    );
+}
+
+#[test]
+fn round_trip() {
+    for json in &[r#""fast""#, r#"{"number":1,"text":"a"}"#] {
+        let res: Setting = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+    }
+}
@@ -0,0 +1,67 @@
+#![cfg(feature = "semver")]
+
+use semver::{Version, VersionReq};
+use serde::Deserialize;
+use serde_either::{DependencyDetail, VersionOrStruct};
+use std::collections::BTreeMap;
+
+#[derive(Deserialize)]
+struct Manifest {
+    dependencies: BTreeMap<String, VersionOrStruct>,
+}
+
+#[test]
+fn manifest_dependencies() {
+    let res: Manifest = toml::from_str(
+        r#"
+        [dependencies]
+        serde = { version = "1.0", features = ["derive"], optional = true }
+        serde-value = "0.7"
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        res.dependencies["serde"],
+        VersionOrStruct::Struct(DependencyDetail {
+            version: Some(VersionReq::parse("1.0").unwrap()),
+            features: vec![String::from("derive")],
+            optional: true,
+        })
+    );
+    assert_eq!(
+        res.dependencies["serde-value"].version(),
+        Some(&VersionReq::parse("0.7").unwrap())
+    );
+}
+
+#[test]
+fn exact_versions() {
+    let res: VersionOrStruct<DependencyDetail, Version> =
+        serde_json::from_str(r#""1.2.3""#).unwrap();
+
+    assert_eq!(res, VersionOrStruct::Version(Version::new(1, 2, 3)));
+
+    let err =
+        serde_json::from_str::<VersionOrStruct<DependencyDetail, Version>>(r#""1.2""#).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid version \"1.2\": unexpected end of input while parsing minor version number"
+    );
+}
+
+#[test]
+fn rejects_invalid_requirements() {
+    let err = serde_json::from_str::<VersionOrStruct>(r#""one""#).unwrap_err();
+
+    assert!(err.to_string().starts_with("invalid version \"one\": "));
+}
+
+#[test]
+fn round_trip() {
+    for json in &[r#""^1.0""#, r#"{"version":"^1.0","features":["derive"]}"#] {
+        let res: VersionOrStruct = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_string(&res).unwrap(), *json);
+    }
+}
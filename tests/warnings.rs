@@ -0,0 +1,160 @@
+mod common;
+
+use crate::common::SimpleStruct;
+use serde::de::{DeserializeSeed, Deserializer};
+use serde::Deserialize;
+use serde_either::{
+    DispatchPolicy, SingleOrVec, StringOrStruct, StringOrStructOrVec, ValueOr, Warned, Warning,
+    WarningSeed,
+};
+use std::collections::HashMap;
+use std::panic::catch_unwind;
+
+#[derive(Deserialize, Debug)]
+struct Config {
+    name: StringOrStruct<SimpleStruct>,
+    tags: SingleOrVec<String>,
+}
+
+#[test]
+fn no_warnings() {
+    let res: Warned<Config> =
+        serde_json::from_str(r#"{"name": {"number": 1, "text": "a"}, "tags": "b"}"#).unwrap();
+
+    assert!(res.warnings.is_empty());
+}
+
+#[test]
+fn nested_warnings() {
+    let res: Warned<Config> = serde_json::from_str(r#"{"name": "a", "tags": []}"#).unwrap();
+
+    assert_eq!(res.warnings, vec![Warning::StringForm, Warning::EmptyVec]);
+    assert_eq!(res.value.name, StringOrStruct::String(String::from("a")));
+    assert_eq!(res.value.tags, SingleOrVec::Vec(vec![]));
+}
+
+#[test]
+fn policy_coercions() {
+    let policy = DispatchPolicy::new()
+        .numbers_as_string(true)
+        .seq_of_one_as_struct(true);
+
+    let res =
+        WarningSeed::new(policy.seed::<StringOrStructOrVec<SimpleStruct, Vec<SimpleStruct>>>())
+            .deserialize(&mut serde_json::Deserializer::from_str("7"))
+            .unwrap();
+
+    assert_eq!(res.value, StringOrStructOrVec::String(String::from("7")));
+    assert_eq!(
+        res.warnings,
+        vec![Warning::NumberAsString, Warning::StringForm]
+    );
+
+    let res = WarningSeed::new(policy.seed::<SingleOrVec<u8>>())
+        .deserialize(&mut serde_json::Deserializer::from_str("[1]"))
+        .unwrap();
+
+    assert_eq!(res.value, SingleOrVec::Single(1));
+    assert_eq!(res.warnings, vec![Warning::SeqOfOneAsStruct]);
+}
+
+#[test]
+fn nested_collections_report_to_outer() {
+    let res: Warned<Vec<Warned<StringOrStruct<SimpleStruct>>>> =
+        serde_json::from_str(r#"["a", "b"]"#).unwrap();
+
+    assert_eq!(res.value[0].warnings, vec![Warning::StringForm]);
+    assert_eq!(res.warnings, vec![Warning::StringForm, Warning::StringForm]);
+}
+
+#[test]
+fn warnings_are_not_collected_outside_of_warned() {
+    let _: StringOrStruct<SimpleStruct> = serde_json::from_str(r#""a""#).unwrap();
+
+    let res: Warned<StringOrStruct<SimpleStruct>> =
+        serde_json::from_str(r#"{"number": 1, "text": "a"}"#).unwrap();
+
+    assert!(res.warnings.is_empty());
+}
+
+// raises a warning, then panics
+struct Panics;
+
+impl<'de> Deserialize<'de> for Panics {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        StringOrStruct::<SimpleStruct>::deserialize(deserializer)?;
+        panic!("deserializing Panics");
+    }
+}
+
+// catches a panicking collection, then reads a string
+struct CatchesPanic;
+
+impl<'de> DeserializeSeed<'de> for CatchesPanic {
+    type Value = StringOrStruct<SimpleStruct>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let res = catch_unwind(|| serde_json::from_str::<Warned<Panics>>(r#""a""#));
+        assert!(res.is_err());
+
+        StringOrStruct::deserialize(deserializer)
+    }
+}
+
+#[test]
+fn panics_restore_the_outer_collection() {
+    let res = WarningSeed::new(CatchesPanic)
+        .deserialize(&mut serde_json::Deserializer::from_str(r#""b""#))
+        .unwrap();
+
+    assert_eq!(res.warnings, vec![Warning::StringForm]);
+}
+
+type Pair = StringOrStructOrVec<HashMap<String, String>, [u8; 2]>;
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(untagged)]
+enum Fallback {
+    Either(Pair),
+    Bytes(Vec<u8>),
+}
+
+#[test]
+fn failed_untagged_attempts_leave_no_warnings() {
+    let res: Warned<Fallback> = serde_json::from_str("[]").unwrap();
+
+    assert_eq!(res.value, Fallback::Bytes(vec![]));
+    assert!(res.warnings.is_empty(), "{:?}", res.warnings);
+
+    let res: Warned<Fallback> = serde_json::from_str(r#""a""#).unwrap();
+
+    assert_eq!(
+        res.value,
+        Fallback::Either(StringOrStructOrVec::String(String::from("a")))
+    );
+    assert_eq!(res.warnings, vec![Warning::StringForm]);
+}
+
+#[test]
+fn failed_fallback_attempts_leave_no_warnings() {
+    let res: Warned<ValueOr<Pair, Vec<u8>>> = serde_json::from_str("[]").unwrap();
+
+    assert_eq!(res.value, ValueOr::Other(vec![]));
+    assert!(res.warnings.is_empty(), "{:?}", res.warnings);
+}
+
+struct Peeks;
+
+impl<'de> Deserialize<'de> for Peeks {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        serde_either::peek_kind(deserializer)?;
+        Ok(Peeks)
+    }
+}
+
+#[test]
+fn peek_kind_records_no_warnings() {
+    let res: Warned<Peeks> = serde_json::from_str(r#""a""#).unwrap();
+
+    assert!(res.warnings.is_empty(), "{:?}", res.warnings);
+}
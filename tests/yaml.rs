@@ -0,0 +1,166 @@
+mod common;
+
+use crate::common::Person;
+use serde::de::value::{EnumAccessDeserializer, Error, StrDeserializer};
+use serde::de::{
+    DeserializeSeed, EnumAccess, Error as _, IntoDeserializer, VariantAccess, Visitor,
+};
+use serde::Deserialize;
+use serde_either::{SingleOrVec, StringOrStruct, StringOrStructOrVec};
+
+#[derive(Deserialize, Debug, PartialEq)]
+enum Temperature {
+    Celsius(f64),
+    Fahrenheit(f64),
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+enum Author {
+    Person(Person),
+    Anonymous(()),
+}
+
+#[test]
+fn tagged_scalar() {
+    let res: StringOrStruct<Temperature> = serde_yaml::from_str("!Celsius 21.5").unwrap();
+
+    assert_eq!(res, StringOrStruct::Struct(Temperature::Celsius(21.5)));
+}
+
+#[test]
+fn tagged_map() {
+    let res: StringOrStructOrVec<Author, Vec<Author>> = serde_yaml::from_str(
+        "!Person
+        first_name: Bob
+        last_name: Smith",
+    )
+    .unwrap();
+
+    assert_eq!(
+        res,
+        StringOrStructOrVec::Struct(Author::Person(Person {
+            first_name: String::from("Bob"),
+            last_name: String::from("Smith")
+        }))
+    );
+}
+
+#[test]
+fn tagged_values_in_vec() {
+    let res: SingleOrVec<Temperature> =
+        serde_yaml::from_str("- !Celsius 20\n- !Fahrenheit 70").unwrap();
+
+    assert_eq!(
+        res,
+        SingleOrVec::Vec(vec![
+            Temperature::Celsius(20.0),
+            Temperature::Fahrenheit(70.0)
+        ])
+    );
+}
+
+#[test]
+fn tagged_unit() {
+    let res: SingleOrVec<Author> = serde_yaml::from_str("!Anonymous").unwrap();
+
+    assert_eq!(res, SingleOrVec::Single(Author::Anonymous(())));
+}
+
+#[test]
+fn untagged_string() {
+    let res: StringOrStruct<Temperature> = serde_yaml::from_str("warm").unwrap();
+
+    assert_eq!(res, StringOrStruct::String(String::from("warm")));
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+enum Shape {
+    Empty,
+    Circle { radius: u32 },
+    Pair(u8, u8),
+}
+
+#[test]
+fn tagged_variants_of_every_shape() {
+    let res: Vec<SingleOrVec<Shape>> = serde_yaml::from_str(
+        "- !Empty
+- !Circle {radius: 1}
+- !Pair [1, 2]",
+    )
+    .unwrap();
+
+    assert_eq!(
+        res,
+        vec![
+            SingleOrVec::Single(Shape::Empty),
+            SingleOrVec::Single(Shape::Circle { radius: 1 }),
+            SingleOrVec::Single(Shape::Pair(1, 2)),
+        ]
+    );
+}
+
+// enum input that only supports unit variants, as formats without content
+// for their tags provide it
+#[test]
+fn unit_only_variants() {
+    let deserializer = EnumAccessDeserializer::new(StrDeserializer::<Error>::new("Empty"));
+
+    let res = SingleOrVec::<Shape>::deserialize(deserializer).unwrap();
+
+    assert_eq!(res, SingleOrVec::Single(Shape::Empty));
+}
+
+// unit-only enum input whose errors carry the location of the input, as
+// serde_json and serde_yaml add it to their messages
+struct LocatedUnitVariant(&'static str);
+
+impl<'de> EnumAccess<'de> for LocatedUnitVariant {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self), Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let tag = seed.deserialize(self.0.into_deserializer())?;
+        Ok((tag, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for LocatedUnitVariant {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, _seed: T) -> Result<T::Value, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        Err(Error::custom(
+            "invalid type: unit variant, expected newtype variant at line 2 column 3",
+        ))
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, _visitor: V) -> Result<V::Value, Error> {
+        Err(Error::custom("unit variant at line 2 column 3"))
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Error> {
+        Err(Error::custom("unit variant at line 2 column 3"))
+    }
+}
+
+#[test]
+fn unit_only_variants_with_located_errors() {
+    let deserializer = EnumAccessDeserializer::new(LocatedUnitVariant("Empty"));
+
+    let res = SingleOrVec::<Shape>::deserialize(deserializer).unwrap();
+
+    assert_eq!(res, SingleOrVec::Single(Shape::Empty));
+}